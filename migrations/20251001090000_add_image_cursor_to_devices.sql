@@ -0,0 +1,2 @@
+ALTER TABLE devices
+ADD COLUMN image_cursor INTEGER DEFAULT 0 NOT NULL;
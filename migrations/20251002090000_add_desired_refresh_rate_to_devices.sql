@@ -0,0 +1,2 @@
+ALTER TABLE devices
+ADD COLUMN desired_refresh_rate INTEGER;
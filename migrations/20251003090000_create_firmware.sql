@@ -0,0 +1,10 @@
+CREATE TABLE firmware (
+    version TEXT NOT NULL PRIMARY KEY,
+    checksum TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    data BLOB NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+ALTER TABLE devices
+ADD COLUMN target_fw_version TEXT;
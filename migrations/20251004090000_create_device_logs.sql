@@ -0,0 +1,18 @@
+CREATE TABLE device_logs (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    device_id TEXT NOT NULL REFERENCES devices (id) ON DELETE CASCADE,
+    created_at INTEGER NOT NULL,
+    received_at INTEGER NOT NULL,
+    level TEXT NOT NULL,
+    message TEXT NOT NULL,
+    source_file TEXT,
+    source_line INTEGER,
+    wake_reason TEXT,
+    battery_voltage REAL,
+    wifi_status TEXT,
+    wifi_rssi INTEGER,
+    fw_version TEXT,
+    additional_info TEXT
+);
+
+CREATE INDEX device_logs_device_id_created_at ON device_logs (device_id, created_at);
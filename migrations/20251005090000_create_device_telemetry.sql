@@ -0,0 +1,11 @@
+CREATE TABLE device_telemetry (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    device_id TEXT NOT NULL REFERENCES devices (id) ON DELETE CASCADE,
+    recorded_at INTEGER NOT NULL,
+    rssi INTEGER,
+    battery_voltage REAL,
+    fw_version TEXT,
+    refresh_rate INTEGER
+);
+
+CREATE INDEX device_telemetry_device_id_recorded_at ON device_telemetry (device_id, recorded_at);
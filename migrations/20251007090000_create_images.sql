@@ -0,0 +1,7 @@
+CREATE TABLE images (
+    id TEXT NOT NULL PRIMARY KEY,
+    content_type TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    data BLOB NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
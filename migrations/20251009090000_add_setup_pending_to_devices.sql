@@ -0,0 +1,3 @@
+-- Set when the API key is rotated so the next setup call for the device hands out the new key
+ALTER TABLE devices
+ADD COLUMN setup_pending BOOLEAN NOT NULL DEFAULT FALSE;
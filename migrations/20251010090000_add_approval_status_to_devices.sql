@@ -0,0 +1,2 @@
+-- Devices registered before approval existed stay approved
+ALTER TABLE devices ADD COLUMN approval_status TEXT NOT NULL DEFAULT 'approved';
@@ -0,0 +1,9 @@
+CREATE TABLE device_commands (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    device_id TEXT NOT NULL REFERENCES devices (id) ON DELETE CASCADE,
+    command_json TEXT NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch()),
+    delivered_at INTEGER
+);
+
+CREATE INDEX device_commands_device_id_delivered_at ON device_commands (device_id, delivered_at);
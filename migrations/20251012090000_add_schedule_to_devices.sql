@@ -0,0 +1,2 @@
+ALTER TABLE devices ADD COLUMN schedule_json TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE devices ADD COLUMN timezone TEXT;
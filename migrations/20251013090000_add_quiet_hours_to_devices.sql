@@ -0,0 +1 @@
+ALTER TABLE devices ADD COLUMN quiet_hours_json TEXT;
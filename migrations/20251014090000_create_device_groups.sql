@@ -0,0 +1,12 @@
+CREATE TABLE device_groups (
+    id TEXT NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    images_json TEXT NOT NULL DEFAULT '[]',
+    desired_refresh_rate INTEGER,
+    target_fw_version TEXT,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
+
+ALTER TABLE devices ADD COLUMN group_id TEXT REFERENCES device_groups (id) ON DELETE SET NULL;
+
+CREATE INDEX devices_group_id ON devices (group_id);
@@ -0,0 +1,2 @@
+ALTER TABLE devices ADD COLUMN first_seen_at INTEGER;
+ALTER TABLE devices ADD COLUMN last_seen_at INTEGER;
@@ -0,0 +1,13 @@
+CREATE TABLE alerts (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    device_id TEXT NOT NULL REFERENCES devices (id) ON DELETE CASCADE,
+    rule TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'firing',
+    message TEXT NOT NULL,
+    fired_at INTEGER NOT NULL,
+    resolved_at INTEGER
+);
+
+-- A rule fires at most once per device until it is resolved
+CREATE UNIQUE INDEX alerts_device_id_rule_firing ON alerts (device_id, rule) WHERE status = 'firing';
+CREATE INDEX alerts_fired_at ON alerts (fired_at);
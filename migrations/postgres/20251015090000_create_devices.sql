@@ -0,0 +1,23 @@
+-- Mirrors the devices table built up by the SQLite migrations. Device groups are stored in
+-- SQLite, so group_id is not a foreign key here.
+CREATE TABLE devices (
+    id TEXT NOT NULL PRIMARY KEY,
+    mac TEXT,
+    api_key TEXT NOT NULL UNIQUE,
+    rssi BIGINT,
+    battery_voltage DOUBLE PRECISION,
+    fw_version TEXT,
+    refresh_rate BIGINT,
+    images_json TEXT NOT NULL DEFAULT '[]',
+    image_cursor BIGINT NOT NULL DEFAULT 0,
+    desired_refresh_rate BIGINT,
+    target_fw_version TEXT,
+    setup_pending BOOLEAN NOT NULL DEFAULT FALSE,
+    approval_status TEXT NOT NULL DEFAULT 'approved',
+    schedule_json TEXT NOT NULL DEFAULT '[]',
+    timezone TEXT,
+    quiet_hours_json TEXT,
+    group_id TEXT
+);
+
+CREATE INDEX devices_group_id ON devices (group_id);
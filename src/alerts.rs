@@ -0,0 +1,178 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use tracing::{instrument, warn};
+
+use crate::{
+    config::AlertSettings,
+    models::{Alert, AlertRule, ApprovalStatus, Device},
+    notifier::NotifierRef,
+    repositories::{alert::AlertRepo, device::DeviceRepo, device_group::DeviceGroupRepo},
+    utils::unix_timestamp,
+};
+
+/// Rules `device` violates as of `now`, each with a message describing what is wrong.
+///
+/// Group settings should already be applied, the firmware rule compares against the effective
+/// target version.
+pub fn violations(device: &Device, settings: &AlertSettings, now: i64) -> Vec<(AlertRule, String)> {
+    // Rejected devices are of no interest anymore, so their alerts resolve
+    if device.approval_status == ApprovalStatus::Rejected {
+        return Vec::new();
+    }
+
+    let mut violations = Vec::new();
+
+    if let (Some(threshold), Some(voltage)) =
+        (settings.battery_voltage_below, device.battery_voltage)
+        && voltage < threshold
+    {
+        violations.push((
+            AlertRule::LowBattery,
+            format!("Battery at {voltage:.2} V, below {threshold:.2} V"),
+        ));
+    }
+
+    if let (Some(threshold), Some(rssi)) = (settings.rssi_below, device.rssi)
+        && rssi < threshold
+    {
+        violations.push((
+            AlertRule::WeakSignal,
+            format!("Signal at {rssi} dBm, below {threshold} dBm"),
+        ));
+    }
+
+    // Devices that never polled are still being set up rather than gone
+    if let (Some(threshold), Some(last_seen_at)) = (settings.missed_polls, device.last_seen_at) {
+        let missed = (now - last_seen_at).max(0) as f64 / device.expected_poll_interval() as f64;
+
+        if missed >= threshold {
+            violations.push((
+                AlertRule::Offline,
+                format!("No poll for {missed:.1} refresh intervals"),
+            ));
+        }
+    }
+
+    if settings.firmware_mismatch
+        && let (Some(current), Some(target)) = (&device.fw_version, &device.target_fw_version)
+        && current != target
+    {
+        violations.push((
+            AlertRule::FirmwareMismatch,
+            format!("Running firmware {current}, target is {target}"),
+        ));
+    }
+
+    violations
+}
+
+/// Keeps the alerts of devices in line with the rules they currently violate
+pub struct AlertEngine {
+    alert_repo: AlertRepo,
+    notifier: NotifierRef,
+    settings: AlertSettings,
+}
+
+impl AlertEngine {
+    pub fn new(alert_repo: AlertRepo, notifier: NotifierRef, settings: AlertSettings) -> Self {
+        Self {
+            alert_repo,
+            notifier,
+            settings,
+        }
+    }
+
+    /// Fires an alert for every rule `device` newly violates and resolves the firing alerts of
+    /// rules it no longer violates, notifying about each change
+    #[instrument(name = "alerts.check", skip(self, device), fields(device_id = %device.id))]
+    pub async fn check(&self, device: &Device, now: i64) -> anyhow::Result<()> {
+        let violations = violations(device, &self.settings, now);
+        let firing = self.alert_repo.list_firing(&device.id).await?;
+
+        for (rule, message) in &violations {
+            if firing.iter().any(|alert| alert.rule == *rule) {
+                continue;
+            }
+
+            // Nothing is returned when a concurrent check fired the alert first
+            if let Some(alert) = self
+                .alert_repo
+                .fire(&device.id, *rule, message, now)
+                .await?
+            {
+                self.notify(&alert).await;
+            }
+        }
+
+        for alert in firing {
+            if violations.iter().any(|(rule, _)| *rule == alert.rule) {
+                continue;
+            }
+
+            if let Some(alert) = self.alert_repo.resolve(&device.id, alert.rule, now).await? {
+                self.notify(&alert).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks every device. Only this catches devices that stopped polling, as checks otherwise
+    /// run when a device polls.
+    #[instrument(name = "alerts.sweep", skip_all)]
+    pub async fn sweep(
+        &self,
+        device_repo: &DeviceRepo,
+        device_group_repo: &DeviceGroupRepo,
+        now: i64,
+    ) -> anyhow::Result<()> {
+        let groups: HashMap<_, _> = device_group_repo
+            .list()
+            .await?
+            .into_iter()
+            .map(|group| (group.id.clone(), group))
+            .collect();
+
+        for device in device_repo.list().await? {
+            let device = match device.group_id.as_ref().and_then(|id| groups.get(id)) {
+                Some(group) => device.with_group(group),
+                None => device,
+            };
+
+            // One failing device should not keep the others from being checked
+            if let Err(error) = self.check(&device, now).await {
+                warn!(msg = "Failed to check device alerts", device_id = %device.id, %error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delivery is best effort, the alert is already recorded and listed by the API
+    async fn notify(&self, alert: &Alert) {
+        if let Err(error) = self.notifier.notify(alert).await {
+            warn!(msg = "Failed to deliver alert notification", alert_id = alert.id, %error);
+        }
+    }
+}
+
+/// Sweeps all devices every `interval`, runs until the task is aborted
+pub async fn run_sweeps(
+    engine: Arc<AlertEngine>,
+    device_repo: DeviceRepo,
+    device_group_repo: DeviceGroupRepo,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        if let Err(error) = engine
+            .sweep(&device_repo, &device_group_repo, unix_timestamp())
+            .await
+        {
+            warn!(msg = "Failed to sweep device alerts", %error);
+        }
+    }
+}
@@ -1,13 +1,37 @@
 use axum::{
     Router,
-    routing::{get, post, put},
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, patch, post, put},
 };
 
-use crate::handlers::{
-    display_handler, get_device_handler, get_device_images_handler, list_devices_handler,
-    log_handler, put_device_images_handler, setup_handler,
+use crate::{
+    handlers::{
+        approve_device_handler, convert_images_handler, create_api_token_handler,
+        create_device_group_handler, create_layout_handler, delete_api_token_handler,
+        delete_device_group_handler, delete_device_handler, delete_firmware_handler,
+        delete_image_handler, delete_layout_handler, display_handler,
+        enqueue_device_command_handler, get_device_group_handler, get_device_handler,
+        get_device_images_handler, get_device_logs_handler, get_device_schedule_handler,
+        get_device_telemetry_handler, get_firmware_binary_handler, get_image_handler,
+        get_image_preview_handler, get_layout_handler, healthz_handler, list_alerts_handler,
+        list_api_tokens_handler, list_device_commands_handler, list_device_groups_handler,
+        list_devices_handler, list_firmware_handler, list_images_handler, list_layouts_handler,
+        log_handler, metrics_handler, patch_device_group_handler, patch_device_handler,
+        pending_approval_handler, preview_image_handler, preview_layout_handler,
+        put_device_group_devices_handler, put_device_group_images_handler,
+        put_device_images_handler, put_device_schedule_handler, put_firmware::MAX_FIRMWARE_SIZE,
+        put_firmware_handler, put_layout_handler, readyz_handler, reject_device_handler,
+        render_layout_handler, rotate_device_key_handler, setup_handler, sleeping_screen_handler,
+        upload_images::MAX_IMAGE_UPLOAD_SIZE, upload_images_handler,
+    },
+    layers::{
+        auth::require_api_token,
+        problem::{attach_request_id, problem_for_rejections},
+    },
 };
 
+#[derive(Default)]
 pub struct App;
 
 impl App {
@@ -17,12 +41,102 @@ impl App {
     pub fn router(self) -> Router {
         Router::new()
             .route("/", get(|| async { "Hello, World!" }))
+            .route("/healthz", get(healthz_handler))
+            .route("/readyz", get(readyz_handler))
             .route("/api/setup", get(setup_handler))
             .route("/api/display", get(display_handler))
             .route("/api/log", post(log_handler))
+            .route("/firmware/{version}", get(get_firmware_binary_handler))
+            .route("/images/{id}", get(get_image_handler))
+            .route("/layouts/{id}", get(render_layout_handler))
+            .route("/pending/{id}", get(pending_approval_handler))
+            .route("/sleeping/{id}", get(sleeping_screen_handler))
+            .merge(Self::management_router())
+            .layer(middleware::from_fn(problem_for_rejections))
+            .layer(middleware::from_fn(attach_request_id))
+    }
+
+    /// Routes used to manage the server, these require an API token
+    fn management_router() -> Router {
+        Router::new()
+            .route("/api/alerts", get(list_alerts_handler))
             .route("/api/devices", get(list_devices_handler))
             .route("/api/devices/{id}", get(get_device_handler))
+            .route("/api/devices/{id}", patch(patch_device_handler))
+            .route("/api/devices/{id}", delete(delete_device_handler))
+            .route("/api/devices/{id}/approve", post(approve_device_handler))
+            .route(
+                "/api/devices/{id}/commands",
+                get(list_device_commands_handler),
+            )
+            .route(
+                "/api/devices/{id}/commands",
+                post(enqueue_device_command_handler),
+            )
             .route("/api/devices/{id}/images", get(get_device_images_handler))
             .route("/api/devices/{id}/images", put(put_device_images_handler))
+            .route("/api/devices/{id}/logs", get(get_device_logs_handler))
+            .route("/api/devices/{id}/reject", post(reject_device_handler))
+            .route(
+                "/api/devices/{id}/schedule",
+                get(get_device_schedule_handler),
+            )
+            .route(
+                "/api/devices/{id}/schedule",
+                put(put_device_schedule_handler),
+            )
+            .route(
+                "/api/devices/{id}/rotate-key",
+                post(rotate_device_key_handler),
+            )
+            .route(
+                "/api/devices/{id}/telemetry",
+                get(get_device_telemetry_handler),
+            )
+            .route("/api/firmware", get(list_firmware_handler))
+            .route("/api/groups", get(list_device_groups_handler))
+            .route("/api/groups", post(create_device_group_handler))
+            .route("/api/groups/{id}", get(get_device_group_handler))
+            .route("/api/groups/{id}", patch(patch_device_group_handler))
+            .route("/api/groups/{id}", delete(delete_device_group_handler))
+            .route(
+                "/api/groups/{id}/devices",
+                put(put_device_group_devices_handler),
+            )
+            .route(
+                "/api/groups/{id}/images",
+                put(put_device_group_images_handler),
+            )
+            .route(
+                "/api/firmware/{version}",
+                put(put_firmware_handler).layer(DefaultBodyLimit::max(MAX_FIRMWARE_SIZE)),
+            )
+            .route("/api/firmware/{version}", delete(delete_firmware_handler))
+            .route("/api/images", get(list_images_handler))
+            .route(
+                "/api/images",
+                post(upload_images_handler).layer(DefaultBodyLimit::max(MAX_IMAGE_UPLOAD_SIZE)),
+            )
+            .route(
+                "/api/images/convert",
+                post(convert_images_handler).layer(DefaultBodyLimit::max(MAX_IMAGE_UPLOAD_SIZE)),
+            )
+            .route(
+                "/api/images/preview",
+                post(preview_image_handler).layer(DefaultBodyLimit::max(MAX_IMAGE_UPLOAD_SIZE)),
+            )
+            .route("/api/images/{id}", delete(delete_image_handler))
+            .route("/api/images/{id}/preview", get(get_image_preview_handler))
+            .route("/api/layouts", get(list_layouts_handler))
+            .route("/api/layouts", post(create_layout_handler))
+            .route("/api/layouts/preview", post(preview_layout_handler))
+            .route("/api/layouts/{id}", get(get_layout_handler))
+            .route("/api/layouts/{id}", put(put_layout_handler))
+            .route("/api/layouts/{id}", delete(delete_layout_handler))
+            .route("/api/tokens", get(list_api_tokens_handler))
+            .route("/api/tokens", post(create_api_token_handler))
+            .route("/api/tokens/{id}", delete(delete_api_token_handler))
+            .route("/metrics", get(metrics_handler))
+            .route_layer(middleware::from_fn(require_api_token))
     }
 }
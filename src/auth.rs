@@ -0,0 +1,48 @@
+use rand::{Rng, distr::Alphanumeric};
+use sha2::{Digest, Sha256};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::repositories::api_token::ApiTokenRepo;
+
+const API_TOKEN_LENGTH: usize = 40;
+const DEVICE_API_KEY_LENGTH: usize = 22;
+
+/// Generates a random management API token
+pub fn generate_api_token() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Generates a random API key for a device to authenticate with
+pub fn generate_device_api_key() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(DEVICE_API_KEY_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Tokens are random and long enough that a fast unsalted hash is sufficient
+pub fn hash_api_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Stores the configured bootstrap token unless it is already known
+pub async fn ensure_bootstrap_token(
+    api_token_repo: &ApiTokenRepo,
+    token: &str,
+) -> anyhow::Result<()> {
+    let token_hash = hash_api_token(token);
+
+    if api_token_repo.get_by_hash(&token_hash).await?.is_none() {
+        let id = Uuid::new_v4().to_string();
+        api_token_repo.create(&id, "bootstrap", &token_hash).await?;
+        info!(msg = "Created bootstrap API token", %id);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use std::net::SocketAddr;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Self-hosted server for TRMNL e-ink displays")]
+pub struct Cli {
+    /// Configuration file, with or without its extension
+    #[arg(long, env = "TRMNL_CONFIG", default_value = "config")]
+    pub config: String,
+
+    /// Address to listen on, overriding the `[server]` section
+    #[arg(long)]
+    pub bind: Option<SocketAddr>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create a management API token and print it
+    CreateToken {
+        /// Name to tell the token apart from others
+        #[arg(default_value = "admin")]
+        name: String,
+    },
+}
@@ -1,28 +1,132 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use config::{Environment, File};
 use serde::Deserialize;
 
+/// Prefix of the environment variables overriding the configuration file, nested keys are joined
+/// with `__` as in `TRMNL_SERVER__PORT`
+pub const ENV_PREFIX: &str = "TRMNL";
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ServerSettings {
+    #[serde(default = "default_server_address")]
+    pub address: IpAddr,
+    #[serde(default = "default_server_port")]
+    pub port: u16,
+    /// Seconds in-flight requests are given to finish on shutdown before they are dropped
+    #[serde(default = "default_drain_timeout")]
+    pub drain_timeout: u64,
+}
+
+impl ServerSettings {
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.address, self.port)
+    }
+
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.drain_timeout)
+    }
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        ServerSettings {
+            address: default_server_address(),
+            port: default_server_port(),
+            drain_timeout: default_drain_timeout(),
+        }
+    }
+}
+
+fn default_server_address() -> IpAddr {
+    IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+}
+
+fn default_server_port() -> u16 {
+    3000
+}
+
+fn default_drain_timeout() -> u64 {
+    30
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct DatabaseSettings {
+    /// SQLite database file, used unless `url` points to a SQLite database
+    #[serde(default = "default_database_path")]
     pub path: String,
+    /// Connection URL whose scheme selects the backend, `sqlite:`, `postgres://` or `memory:`
+    pub url: Option<String>,
+}
+
+fn default_database_path() -> String {
+    "trmnl.db".to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Sqlite,
+    Postgres,
+    /// Nothing is written to disk and all data is lost when the server stops
+    Memory,
+}
+
+impl DatabaseSettings {
+    pub fn backend(&self) -> anyhow::Result<DatabaseBackend> {
+        match self.url.as_deref() {
+            None => Ok(DatabaseBackend::Sqlite),
+            Some(url) if url.starts_with("sqlite:") => Ok(DatabaseBackend::Sqlite),
+            Some(url) if url.starts_with("postgres://") || url.starts_with("postgresql://") => {
+                Ok(DatabaseBackend::Postgres)
+            }
+            Some("memory:") => Ok(DatabaseBackend::Memory),
+            Some(_) => {
+                anyhow::bail!("Database URL must start with sqlite: or postgres://, or be memory:")
+            }
+        }
+    }
+
+    /// SQLite database file, taken from `url` when it is a SQLite URL
+    pub fn sqlite_path(&self) -> &str {
+        self.url
+            .as_deref()
+            .and_then(|url| url.strip_prefix("sqlite:"))
+            .map(|path| path.trim_start_matches("//"))
+            .unwrap_or(&self.path)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppSettings {
     pub setup_logo_url: String,
+    /// Public URL of this server, used to build links handed out to devices
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl AppSettings {
+    /// Absolute URL of `path` on this server as seen by the devices
+    pub fn public_url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url.trim_end_matches('/'))
+    }
+}
+
+fn default_base_url() -> String {
+    "http://localhost:3000".to_string()
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
+    #[default]
     Json,
     Pretty,
 }
 
-impl Default for LogFormat {
-    fn default() -> Self {
-        LogFormat::Json
-    }
-}
-
 #[derive(Debug, Deserialize, Clone)]
 pub struct LoggingSettings {
     #[serde(default)]
@@ -37,21 +141,221 @@ impl Default for LoggingSettings {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TraceExporter {
+    #[default]
+    None,
+    OtlpHttp,
+    OtlpGrpc,
+    Stdout,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TelemetrySettings {
+    /// Where spans are sent, nothing is exported by default
+    #[serde(default)]
+    pub exporter: TraceExporter,
+    /// Collector URL for the OTLP exporters, the exporter's own default when unset
+    pub endpoint: Option<String>,
+    /// Sent along with every OTLP export, e.g. for authentication
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// Share of new traces that are recorded, from 0.0 to 1.0
+    #[serde(default = "default_sampling_ratio")]
+    pub sampling_ratio: f64,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        TelemetrySettings {
+            exporter: TraceExporter::default(),
+            endpoint: None,
+            headers: HashMap::new(),
+            service_name: default_service_name(),
+            sampling_ratio: default_sampling_ratio(),
+        }
+    }
+}
+
+fn default_service_name() -> String {
+    "trmnl-server".to_string()
+}
+
+fn default_sampling_ratio() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AuthSettings {
+    /// Management API token created on startup if it does not exist yet
+    pub bootstrap_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ApprovalSettings {
+    /// Hold newly registered devices as pending until approved through the management API
+    #[serde(default)]
+    pub required: bool,
+    /// MAC addresses that are approved on registration even when approval is required
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+impl ApprovalSettings {
+    /// Whether a device registering with `mac` has to wait for approval
+    pub fn requires_approval(&self, mac: Option<&str>) -> bool {
+        self.required
+            && !mac.is_some_and(|mac| {
+                self.allowlist
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(mac))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusSettings {
+    /// Poll intervals a device may miss and still count as online, so a poll delayed by a slow
+    /// network does not flag it
+    #[serde(default = "default_grace_factor")]
+    pub grace_factor: f64,
+    /// Poll intervals after which a device counts as offline rather than late
+    #[serde(default = "default_offline_factor")]
+    pub offline_factor: f64,
+}
+
+impl Default for StatusSettings {
+    fn default() -> Self {
+        Self {
+            grace_factor: default_grace_factor(),
+            offline_factor: default_offline_factor(),
+        }
+    }
+}
+
+impl StatusSettings {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.grace_factor <= 0.0 {
+            anyhow::bail!("Status grace factor must be greater than 0");
+        }
+        if self.offline_factor < self.grace_factor {
+            anyhow::bail!("Status offline factor must not be less than the grace factor");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_grace_factor() -> f64 {
+    1.5
+}
+
+fn default_offline_factor() -> f64 {
+    3.0
+}
+
+/// Rules the alert engine checks devices against, each rule is off until its threshold is set
+#[derive(Debug, Deserialize, Clone)]
+pub struct AlertSettings {
+    /// Fire when the battery drops below this many volts
+    pub battery_voltage_below: Option<f64>,
+    /// Fire when the Wi-Fi signal drops below this many dBm
+    pub rssi_below: Option<i64>,
+    /// Fire when a device has not polled for this many of its refresh intervals
+    pub missed_polls: Option<f64>,
+    /// Fire when a device runs another firmware version than its target
+    #[serde(default)]
+    pub firmware_mismatch: bool,
+    /// Seconds between checks of all devices, which catch devices that stopped polling
+    #[serde(default = "default_sweep_interval")]
+    pub sweep_interval: u64,
+    /// URL every fired and resolved alert is posted to as JSON, alerts are only logged when unset
+    pub webhook_url: Option<String>,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            battery_voltage_below: None,
+            rssi_below: None,
+            missed_polls: None,
+            firmware_mismatch: false,
+            sweep_interval: default_sweep_interval(),
+            webhook_url: None,
+        }
+    }
+}
+
+impl AlertSettings {
+    pub fn sweep_interval(&self) -> Duration {
+        Duration::from_secs(self.sweep_interval)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.sweep_interval == 0 {
+            anyhow::bail!("Alert sweep interval must be greater than 0");
+        }
+        if self
+            .missed_polls
+            .is_some_and(|missed_polls| missed_polls <= 0.0)
+        {
+            anyhow::bail!("Alert missed polls must be greater than 0");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_sweep_interval() -> u64 {
+    60
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServerConfig {
+    #[serde(default)]
+    pub server: ServerSettings,
     pub database: DatabaseSettings,
     pub app: AppSettings,
     #[serde(default)]
     pub logging: LoggingSettings,
+    #[serde(default)]
+    pub telemetry: TelemetrySettings,
+    #[serde(default)]
+    pub auth: AuthSettings,
+    #[serde(default)]
+    pub approval: ApprovalSettings,
+    #[serde(default)]
+    pub status: StatusSettings,
+    #[serde(default)]
+    pub alerts: AlertSettings,
 }
 
 impl ServerConfig {
-    pub fn load() -> anyhow::Result<Self> {
+    /// Reads the configuration file at `path` with overrides from `TRMNL_` environment variables
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        Self::load_with_env(path, environment())
+    }
+
+    pub fn load_with_env(path: &str, environment: Environment) -> anyhow::Result<Self> {
         let settings = config::Config::builder()
-            .add_source(config::File::with_name("config"))
+            .add_source(File::with_name(path))
+            .add_source(environment)
             .build()?
             .try_deserialize::<ServerConfig>()?;
 
         Ok(settings)
     }
 }
+
+/// Environment variables with [`ENV_PREFIX`], lists are separated by commas
+pub fn environment() -> Environment {
+    Environment::with_prefix(ENV_PREFIX)
+        .prefix_separator("_")
+        .separator("__")
+        .try_parsing(true)
+        .list_separator(",")
+        .with_list_parse_key("approval.allowlist")
+}
@@ -0,0 +1,315 @@
+use image::{
+    GrayImage, Luma,
+    imageops::{self, FilterType},
+};
+use serde::{Deserialize, Serialize};
+
+/// Resolution of the TRMNL panel, used unless a conversion or layout asks for another
+pub const DISPLAY_WIDTH: u32 = 800;
+pub const DISPLAY_HEIGHT: u32 = 480;
+
+/// Largest width or height a bitmap can be rendered at
+pub const MAX_DISPLAY_SIZE: u32 = 2048;
+
+/// Size of the file and info headers plus the two entry palette of a 1-bit BMP
+const BMP_HEADER_SIZE: u32 = 14 + 40 + 8;
+
+/// Rows of the 8x8 Bayer matrix used for ordered dithering
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResizeMode {
+    /// Scale to fit within the display, padding the remaining space with white
+    #[default]
+    Fit,
+    /// Scale to cover the display, cropping whatever overflows
+    Fill,
+    /// Scale to the display size ignoring the aspect ratio
+    Stretch,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DitherMode {
+    /// Plain threshold at mid grey, best for text and line art
+    Threshold,
+    #[default]
+    FloydSteinberg,
+    Atkinson,
+    /// Ordered dithering with an 8x8 Bayer matrix
+    Ordered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ConversionOptions {
+    #[serde(default)]
+    pub resize: ResizeMode,
+    #[serde(default)]
+    pub dither: DitherMode,
+    /// Contrast multiplier around mid grey, 1.0 leaves the image unchanged
+    #[serde(default = "default_contrast")]
+    pub contrast: f32,
+    /// Brightness offset as a fraction of the full range, 0.0 leaves the image unchanged
+    #[serde(default)]
+    pub brightness: f32,
+    /// Resolution of the converted bitmap
+    #[serde(default = "default_width")]
+    pub width: u32,
+    #[serde(default = "default_height")]
+    pub height: u32,
+}
+
+fn default_contrast() -> f32 {
+    1.0
+}
+
+pub(crate) fn default_width() -> u32 {
+    DISPLAY_WIDTH
+}
+
+pub(crate) fn default_height() -> u32 {
+    DISPLAY_HEIGHT
+}
+
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        ConversionOptions {
+            resize: ResizeMode::default(),
+            dither: DitherMode::default(),
+            contrast: default_contrast(),
+            brightness: 0.0,
+            width: default_width(),
+            height: default_height(),
+        }
+    }
+}
+
+impl ConversionOptions {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !(0.0..=4.0).contains(&self.contrast) {
+            return Err("Contrast must be between 0 and 4");
+        }
+
+        if !(-1.0..=1.0).contains(&self.brightness) {
+            return Err("Brightness must be between -1 and 1");
+        }
+
+        if !valid_display_size(self.width, self.height) {
+            return Err("Width and height must be between 1 and 2048");
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn valid_display_size(width: u32, height: u32) -> bool {
+    (1..=MAX_DISPLAY_SIZE).contains(&width) && (1..=MAX_DISPLAY_SIZE).contains(&height)
+}
+
+/// Converts an encoded image into a 1-bit BMP at the resolution in `options`
+pub fn convert_to_bmp(data: &[u8], options: &ConversionOptions) -> image::ImageResult<Vec<u8>> {
+    let pixels = convert_to_pixels(data, options.width, options.height, options)?;
+
+    Ok(encode_bmp(&pixels, options.width, options.height))
+}
+
+/// Converts an encoded image into black and white pixels in row major order, `true` being white
+pub(crate) fn convert_to_pixels(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    options: &ConversionOptions,
+) -> image::ImageResult<Vec<bool>> {
+    let source = flatten_on_white(image::load_from_memory(data)?);
+    let resized = resize(&source, width, height, options.resize);
+    let levels = adjust(&resized, options.contrast, options.brightness);
+
+    Ok(dither(levels, width as usize, options.dither))
+}
+
+/// Transparent areas are shown as white on the panel rather than whatever colour hides beneath
+fn flatten_on_white(image: image::DynamicImage) -> GrayImage {
+    let image = image.to_luma_alpha8();
+
+    GrayImage::from_fn(image.width(), image.height(), |x, y| {
+        let [luma, alpha] = image.get_pixel(x, y).0;
+        let alpha = alpha as u32;
+
+        Luma([((luma as u32 * alpha + 255 * (255 - alpha)) / 255) as u8])
+    })
+}
+
+fn resize(image: &GrayImage, target_width: u32, target_height: u32, mode: ResizeMode) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let scale_x = target_width as f32 / width as f32;
+    let scale_y = target_height as f32 / height as f32;
+
+    let scaled = |scale: f32| {
+        let width = ((width as f32 * scale).round() as u32).max(1);
+        let height = ((height as f32 * scale).round() as u32).max(1);
+
+        imageops::resize(image, width, height, FilterType::Lanczos3)
+    };
+
+    match mode {
+        ResizeMode::Stretch => {
+            imageops::resize(image, target_width, target_height, FilterType::Lanczos3)
+        }
+        ResizeMode::Fit => {
+            let scaled = scaled(scale_x.min(scale_y));
+            let mut canvas = GrayImage::from_pixel(target_width, target_height, Luma([255]));
+
+            imageops::overlay(
+                &mut canvas,
+                &scaled,
+                (target_width as i64 - scaled.width() as i64) / 2,
+                (target_height as i64 - scaled.height() as i64) / 2,
+            );
+
+            canvas
+        }
+        ResizeMode::Fill => {
+            let scaled = scaled(scale_x.max(scale_y));
+            let x = scaled.width().saturating_sub(target_width) / 2;
+            let y = scaled.height().saturating_sub(target_height) / 2;
+            let cropped = imageops::crop_imm(&scaled, x, y, target_width, target_height);
+
+            // Rounding may leave the scaled image a pixel short of the target
+            let mut canvas = GrayImage::from_pixel(target_width, target_height, Luma([255]));
+            imageops::overlay(&mut canvas, &*cropped, 0, 0);
+            canvas
+        }
+    }
+}
+
+/// Applies contrast and brightness, returning the grey levels in row major order
+fn adjust(image: &GrayImage, contrast: f32, brightness: f32) -> Vec<f32> {
+    image
+        .pixels()
+        .map(|Luma([luma])| {
+            let level = (*luma as f32 / 255.0 - 0.5) * contrast + 0.5 + brightness;
+            level.clamp(0.0, 1.0) * 255.0
+        })
+        .collect()
+}
+
+/// Reduces grey levels to black and white, `true` being a white pixel
+fn dither(mut levels: Vec<f32>, width: usize, mode: DitherMode) -> Vec<bool> {
+    match mode {
+        DitherMode::Threshold => levels.iter().map(|level| *level >= 128.0).collect(),
+        DitherMode::Ordered => levels
+            .iter()
+            .enumerate()
+            .map(|(i, level)| {
+                let threshold = BAYER_8X8[(i / width) % 8][(i % width) % 8] as f32;
+                *level > (threshold + 0.5) * 255.0 / 64.0
+            })
+            .collect(),
+        DitherMode::FloydSteinberg => diffuse(
+            &mut levels,
+            width,
+            &[(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)],
+            16.0,
+        ),
+        // Atkinson only spreads three quarters of the error, keeping highlights and shadows crisp
+        DitherMode::Atkinson => diffuse(
+            &mut levels,
+            width,
+            &[
+                (1, 0, 1.0),
+                (2, 0, 1.0),
+                (-1, 1, 1.0),
+                (0, 1, 1.0),
+                (1, 1, 1.0),
+                (0, 2, 1.0),
+            ],
+            8.0,
+        ),
+    }
+}
+
+/// Error diffusion dithering, spreading the quantization error to the neighbours in `kernel`
+fn diffuse(
+    levels: &mut [f32],
+    width: usize,
+    kernel: &[(isize, usize, f32)],
+    divisor: f32,
+) -> Vec<bool> {
+    let height = levels.len() / width;
+    let mut pixels = Vec::with_capacity(levels.len());
+
+    for y in 0..height {
+        for x in 0..width {
+            let level = levels[y * width + x];
+            let white = level >= 128.0;
+            let error = level - if white { 255.0 } else { 0.0 };
+
+            for (dx, dy, weight) in kernel {
+                let nx = x as isize + dx;
+                let ny = y + dy;
+
+                if nx >= 0 && (nx as usize) < width && ny < height {
+                    levels[ny * width + nx as usize] += error * weight / divisor;
+                }
+            }
+
+            pixels.push(white);
+        }
+    }
+
+    pixels
+}
+
+/// Encodes black and white pixels as a bottom-up 1-bit BMP with a black and white palette
+pub(crate) fn encode_bmp(pixels: &[bool], width: u32, height: u32) -> Vec<u8> {
+    // Rows are padded to a multiple of four bytes
+    let row_size = width.div_ceil(32) * 4;
+    let image_size = row_size * height;
+    let file_size = BMP_HEADER_SIZE + image_size;
+
+    let mut bmp = Vec::with_capacity(file_size as usize);
+
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&file_size.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&BMP_HEADER_SIZE.to_le_bytes());
+
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(height as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&image_size.to_le_bytes());
+    bmp.extend_from_slice(&2835i32.to_le_bytes());
+    bmp.extend_from_slice(&2835i32.to_le_bytes());
+    bmp.extend_from_slice(&2u32.to_le_bytes());
+    bmp.extend_from_slice(&2u32.to_le_bytes());
+
+    bmp.extend_from_slice(&[0, 0, 0, 0]);
+    bmp.extend_from_slice(&[255, 255, 255, 0]);
+
+    for row in pixels.chunks(width as usize).rev() {
+        let mut bytes = vec![0u8; row_size as usize];
+
+        for (x, white) in row.iter().enumerate() {
+            if *white {
+                bytes[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        bmp.extend_from_slice(&bytes);
+    }
+
+    bmp
+}
@@ -1,17 +1,63 @@
-use sqlx::{SqlitePool, sqlite::SqliteConnectOptions};
+#[cfg(feature = "postgres")]
+use sqlx::PgPool;
+use sqlx::{
+    SqlitePool,
+    migrate::Migrator,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
 use tracing::info;
 
-pub async fn connect(path: &str) -> anyhow::Result<SqlitePool> {
+/// Opens the SQLite database. Foreign keys are only left unenforced while devices are stored in
+/// another database, as the tables referencing them could never be satisfied.
+pub async fn connect(path: &str, foreign_keys: bool) -> anyhow::Result<SqlitePool> {
     let pool = SqlitePool::connect_with(
         SqliteConnectOptions::new()
             .filename(path)
-            .create_if_missing(true),
+            .create_if_missing(true)
+            .foreign_keys(foreign_keys),
     )
     .await?;
 
     Ok(pool)
 }
 
+/// Opens a private in-memory SQLite database. The pool holds on to its only connection, as the
+/// database disappears along with it.
+pub async fn connect_in_memory() -> anyhow::Result<SqlitePool> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .min_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect_with(
+            SqliteConnectOptions::new()
+                .in_memory(true)
+                .foreign_keys(false),
+        )
+        .await?;
+
+    Ok(pool)
+}
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+#[cfg(feature = "postgres")]
+static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+/// Version of the newest SQLite migration bundled with this build
+pub fn latest_migration_version() -> Option<i64> {
+    MIGRATOR.iter().map(|migration| migration.version).max()
+}
+
+/// Version of the newest PostgreSQL migration bundled with this build
+#[cfg(feature = "postgres")]
+pub fn latest_postgres_migration_version() -> Option<i64> {
+    POSTGRES_MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+}
+
 pub async fn apply_migrations(pool: &SqlitePool) -> anyhow::Result<()> {
     let before_count: i64 = match sqlx::query_scalar!("SELECT COUNT(*) FROM _sqlx_migrations")
         .fetch_one(pool)
@@ -22,7 +68,7 @@ pub async fn apply_migrations(pool: &SqlitePool) -> anyhow::Result<()> {
         Err(e) => return Err(e.into()),
     };
 
-    sqlx::migrate!("./migrations").run(pool).await?;
+    MIGRATOR.run(pool).await?;
 
     let after_count: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM _sqlx_migrations")
         .fetch_one(pool)
@@ -47,3 +93,36 @@ pub async fn apply_migrations(pool: &SqlitePool) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "postgres")]
+pub async fn connect_postgres(url: &str) -> anyhow::Result<PgPool> {
+    Ok(PgPool::connect(url).await?)
+}
+
+/// Applies the separate migration set under `migrations/postgres`
+#[cfg(feature = "postgres")]
+pub async fn apply_postgres_migrations(pool: &PgPool) -> anyhow::Result<()> {
+    let applied: Vec<i64> = match sqlx::query_scalar("SELECT version FROM _sqlx_migrations")
+        .fetch_all(pool)
+        .await
+    {
+        Ok(versions) => versions,
+        Err(sqlx::Error::Database(_)) => vec![],
+        Err(e) => return Err(e.into()),
+    };
+
+    POSTGRES_MIGRATOR.run(pool).await?;
+
+    for m in POSTGRES_MIGRATOR
+        .iter()
+        .filter(|m| !applied.contains(&m.version))
+    {
+        info!(
+            msg = "Applied database migration",
+            version = %m.version,
+            description = %m.description,
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+/// Media type of the error bodies, see RFC 9457
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Error returned by handlers, rendered as a problem details document.
+///
+/// Client errors carry the message shown to the caller, internal errors keep the underlying
+/// error for the logs and only ever tell the caller that something went wrong.
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(&'static str),
+    Unauthorized(&'static str),
+    NotFound(&'static str),
+    Conflict(&'static str),
+    UnsupportedMediaType(&'static str),
+    Validation(&'static str),
+    Internal(anyhow::Error),
+}
+
+/// Any error a repository or library call can fail with is an internal failure, so handlers can
+/// use `?` on them directly
+impl<E> From<E> for ApiError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(error: E) -> Self {
+        let error = error.into();
+        // Logged here rather than when the response is built, so the event lands on the span of
+        // the handler that failed
+        error!(msg = "Request failed", error = %format_args!("{error:#}"));
+        Self::Internal(error)
+    }
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Stable identifier of the kind of error, clients should match on this rather than on the
+    /// message
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest(_) => "bad_request",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::UnsupportedMediaType(_) => "unsupported_media_type",
+            Self::Validation(_) => "validation_failed",
+            Self::Internal(_) => "internal_error",
+        }
+    }
+
+    fn detail(&self) -> &'static str {
+        match self {
+            Self::BadRequest(detail)
+            | Self::Unauthorized(detail)
+            | Self::NotFound(detail)
+            | Self::Conflict(detail)
+            | Self::UnsupportedMediaType(detail)
+            | Self::Validation(detail) => detail,
+            Self::Internal(_) => "Something went wrong",
+        }
+    }
+}
+
+/// Body of an error response. `request_id` is filled in by the
+/// [`attach_request_id`](crate::layers::problem::attach_request_id) middleware, which finds the
+/// problem in the response extensions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Problem {
+    /// Problem for a request axum rejected before the handler ran, such as a body that is not
+    /// valid JSON. The rejection message describes what the caller got wrong, so it is passed on.
+    pub fn from_rejection(status: StatusCode, detail: String) -> Self {
+        let code = match status {
+            StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+            StatusCode::UNPROCESSABLE_ENTITY => "validation_failed",
+            _ => "bad_request",
+        };
+
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail,
+            code: code.to_string(),
+            request_id: None,
+        }
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, Json(self.clone())).into_response();
+
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(PROBLEM_CONTENT_TYPE),
+        );
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+impl From<&ApiError> for Problem {
+    fn from(error: &ApiError) -> Self {
+        let status = error.status();
+
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail: error.detail().to_string(),
+            code: error.code().to_string(),
+            request_id: None,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        Problem::from(&self).into_response()
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    config::StatusSettings,
+    error::ApiError,
+    handlers::get_device::device_info,
+    models::{ApprovalStatus, DeviceInfo},
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo},
+};
+
+/// Stores the approval decision and returns the updated device
+pub(crate) async fn set_approval_status(
+    device_repo: &DeviceRepo,
+    device_group_repo: &DeviceGroupRepo,
+    status_settings: &StatusSettings,
+    id: &str,
+    status: ApprovalStatus,
+) -> Result<Json<DeviceInfo>, ApiError> {
+    if !device_repo.update_approval_status(id, status).await? {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    info!(
+        msg = "Device approval status updated",
+        status = status.as_str()
+    );
+
+    match device_repo.get_by_id(id).await? {
+        Some(device) => Ok(Json(
+            device_info(device_group_repo, status_settings, device).await?,
+        )),
+        None => Err(ApiError::NotFound("Device not found")),
+    }
+}
+
+#[instrument(
+    name = "handlers.approve_device",
+    skip(device_repo, device_group_repo, status_settings, id),
+    fields(device_id = %id)
+)]
+pub async fn approve_device_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(status_settings): Extension<StatusSettings>,
+) -> Result<Json<DeviceInfo>, ApiError> {
+    set_approval_status(
+        &device_repo,
+        &device_group_repo,
+        &status_settings,
+        &id,
+        ApprovalStatus::Approved,
+    )
+    .await
+}
@@ -0,0 +1,43 @@
+use axum::{
+    Json,
+    extract::{Extension, Multipart, Query},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use crate::{
+    convert::{ConversionOptions, convert_to_bmp},
+    error::ApiError,
+    handlers::upload_images::{read_files, store_image},
+    repositories::image::ImageRepo,
+};
+
+#[instrument(name = "handlers.convert_images", skip(image_repo, multipart))]
+pub async fn convert_images_handler(
+    Extension(image_repo): Extension<ImageRepo>,
+    Query(options): Query<ConversionOptions>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    options.validate().map_err(ApiError::Validation)?;
+
+    let files = read_files(multipart).await?;
+
+    // Converting is CPU bound so keep it off the async workers
+    let bitmaps = tokio::task::spawn_blocking(move || {
+        files
+            .iter()
+            .map(|data| convert_to_bmp(data, &options))
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .await?
+    .map_err(|_| ApiError::Validation("Image could not be decoded"))?;
+
+    let mut images = Vec::with_capacity(bitmaps.len());
+
+    for bitmap in bitmaps {
+        images.push(store_image(&image_repo, "image/bmp", &bitmap).await?);
+    }
+
+    Ok((StatusCode::CREATED, Json(images)))
+}
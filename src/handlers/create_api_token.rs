@@ -0,0 +1,40 @@
+use axum::{Extension, Json, http::StatusCode};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    auth::{generate_api_token, hash_api_token},
+    error::ApiError,
+    models::{CreateApiTokenRequest, CreatedApiToken},
+    repositories::api_token::ApiTokenRepo,
+};
+
+#[instrument(name = "handlers.create_api_token", skip(api_token_repo, request))]
+pub async fn create_api_token_handler(
+    Extension(api_token_repo): Extension<ApiTokenRepo>,
+    Json(request): Json<CreateApiTokenRequest>,
+) -> Result<(StatusCode, Json<CreatedApiToken>), ApiError> {
+    let name = request.name.trim();
+
+    if name.is_empty() {
+        return Err(ApiError::Validation("Token name is required"));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let token = generate_api_token();
+
+    api_token_repo
+        .create(&id, name, &hash_api_token(&token))
+        .await?;
+
+    info!(msg = "API token created", %id, %name);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedApiToken {
+            id,
+            name: name.to_string(),
+            token,
+        }),
+    ))
+}
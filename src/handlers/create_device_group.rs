@@ -0,0 +1,35 @@
+use axum::{Json, extract::Extension, http::StatusCode};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    error::ApiError,
+    models::{DeviceGroup, DeviceGroupRequest},
+    repositories::device_group::DeviceGroupRepo,
+};
+
+#[instrument(
+    name = "handlers.create_device_group",
+    skip(device_group_repo, request)
+)]
+pub async fn create_device_group_handler(
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Json(request): Json<DeviceGroupRequest>,
+) -> Result<(StatusCode, Json<DeviceGroup>), ApiError> {
+    let name = request.name.trim();
+
+    if name.is_empty() {
+        return Err(ApiError::Validation("Group name is required"));
+    }
+
+    let id = Uuid::new_v4().to_string();
+
+    device_group_repo.create(&id, name).await?;
+
+    info!(msg = "Device group created", %id, %name);
+
+    match device_group_repo.get(&id).await? {
+        Some(group) => Ok((StatusCode::CREATED, Json(group))),
+        _ => Err(anyhow::anyhow!("Device group {id} is missing after it was created").into()),
+    }
+}
@@ -0,0 +1,56 @@
+use axum::{Json, extract::Extension, http::StatusCode};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    error::ApiError,
+    layout::LayoutSpec,
+    models::{Layout, LayoutRequest},
+    repositories::{image::ImageRepo, layout::LayoutRepo},
+};
+
+/// Checks a layout before it is stored, including that the images it draws exist
+pub(crate) async fn validate_layout(
+    image_repo: &ImageRepo,
+    name: &str,
+    spec: &LayoutSpec,
+) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::Validation("Layout name is required"));
+    }
+
+    spec.validate().map_err(ApiError::Validation)?;
+
+    for image_id in spec.image_ids() {
+        if image_repo.get(image_id).await?.is_none() {
+            return Err(ApiError::Validation("Layout references an unknown image"));
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument(
+    name = "handlers.create_layout",
+    skip(layout_repo, image_repo, request)
+)]
+pub async fn create_layout_handler(
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Json(request): Json<LayoutRequest>,
+) -> Result<(StatusCode, Json<Layout>), ApiError> {
+    let name = request.name.trim();
+
+    validate_layout(&image_repo, name, &request.spec).await?;
+
+    let id = Uuid::new_v4().to_string();
+
+    layout_repo.create(&id, name, &request.spec).await?;
+
+    info!(msg = "Layout created", %id, %name);
+
+    match layout_repo.get(&id).await? {
+        Some(layout) => Ok((StatusCode::CREATED, Json(layout))),
+        _ => Err(anyhow::anyhow!("Layout {id} is missing after it was created").into()),
+    }
+}
@@ -0,0 +1,21 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use tracing::{info, instrument};
+
+use crate::{error::ApiError, repositories::api_token::ApiTokenRepo};
+
+#[instrument(name = "handlers.delete_api_token", skip(api_token_repo, id), fields(token_id = %id))]
+pub async fn delete_api_token_handler(
+    Path(id): Path<String>,
+    Extension(api_token_repo): Extension<ApiTokenRepo>,
+) -> Result<StatusCode, ApiError> {
+    match api_token_repo.delete(&id).await? {
+        true => {
+            info!(msg = "API token revoked");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        false => Err(ApiError::NotFound("API token not found")),
+    }
+}
@@ -0,0 +1,50 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    repositories::{
+        alert::AlertRepo, device::DeviceRepo, device_command::DeviceCommandRepo,
+        device_log::DeviceLogRepo, telemetry::TelemetryRepo,
+    },
+};
+
+/// Data kept for the device is deleted by the handler rather than left to foreign keys, as those
+/// are not enforced while devices are stored outside the SQLite database. It goes before the
+/// device itself, so a failed request can be repeated.
+#[instrument(
+    name = "handlers.delete_device",
+    skip(
+        device_repo,
+        device_log_repo,
+        telemetry_repo,
+        device_command_repo,
+        alert_repo,
+        id
+    ),
+    fields(device_id = %id)
+)]
+pub async fn delete_device_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_log_repo): Extension<DeviceLogRepo>,
+    Extension(telemetry_repo): Extension<TelemetryRepo>,
+    Extension(device_command_repo): Extension<DeviceCommandRepo>,
+    Extension(alert_repo): Extension<AlertRepo>,
+) -> Result<StatusCode, ApiError> {
+    device_log_repo.delete_by_device(&id).await?;
+    telemetry_repo.delete_by_device(&id).await?;
+    device_command_repo.delete_by_device(&id).await?;
+    alert_repo.delete_by_device(&id).await?;
+
+    match device_repo.delete(&id).await? {
+        true => {
+            info!(msg = "Device deleted");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        false => Err(ApiError::NotFound("Device not found")),
+    }
+}
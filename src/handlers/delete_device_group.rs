@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    models::DevicePatch,
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo},
+};
+
+/// Members of a deleted group keep polling with their own settings. They are taken out of the
+/// group by the handler, as devices stored outside the SQLite database are not covered by its
+/// foreign keys.
+#[instrument(
+    name = "handlers.delete_device_group",
+    skip(device_repo, device_group_repo, id),
+    fields(group_id = %id)
+)]
+pub async fn delete_device_group_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+) -> Result<StatusCode, ApiError> {
+    let leave_group = DevicePatch {
+        group_id: Some(None),
+        ..Default::default()
+    };
+
+    for device in device_repo.list_by_group(&id).await? {
+        device_repo.update(&device.id, &leave_group).await?;
+    }
+
+    match device_group_repo.delete(&id).await? {
+        true => {
+            info!(msg = "Device group deleted");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        false => Err(ApiError::NotFound("Device group not found")),
+    }
+}
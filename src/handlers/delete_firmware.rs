@@ -0,0 +1,41 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo, firmware::FirmwareRepo},
+};
+
+#[instrument(name = "handlers.delete_firmware", skip(firmware_repo, device_repo, device_group_repo, version), fields(version = %version))]
+pub async fn delete_firmware_handler(
+    Path(version): Path<String>,
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+) -> Result<StatusCode, ApiError> {
+    let devices = device_repo.list().await?;
+
+    if devices
+        .iter()
+        .any(|device| device.target_fw_version.as_deref() == Some(version.as_str()))
+    {
+        return Err(ApiError::Conflict("Firmware is targeted by a device"));
+    }
+
+    let groups = device_group_repo.list().await?;
+
+    if groups
+        .iter()
+        .any(|group| group.target_fw_version.as_deref() == Some(version.as_str()))
+    {
+        return Err(ApiError::Conflict("Firmware is targeted by a group"));
+    }
+
+    match firmware_repo.delete(&version).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::NotFound("Firmware not found")),
+    }
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    repositories::{
+        device::DeviceRepo, device_group::DeviceGroupRepo, image::ImageRepo, layout::LayoutRepo,
+    },
+    utils::image_reference,
+};
+
+#[instrument(name = "handlers.delete_image", skip(image_repo, device_repo, device_group_repo, layout_repo, id), fields(image_id = %id))]
+pub async fn delete_image_handler(
+    Path(id): Path<String>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+) -> Result<StatusCode, ApiError> {
+    let devices = device_repo.list().await?;
+
+    // Deleting an image still on rotation would leave devices fetching a missing file
+    if devices
+        .iter()
+        .flat_map(|device| device.rotation_entries())
+        .any(|entry| image_reference(entry) == Some(id.as_str()))
+    {
+        return Err(ApiError::Conflict("Image is used by a device rotation"));
+    }
+
+    let groups = device_group_repo.list().await?;
+
+    if groups
+        .iter()
+        .flat_map(|group| &group.images)
+        .any(|entry| image_reference(entry) == Some(id.as_str()))
+    {
+        return Err(ApiError::Conflict("Image is used by a group rotation"));
+    }
+
+    let layouts = layout_repo.list().await?;
+
+    if layouts
+        .iter()
+        .any(|layout| layout.spec.image_ids().contains(&id.as_str()))
+    {
+        return Err(ApiError::Conflict("Image is used by a layout"));
+    }
+
+    match image_repo.delete(&id).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::NotFound("Image not found")),
+    }
+}
@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo, layout::LayoutRepo},
+    utils::layout_reference,
+};
+
+#[instrument(name = "handlers.delete_layout", skip(layout_repo, device_repo, device_group_repo, id), fields(layout_id = %id))]
+pub async fn delete_layout_handler(
+    Path(id): Path<String>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+) -> Result<StatusCode, ApiError> {
+    let devices = device_repo.list().await?;
+
+    if devices
+        .iter()
+        .flat_map(|device| device.rotation_entries())
+        .any(|entry| layout_reference(entry) == Some(id.as_str()))
+    {
+        return Err(ApiError::Conflict("Layout is used by a device rotation"));
+    }
+
+    let groups = device_group_repo.list().await?;
+
+    if groups
+        .iter()
+        .flat_map(|group| &group.images)
+        .any(|entry| layout_reference(entry) == Some(id.as_str()))
+    {
+        return Err(ApiError::Conflict("Layout is used by a group rotation"));
+    }
+
+    match layout_repo.delete(&id).await? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::NotFound("Layout not found")),
+    }
+}
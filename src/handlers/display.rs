@@ -1,38 +1,72 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-
-use axum::{
-    Json,
-    extract::Extension,
-    http::{HeaderMap, StatusCode},
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
-use tracing::{info, instrument};
+
+use axum::{Json, extract::Extension, http::HeaderMap};
+use chrono::Utc;
+use tracing::{info, instrument, warn};
 
 use crate::{
+    alerts::AlertEngine,
     config::AppSettings,
+    error::ApiError,
+    handlers::patch_device::{MAX_REFRESH_RATE, MIN_REFRESH_RATE},
     headers::{
         HEADER_ACCESS_TOKEN, HEADER_BATTERY_VOLTAGE, HEADER_FW_VERSION, HEADER_REFRESH_RATE,
         HEADER_RSSI,
     },
-    models::DisplayResponse,
-    repositories::device::DeviceRepo,
-    utils::get_header,
+    models::{
+        ApprovalStatus, DEFAULT_REFRESH_RATE, DeviceCommand, DisplayResponse, TelemetrySample,
+    },
+    repositories::{
+        device::DeviceRepo, device_command::DeviceCommandRepo, device_group::DeviceGroupRepo,
+        telemetry::TelemetryRepo,
+    },
+    schedule::active_rotation,
+    utils::{get_header, image_reference, is_older_version, layout_reference, unix_timestamp},
 };
 
-const DEFAULT_REFRESH_RATE: &str = "1800";
+/// Turns a rotation entry into the URL the device downloads
+fn resolve_entry(settings: &AppSettings, entry: &str) -> String {
+    if let Some(hash) = image_reference(entry) {
+        settings.public_url(&format!("/images/{hash}"))
+    } else if let Some(layout_id) = layout_reference(entry) {
+        settings.public_url(&format!("/layouts/{layout_id}"))
+    } else {
+        entry.to_string()
+    }
+}
 
-#[instrument(name = "handlers.display", skip(headers, device_repo, settings))]
+#[instrument(
+    name = "handlers.display",
+    skip(
+        headers,
+        device_repo,
+        device_command_repo,
+        device_group_repo,
+        telemetry_repo,
+        alert_engine,
+        settings
+    )
+)]
 pub async fn display_handler(
     headers: HeaderMap,
     Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_command_repo): Extension<DeviceCommandRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(telemetry_repo): Extension<TelemetryRepo>,
+    Extension(alert_engine): Extension<Arc<AlertEngine>>,
     Extension(settings): Extension<AppSettings>,
-) -> Result<Json<DisplayResponse>, (StatusCode, &'static str)> {
+) -> Result<Json<DisplayResponse>, ApiError> {
     let access_token = get_header(&headers, &HEADER_ACCESS_TOKEN);
     let rssi = get_header(&headers, &HEADER_RSSI);
     let fw_version = get_header(&headers, &HEADER_FW_VERSION);
     let battery_voltage = get_header(&headers, &HEADER_BATTERY_VOLTAGE);
     let refresh_rate_raw = get_header(&headers, &HEADER_REFRESH_RATE);
+    let default_refresh_rate = DEFAULT_REFRESH_RATE.to_string();
     let refresh_rate = if refresh_rate_raw.is_empty() {
-        DEFAULT_REFRESH_RATE
+        default_refresh_rate.as_str()
     } else {
         refresh_rate_raw
     };
@@ -42,34 +76,172 @@ pub async fn display_handler(
         .map(|d| d.as_secs().to_string())
         .unwrap_or_else(|_| "unknown".to_string());
 
+    // Rejected devices are turned away like unknown ones
     if let Some(device) = device_repo
         .get_by_api_key(access_token)
-        .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?
+        .await?
+        .filter(|device| device.approval_status != ApprovalStatus::Rejected)
     {
+        let seen_at = unix_timestamp();
+        let reported_rssi = rssi.parse().ok();
+        // Parsed at full precision for history, the device row keeps an f32
+        let reported_battery_voltage: Option<f64> = battery_voltage.parse().ok();
+        let device_battery_voltage = reported_battery_voltage.map(|voltage| voltage as f32);
+        let reported_fw_version = (!fw_version.is_empty()).then_some(fw_version);
+        let reported_refresh_rate = refresh_rate.parse().ok();
+
         device_repo
             .update_status(
                 &device.id,
-                rssi.parse().ok(),
-                battery_voltage.parse().ok(),
-                if fw_version.is_empty() {
-                    None
-                } else {
-                    Some(&fw_version)
+                seen_at,
+                reported_rssi,
+                device_battery_voltage,
+                reported_fw_version,
+                reported_refresh_rate,
+            )
+            .await?;
+
+        let mut device = device;
+        device.apply_status(
+            seen_at,
+            reported_rssi,
+            device_battery_voltage,
+            reported_fw_version,
+            reported_refresh_rate,
+        );
+
+        // History is best effort, a failed insert should not leave the device without an image
+        if let Err(error) = telemetry_repo
+            .record(
+                &device.id,
+                &TelemetrySample {
+                    recorded_at: seen_at,
+                    rssi: reported_rssi.map(Into::into),
+                    battery_voltage: reported_battery_voltage,
+                    fw_version: reported_fw_version.map(str::to_string),
+                    refresh_rate: reported_refresh_rate.map(Into::into),
                 },
-                refresh_rate.parse().ok(),
             )
             .await
-            .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?;
+        {
+            warn!(msg = "Failed to record device telemetry", device_id = %device.id, %error);
+        }
+
+        // Settings the device leaves unset are inherited from its group
+        let device = match &device.group_id {
+            Some(group_id) => match device_group_repo.get(group_id).await? {
+                Some(group) => device.with_group(&group),
+                None => device,
+            },
+            None => device,
+        };
+
+        // Alerting is best effort as well, and judged on what the device just reported
+        if let Err(error) = alert_engine.check(&device, seen_at).await {
+            warn!(msg = "Failed to check device alerts", device_id = %device.id, %error);
+        }
+
+        if device.approval_status == ApprovalStatus::Pending {
+            info!(msg = "Device is waiting for approval", device_id = %device.id);
+
+            return Ok(Json(DisplayResponse {
+                status: 0,
+                image_url: settings.public_url(&format!("/pending/{}", device.id)),
+                filename,
+                update_firmware: false,
+                firmware_url: None,
+                refresh_rate: refresh_rate.to_string(),
+                reset_firmware: false,
+            }));
+        }
+
+        // A failed lookup leaves the command queued for the next poll
+        let command = match device_command_repo.take_next(&device.id).await {
+            Ok(queued) => queued.map(|queued| {
+                info!(msg = "Delivering device command", device_id = %device.id, command_id = queued.id);
+                queued.command
+            }),
+            Err(error) => {
+                warn!(msg = "Failed to take device command", device_id = %device.id, %error);
+                None
+            }
+        };
+
+        let timezone = device.local_timezone();
+        let now = Utc::now();
+
+        // Outside of all schedule windows the default rotation is shown
+        let rotation = active_rotation(&device.schedule, timezone, now).unwrap_or(&device.images);
+
+        let quiet_hours = device.quiet_hours_sleep(now);
+
+        let image_url = match (&command, rotation.len()) {
+            // A one-off image leaves the rotation where it was
+            (Some(DeviceCommand::ShowImage { image }), _) => resolve_entry(&settings, image),
+            _ if quiet_hours.is_some_and(|(quiet_hours, _)| quiet_hours.sleep_screen) => {
+                settings.public_url(&format!("/sleeping/{}", device.id))
+            }
+            (_, 0) => settings.setup_logo_url.clone(),
+            (_, len) => {
+                // The cursor may be stale if the rotation shrank since it was stored
+                let index = device.image_cursor.rem_euclid(len as i64) as usize;
+
+                // Polls during quiet hours keep the rotation where it was, so the morning image
+                // does not depend on how often the device woke overnight.
+                // Best effort too, as a command taken above would be lost with a failed response.
+                // The device is shown the same image again on its next poll.
+                if quiet_hours.is_none()
+                    && let Err(error) = device_repo
+                        .update_image_cursor(&device.id, ((index + 1) % len) as i64)
+                        .await
+                {
+                    warn!(msg = "Failed to advance image rotation", device_id = %device.id, %error);
+                }
+
+                resolve_entry(&settings, &rotation[index])
+            }
+        };
+
+        // During quiet hours the device sleeps until the window ends, otherwise the server
+        // controlled rate takes precedence over what the device reported
+        let refresh_rate = match quiet_hours {
+            Some((_, remaining)) => {
+                info!(msg = "Device is in quiet hours", device_id = %device.id, remaining);
+                remaining
+                    .clamp(MIN_REFRESH_RATE, MAX_REFRESH_RATE)
+                    .to_string()
+            }
+            None => device
+                .desired_refresh_rate
+                .map(|rate| rate.to_string())
+                .unwrap_or_else(|| refresh_rate.to_string()),
+        };
+
+        let firmware_url = match &command {
+            // An explicit command skips the version check so devices can also be downgraded
+            Some(DeviceCommand::UpdateFirmware { version }) => Some(version.as_str()),
+            _ => device.target_fw_version.as_deref().filter(|target| {
+                // Already the version just reported, or else the one stored from a previous poll
+                device
+                    .fw_version
+                    .as_deref()
+                    .is_some_and(|current| is_older_version(current, target))
+            }),
+        }
+        .map(|target| settings.public_url(&format!("/firmware/{target}")));
+
+        if let Some(firmware_url) = &firmware_url {
+            info!(msg = "Requesting firmware update", device_id = %device.id, %firmware_url);
+        }
 
         return Ok(Json(DisplayResponse {
             status: 0,
-            image_url: settings.setup_logo_url.clone(),
+            image_url,
             filename,
-            update_firmware: false,
-            firmware_url: None,
-            refresh_rate: refresh_rate.to_string(),
-            reset_firmware: false,
+            update_firmware: firmware_url.is_some(),
+            firmware_url,
+            refresh_rate,
+            reset_firmware: matches!(command, Some(DeviceCommand::ResetFirmware)),
         }));
     }
 
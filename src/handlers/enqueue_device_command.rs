@@ -0,0 +1,63 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use semver::Version;
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    handlers::put_device_images::validate_entries,
+    models::DeviceCommand,
+    repositories::{
+        device::DeviceRepo, device_command::DeviceCommandRepo, firmware::FirmwareRepo,
+        image::ImageRepo, layout::LayoutRepo,
+    },
+};
+
+#[instrument(
+    name = "handlers.enqueue_device_command",
+    skip(device_repo, device_command_repo, firmware_repo, image_repo, layout_repo, id, command),
+    fields(device_id = %id)
+)]
+pub async fn enqueue_device_command_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_command_repo): Extension<DeviceCommandRepo>,
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Json(command): Json<DeviceCommand>,
+) -> Result<impl IntoResponse, ApiError> {
+    if device_repo.get_by_id(&id).await?.is_none() {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    match &command {
+        DeviceCommand::ResetFirmware => {}
+        DeviceCommand::UpdateFirmware { version } => {
+            if Version::parse(version).is_err() {
+                return Err(ApiError::Validation(
+                    "Firmware version must be a valid semantic version",
+                ));
+            }
+
+            if firmware_repo.get(version).await?.is_none() {
+                return Err(ApiError::Validation(
+                    "Firmware version has not been uploaded",
+                ));
+            }
+        }
+        DeviceCommand::ShowImage { image } => {
+            validate_entries(&image_repo, &layout_repo, std::slice::from_ref(image)).await?;
+        }
+    }
+
+    let queued = device_command_repo.enqueue(&id, &command).await?;
+
+    info!(msg = "Device command queued", command_id = queued.id);
+
+    Ok((StatusCode::CREATED, Json(queued)))
+}
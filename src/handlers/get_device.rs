@@ -1,30 +1,52 @@
 use axum::{
     Json,
     extract::{Extension, Path},
-    http::StatusCode,
 };
 use tracing::instrument;
 
-use crate::{models::DeviceInfo, repositories::device::DeviceRepo};
+use crate::{
+    config::StatusSettings,
+    error::ApiError,
+    models::{Device, DeviceInfo},
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo},
+    utils::unix_timestamp,
+};
+
+/// Describes a device with its status judged the way alerting does, on the settings it inherits
+/// from its group
+pub(crate) async fn device_info(
+    device_group_repo: &DeviceGroupRepo,
+    status_settings: &StatusSettings,
+    device: Device,
+) -> anyhow::Result<DeviceInfo> {
+    let group = match &device.group_id {
+        Some(group_id) => device_group_repo.get(group_id).await?,
+        None => None,
+    };
+
+    Ok(DeviceInfo::new(
+        device,
+        group.as_ref(),
+        status_settings,
+        unix_timestamp(),
+    ))
+}
 
-#[instrument(name = "handlers.get_device", skip(device_repo, id), fields(device_id = %id))]
+#[instrument(
+    name = "handlers.get_device",
+    skip(device_repo, device_group_repo, status_settings, id),
+    fields(device_id = %id)
+)]
 pub async fn get_device_handler(
     Path(id): Path<String>,
     Extension(device_repo): Extension<DeviceRepo>,
-) -> Result<Json<DeviceInfo>, (StatusCode, &'static str)> {
-    match device_repo
-        .get_by_id(&id)
-        .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?
-    {
-        Some(device) => Ok(Json(DeviceInfo {
-            id: device.id,
-            mac: device.mac,
-            rssi: device.rssi,
-            battery_voltage: device.battery_voltage,
-            fw_version: device.fw_version,
-            refresh_rate: device.refresh_rate,
-        })),
-        _ => Err((StatusCode::NOT_FOUND, "Device not found")),
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(status_settings): Extension<StatusSettings>,
+) -> Result<Json<DeviceInfo>, ApiError> {
+    match device_repo.get_by_id(&id).await? {
+        Some(device) => Ok(Json(
+            device_info(&device_group_repo, &status_settings, device).await?,
+        )),
+        _ => Err(ApiError::NotFound("Device not found")),
     }
 }
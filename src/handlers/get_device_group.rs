@@ -0,0 +1,18 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::DeviceGroup, repositories::device_group::DeviceGroupRepo};
+
+#[instrument(name = "handlers.get_device_group", skip(device_group_repo, id), fields(group_id = %id))]
+pub async fn get_device_group_handler(
+    Path(id): Path<String>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+) -> Result<Json<DeviceGroup>, ApiError> {
+    match device_group_repo.get(&id).await? {
+        Some(group) => Ok(Json(group)),
+        _ => Err(ApiError::NotFound("Device group not found")),
+    }
+}
@@ -1,19 +1,15 @@
-use axum::{Extension, Json, extract::Path, http::StatusCode};
+use axum::{Extension, Json, extract::Path};
 use tracing::instrument;
 
-use crate::repositories::device::DeviceRepo;
+use crate::{error::ApiError, repositories::device::DeviceRepo};
 
 #[instrument(name = "handlers.get_device_images", skip(device_repo, id), fields(device_id = %id))]
 pub async fn get_device_images_handler(
     Path(id): Path<String>,
     Extension(device_repo): Extension<DeviceRepo>,
-) -> Result<Json<Vec<String>>, (StatusCode, &'static str)> {
-    match device_repo
-        .get_by_id(&id)
-        .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?
-    {
+) -> Result<Json<Vec<String>>, ApiError> {
+    match device_repo.get_by_id(&id).await? {
         Some(device) => Ok(Json(device.images)),
-        _ => Err((StatusCode::NOT_FOUND, "Device not found")),
+        _ => Err(ApiError::NotFound("Device not found")),
     }
 }
@@ -0,0 +1,31 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, Query},
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    models::{DeviceLog, DeviceLogQuery},
+    repositories::{device::DeviceRepo, device_log::DeviceLogRepo},
+};
+
+#[instrument(
+    name = "handlers.get_device_logs",
+    skip(device_repo, device_log_repo, id),
+    fields(device_id = %id)
+)]
+pub async fn get_device_logs_handler(
+    Path(id): Path<String>,
+    Query(query): Query<DeviceLogQuery>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_log_repo): Extension<DeviceLogRepo>,
+) -> Result<Json<Vec<DeviceLog>>, ApiError> {
+    if device_repo.get_by_id(&id).await?.is_none() {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    let logs = device_log_repo.list(&id, &query).await?;
+
+    Ok(Json(logs))
+}
@@ -0,0 +1,19 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::instrument;
+
+use crate::{error::ApiError, repositories::device::DeviceRepo, schedule::ScheduleRule};
+
+#[instrument(name = "handlers.get_device_schedule", skip(device_repo, id), fields(device_id = %id))]
+pub async fn get_device_schedule_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+) -> Result<Json<Vec<ScheduleRule>>, ApiError> {
+    device_repo
+        .get_by_id(&id)
+        .await?
+        .map(|device| Json(device.schedule))
+        .ok_or(ApiError::NotFound("Device not found"))
+}
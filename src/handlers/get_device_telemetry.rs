@@ -0,0 +1,73 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, Query},
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    models::{TelemetryQuery, TelemetrySeries},
+    repositories::{device::DeviceRepo, telemetry::TelemetryRepo},
+    utils::unix_timestamp,
+};
+
+/// Window returned when the query does not specify `from`
+const DEFAULT_RANGE: i64 = 7 * 24 * 60 * 60;
+
+/// Parses a bucket width in seconds, optionally suffixed with `s`, `m`, `h` or `d`
+fn parse_bucket(bucket: &str) -> Option<i64> {
+    let (value, unit) = match bucket.char_indices().last()? {
+        (i, 's') => (&bucket[..i], 1),
+        (i, 'm') => (&bucket[..i], 60),
+        (i, 'h') => (&bucket[..i], 60 * 60),
+        (i, 'd') => (&bucket[..i], 24 * 60 * 60),
+        _ => (bucket, 1),
+    };
+
+    value
+        .parse::<i64>()
+        .ok()
+        .and_then(|value| value.checked_mul(unit))
+        .filter(|seconds| *seconds > 0)
+}
+
+#[instrument(
+    name = "handlers.get_device_telemetry",
+    skip(device_repo, telemetry_repo, id),
+    fields(device_id = %id)
+)]
+pub async fn get_device_telemetry_handler(
+    Path(id): Path<String>,
+    Query(query): Query<TelemetryQuery>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(telemetry_repo): Extension<TelemetryRepo>,
+) -> Result<Json<TelemetrySeries>, ApiError> {
+    let bucket = match query.bucket.as_deref() {
+        Some(bucket) => Some(parse_bucket(bucket).ok_or(ApiError::Validation(
+            "Bucket must be a positive number of seconds, minutes (m), hours (h) or days (d)",
+        ))?),
+        None => None,
+    };
+
+    let to = query.to.unwrap_or_else(unix_timestamp);
+    let from = query.from.unwrap_or(to - DEFAULT_RANGE);
+
+    if from > to {
+        return Err(ApiError::Validation(
+            "The start of the range must be before the end",
+        ));
+    }
+
+    if device_repo.get_by_id(&id).await?.is_none() {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    let series = match bucket {
+        Some(bucket) => {
+            TelemetrySeries::Bucketed(telemetry_repo.aggregate(&id, from, to, bucket).await?)
+        }
+        None => TelemetrySeries::Raw(telemetry_repo.list(&id, from, to).await?),
+    };
+
+    Ok(Json(series))
+}
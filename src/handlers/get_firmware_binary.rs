@@ -0,0 +1,28 @@
+use axum::{
+    extract::{Extension, Path},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use crate::{error::ApiError, repositories::firmware::FirmwareRepo};
+
+#[instrument(name = "handlers.get_firmware_binary", skip(firmware_repo, version), fields(version = %version))]
+pub async fn get_firmware_binary_handler(
+    Path(version): Path<String>,
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+) -> Result<impl IntoResponse, ApiError> {
+    match firmware_repo.get_data(&version).await? {
+        Some(data) => Ok((
+            [
+                (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"firmware-{version}.bin\""),
+                ),
+            ],
+            data,
+        )),
+        _ => Err(ApiError::NotFound("Firmware not found")),
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Extension, Path},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+
+use crate::{error::ApiError, repositories::image::ImageRepo, utils::get_optional_header};
+
+/// Images are addressed by their content hash so the content behind a URL never changes
+const IMAGE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+#[instrument(name = "handlers.get_image", skip(image_repo, headers, id), fields(image_id = %id))]
+pub async fn get_image_handler(
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Extension(image_repo): Extension<ImageRepo>,
+) -> Result<Response, ApiError> {
+    let content = image_repo
+        .get_content(&id)
+        .await?
+        .ok_or(ApiError::NotFound("Image not found"))?;
+
+    let etag = format!("\"{id}\"");
+
+    if get_optional_header(&headers, &header::IF_NONE_MATCH) == Some(etag.as_str()) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::ETAG, etag),
+                (header::CACHE_CONTROL, IMAGE_CACHE_CONTROL.to_string()),
+            ],
+        )
+            .into_response());
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content.content_type),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, IMAGE_CACHE_CONTROL.to_string()),
+        ],
+        content.data,
+    )
+        .into_response())
+}
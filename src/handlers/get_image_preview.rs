@@ -0,0 +1,32 @@
+use axum::{
+    extract::{Extension, Path, Query},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use crate::{
+    convert::{ConversionOptions, convert_to_bmp},
+    error::ApiError,
+    repositories::image::ImageRepo,
+};
+
+#[instrument(name = "handlers.get_image_preview", skip(image_repo, id), fields(image_id = %id))]
+pub async fn get_image_preview_handler(
+    Path(id): Path<String>,
+    Query(options): Query<ConversionOptions>,
+    Extension(image_repo): Extension<ImageRepo>,
+) -> Result<impl IntoResponse, ApiError> {
+    options.validate().map_err(ApiError::Validation)?;
+
+    let content = image_repo
+        .get_content(&id)
+        .await?
+        .ok_or(ApiError::NotFound("Image not found"))?;
+
+    let bitmap = tokio::task::spawn_blocking(move || convert_to_bmp(&content.data, &options))
+        .await?
+        .map_err(|_| ApiError::Validation("Image could not be decoded"))?;
+
+    Ok(([(header::CONTENT_TYPE, "image/bmp")], bitmap))
+}
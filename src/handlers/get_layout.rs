@@ -0,0 +1,18 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::Layout, repositories::layout::LayoutRepo};
+
+#[instrument(name = "handlers.get_layout", skip(layout_repo, id), fields(layout_id = %id))]
+pub async fn get_layout_handler(
+    Path(id): Path<String>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+) -> Result<Json<Layout>, ApiError> {
+    match layout_repo.get(&id).await? {
+        Some(layout) => Ok(Json(layout)),
+        _ => Err(ApiError::NotFound("Layout not found")),
+    }
+}
@@ -0,0 +1,8 @@
+use axum::Json;
+use serde_json::{Value, json};
+
+/// Answers as long as the process is able to serve requests, dependencies are checked by
+/// `/readyz`
+pub async fn healthz_handler() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
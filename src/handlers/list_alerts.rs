@@ -0,0 +1,21 @@
+use axum::{
+    Json,
+    extract::{Extension, Query},
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    models::{Alert, AlertListQuery},
+    repositories::alert::AlertRepo,
+};
+
+#[instrument(name = "handlers.list_alerts", skip(alert_repo))]
+pub async fn list_alerts_handler(
+    Query(query): Query<AlertListQuery>,
+    Extension(alert_repo): Extension<AlertRepo>,
+) -> Result<Json<Vec<Alert>>, ApiError> {
+    let alerts = alert_repo.list(&query).await?;
+
+    Ok(Json(alerts))
+}
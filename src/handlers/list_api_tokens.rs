@@ -0,0 +1,13 @@
+use axum::{Extension, Json};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::ApiToken, repositories::api_token::ApiTokenRepo};
+
+#[instrument(name = "handlers.list_api_tokens", skip(api_token_repo))]
+pub async fn list_api_tokens_handler(
+    Extension(api_token_repo): Extension<ApiTokenRepo>,
+) -> Result<Json<Vec<ApiToken>>, ApiError> {
+    let tokens = api_token_repo.list().await?;
+
+    Ok(Json(tokens))
+}
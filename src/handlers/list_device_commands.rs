@@ -0,0 +1,30 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    models::QueuedDeviceCommand,
+    repositories::{device::DeviceRepo, device_command::DeviceCommandRepo},
+};
+
+#[instrument(
+    name = "handlers.list_device_commands",
+    skip(device_repo, device_command_repo, id),
+    fields(device_id = %id)
+)]
+pub async fn list_device_commands_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_command_repo): Extension<DeviceCommandRepo>,
+) -> Result<Json<Vec<QueuedDeviceCommand>>, ApiError> {
+    if device_repo.get_by_id(&id).await?.is_none() {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    let commands = device_command_repo.list(&id).await?;
+
+    Ok(Json(commands))
+}
@@ -0,0 +1,13 @@
+use axum::{Extension, Json};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::DeviceGroup, repositories::device_group::DeviceGroupRepo};
+
+#[instrument(name = "handlers.list_device_groups", skip(device_group_repo))]
+pub async fn list_device_groups_handler(
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+) -> Result<Json<Vec<DeviceGroup>>, ApiError> {
+    let groups = device_group_repo.list().await?;
+
+    Ok(Json(groups))
+}
@@ -1,28 +1,58 @@
-use crate::{models::DeviceInfo, repositories::device::DeviceRepo};
+use std::collections::HashMap;
+
+use crate::{
+    config::StatusSettings,
+    error::ApiError,
+    models::{DeviceInfo, DeviceListQuery},
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo},
+    utils::unix_timestamp,
+};
 use axum::Extension;
 use axum::Json;
-use axum::http::StatusCode;
+use axum::extract::Query;
 use tracing::instrument;
 
-#[instrument(name = "handlers.list_devices", skip(device_repo))]
+#[instrument(
+    name = "handlers.list_devices",
+    skip(device_repo, device_group_repo, status_settings)
+)]
 pub async fn list_devices_handler(
+    Query(query): Query<DeviceListQuery>,
     Extension(device_repo): Extension<DeviceRepo>,
-) -> Result<Json<Vec<DeviceInfo>>, (StatusCode, &'static str)> {
-    let devices = device_repo
-        .list()
-        .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?;
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(status_settings): Extension<StatusSettings>,
+) -> Result<Json<Vec<DeviceInfo>>, ApiError> {
+    let devices = match (&query.group, query.approval_status) {
+        (Some(group_id), _) => device_repo.list_by_group(group_id).await,
+        (None, Some(status)) => device_repo.list_by_approval_status(status).await,
+        (None, None) => device_repo.list().await,
+    }?;
+    let now = unix_timestamp();
+
+    // Groups are loaded once for the status of their members, and not at all without any
+    let groups: HashMap<_, _> = if devices.iter().any(|device| device.group_id.is_some()) {
+        device_group_repo
+            .list()
+            .await?
+            .into_iter()
+            .map(|group| (group.id.clone(), group))
+            .collect()
+    } else {
+        HashMap::new()
+    };
 
     Ok(Json(
         devices
-            .iter()
-            .map(|device| DeviceInfo {
-                id: device.id.clone(),
-                mac: device.mac.clone(),
-                rssi: device.rssi,
-                battery_voltage: device.battery_voltage,
-                fw_version: device.fw_version.clone(),
-                refresh_rate: device.refresh_rate,
+            .into_iter()
+            // Group members are filtered by status here rather than in another query
+            .filter(|device| {
+                query
+                    .approval_status
+                    .is_none_or(|status| device.approval_status == status)
+            })
+            .map(|device| {
+                let group = device.group_id.as_ref().and_then(|id| groups.get(id));
+                DeviceInfo::new(device, group, &status_settings, now)
             })
             .collect(),
     ))
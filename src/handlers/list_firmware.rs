@@ -0,0 +1,13 @@
+use axum::{Extension, Json};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::Firmware, repositories::firmware::FirmwareRepo};
+
+#[instrument(name = "handlers.list_firmware", skip(firmware_repo))]
+pub async fn list_firmware_handler(
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+) -> Result<Json<Vec<Firmware>>, ApiError> {
+    let firmware = firmware_repo.list().await?;
+
+    Ok(Json(firmware))
+}
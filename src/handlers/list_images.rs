@@ -0,0 +1,13 @@
+use axum::{Extension, Json};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::Image, repositories::image::ImageRepo};
+
+#[instrument(name = "handlers.list_images", skip(image_repo))]
+pub async fn list_images_handler(
+    Extension(image_repo): Extension<ImageRepo>,
+) -> Result<Json<Vec<Image>>, ApiError> {
+    let images = image_repo.list().await?;
+
+    Ok(Json(images))
+}
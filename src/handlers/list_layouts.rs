@@ -0,0 +1,13 @@
+use axum::{Extension, Json};
+use tracing::instrument;
+
+use crate::{error::ApiError, models::Layout, repositories::layout::LayoutRepo};
+
+#[instrument(name = "handlers.list_layouts", skip(layout_repo))]
+pub async fn list_layouts_handler(
+    Extension(layout_repo): Extension<LayoutRepo>,
+) -> Result<Json<Vec<Layout>>, ApiError> {
+    let layouts = layout_repo.list().await?;
+
+    Ok(Json(layouts))
+}
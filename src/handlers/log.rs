@@ -1,13 +1,49 @@
-use axum::{Json, body::Bytes};
-use tracing::instrument;
+use axum::{Json, body::Bytes, extract::Extension, http::HeaderMap};
+use tracing::{info, instrument};
 
-#[instrument(name = "handlers.log", skip(body))]
-pub async fn log_handler(body: Bytes) -> Json<serde_json::Value> {
-    // Just grab body for future DB insert
-    let _body_str = String::from_utf8_lossy(&body);
+use crate::{
+    error::ApiError,
+    headers::HEADER_ACCESS_TOKEN,
+    models::{DeviceLogPayload, NewDeviceLog},
+    repositories::{device::DeviceRepo, device_log::DeviceLogRepo},
+    utils::{get_header, unix_timestamp},
+};
 
-    Json(serde_json::json!({
+#[instrument(
+    name = "handlers.log",
+    skip(headers, device_repo, device_log_repo, body)
+)]
+pub async fn log_handler(
+    headers: HeaderMap,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_log_repo): Extension<DeviceLogRepo>,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let access_token = get_header(&headers, &HEADER_ACCESS_TOKEN);
+
+    let Some(device) = device_repo.get_by_api_key(access_token).await? else {
+        info!(msg = "Rejecting log upload from unknown device");
+        return Err(ApiError::Unauthorized("Invalid access token"));
+    };
+
+    let payload: DeviceLogPayload =
+        serde_json::from_slice(&body).map_err(|_| ApiError::BadRequest("Invalid log payload"))?;
+
+    let received_at = unix_timestamp();
+    let logs: Vec<NewDeviceLog> = payload
+        .into_entries()
+        .into_iter()
+        .map(|entry| NewDeviceLog::from_entry(entry, received_at))
+        .collect();
+
+    device_log_repo
+        .create(&device.id, received_at, &logs)
+        .await?;
+
+    info!(msg = "Stored device logs", device_id = %device.id, count = logs.len());
+
+    Ok(Json(serde_json::json!({
         "status": 200,
         "msg": "log received"
-    }))
+    })))
 }
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use axum::{Extension, http::header, response::IntoResponse};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    metrics::{HttpMetrics, render_devices},
+    repositories::device::DeviceRepo,
+    utils::unix_timestamp,
+};
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[instrument(name = "handlers.metrics", skip(metrics, device_repo))]
+pub async fn metrics_handler(
+    Extension(metrics): Extension<Arc<HttpMetrics>>,
+    Extension(device_repo): Extension<DeviceRepo>,
+) -> Result<impl IntoResponse, ApiError> {
+    let devices = device_repo.list().await?;
+
+    let mut body = String::new();
+    metrics.render(&mut body);
+    render_devices(&mut body, &devices, unix_timestamp());
+
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], body))
+}
@@ -1,15 +1,105 @@
+pub mod approve_device;
+pub mod convert_images;
+pub mod create_api_token;
+pub mod create_device_group;
+pub mod create_layout;
+pub mod delete_api_token;
+pub mod delete_device;
+pub mod delete_device_group;
+pub mod delete_firmware;
+pub mod delete_image;
+pub mod delete_layout;
 pub mod display;
+pub mod enqueue_device_command;
 pub mod get_device;
+pub mod get_device_group;
 pub mod get_device_images;
+pub mod get_device_logs;
+pub mod get_device_schedule;
+pub mod get_device_telemetry;
+pub mod get_firmware_binary;
+pub mod get_image;
+pub mod get_image_preview;
+pub mod get_layout;
+pub mod healthz;
+pub mod list_alerts;
+pub mod list_api_tokens;
+pub mod list_device_commands;
+pub mod list_device_groups;
 pub mod list_devices;
+pub mod list_firmware;
+pub mod list_images;
+pub mod list_layouts;
 pub mod log;
+pub mod metrics;
+pub mod patch_device;
+pub mod patch_device_group;
+pub mod pending_approval;
+pub mod preview_image;
+pub mod preview_layout;
+pub mod put_device_group_devices;
+pub mod put_device_group_images;
 pub mod put_device_images;
+pub mod put_device_schedule;
+pub mod put_firmware;
+pub mod put_layout;
+pub mod readyz;
+pub mod reject_device;
+pub mod render_layout;
+pub mod rotate_device_key;
 pub mod setup;
+pub mod sleeping_screen;
+pub mod upload_images;
 
+pub use approve_device::approve_device_handler;
+pub use convert_images::convert_images_handler;
+pub use create_api_token::create_api_token_handler;
+pub use create_device_group::create_device_group_handler;
+pub use create_layout::create_layout_handler;
+pub use delete_api_token::delete_api_token_handler;
+pub use delete_device::delete_device_handler;
+pub use delete_device_group::delete_device_group_handler;
+pub use delete_firmware::delete_firmware_handler;
+pub use delete_image::delete_image_handler;
+pub use delete_layout::delete_layout_handler;
 pub use display::display_handler;
+pub use enqueue_device_command::enqueue_device_command_handler;
 pub use get_device::get_device_handler;
+pub use get_device_group::get_device_group_handler;
 pub use get_device_images::get_device_images_handler;
+pub use get_device_logs::get_device_logs_handler;
+pub use get_device_schedule::get_device_schedule_handler;
+pub use get_device_telemetry::get_device_telemetry_handler;
+pub use get_firmware_binary::get_firmware_binary_handler;
+pub use get_image::get_image_handler;
+pub use get_image_preview::get_image_preview_handler;
+pub use get_layout::get_layout_handler;
+pub use healthz::healthz_handler;
+pub use list_alerts::list_alerts_handler;
+pub use list_api_tokens::list_api_tokens_handler;
+pub use list_device_commands::list_device_commands_handler;
+pub use list_device_groups::list_device_groups_handler;
 pub use list_devices::list_devices_handler;
+pub use list_firmware::list_firmware_handler;
+pub use list_images::list_images_handler;
+pub use list_layouts::list_layouts_handler;
 pub use log::log_handler;
+pub use metrics::metrics_handler;
+pub use patch_device::patch_device_handler;
+pub use patch_device_group::patch_device_group_handler;
+pub use pending_approval::pending_approval_handler;
+pub use preview_image::preview_image_handler;
+pub use preview_layout::preview_layout_handler;
+pub use put_device_group_devices::put_device_group_devices_handler;
+pub use put_device_group_images::put_device_group_images_handler;
 pub use put_device_images::put_device_images_handler;
+pub use put_device_schedule::put_device_schedule_handler;
+pub use put_firmware::put_firmware_handler;
+pub use put_layout::put_layout_handler;
+pub use readyz::readyz_handler;
+pub use reject_device::reject_device_handler;
+pub use render_layout::render_layout_handler;
+pub use rotate_device_key::rotate_device_key_handler;
 pub use setup::setup_handler;
+pub use sleeping_screen::sleeping_screen_handler;
+pub use upload_images::upload_images_handler;
@@ -0,0 +1,104 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use semver::Version;
+use tracing::{info, instrument};
+
+use crate::{
+    config::StatusSettings,
+    error::ApiError,
+    handlers::get_device::device_info,
+    models::{DeviceInfo, DevicePatch},
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo, firmware::FirmwareRepo},
+    schedule::parse_timezone,
+};
+
+pub const MIN_REFRESH_RATE: i64 = 1;
+pub const MAX_REFRESH_RATE: i64 = 86_400;
+
+/// Checks the refresh rate and firmware target shared by device and group settings
+pub(crate) async fn validate_settings(
+    firmware_repo: &FirmwareRepo,
+    desired_refresh_rate: Option<i64>,
+    target_fw_version: Option<&str>,
+) -> Result<(), ApiError> {
+    if let Some(rate) = desired_refresh_rate
+        && !(MIN_REFRESH_RATE..=MAX_REFRESH_RATE).contains(&rate)
+    {
+        return Err(ApiError::Validation(
+            "Refresh rate must be between 1 and 86400 seconds",
+        ));
+    }
+
+    if let Some(version) = target_fw_version {
+        if Version::parse(version).is_err() {
+            return Err(ApiError::Validation(
+                "Firmware version must be a valid semantic version",
+            ));
+        }
+
+        if firmware_repo.get(version).await?.is_none() {
+            return Err(ApiError::Validation(
+                "Firmware version has not been uploaded",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument(
+    name = "handlers.patch_device",
+    skip(device_repo, device_group_repo, firmware_repo, status_settings, id, patch),
+    fields(device_id = %id)
+)]
+pub async fn patch_device_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+    Extension(status_settings): Extension<StatusSettings>,
+    Json(patch): Json<DevicePatch>,
+) -> Result<Json<DeviceInfo>, ApiError> {
+    validate_settings(
+        &firmware_repo,
+        patch.desired_refresh_rate.flatten(),
+        patch.target_fw_version.as_ref().and_then(Option::as_deref),
+    )
+    .await?;
+
+    if let Some(Some(timezone)) = &patch.timezone
+        && parse_timezone(timezone).is_none()
+    {
+        return Err(ApiError::Validation(
+            "Timezone must be an IANA timezone name",
+        ));
+    }
+
+    if let Some(Some(group_id)) = &patch.group_id
+        && device_group_repo.get(group_id).await?.is_none()
+    {
+        return Err(ApiError::Validation("Unknown device group"));
+    }
+
+    if let Some(Some(quiet_hours)) = &patch.quiet_hours {
+        quiet_hours.validate().map_err(ApiError::Validation)?;
+    }
+
+    device_repo.update(&id, &patch).await?;
+
+    match device_repo.get_by_id(&id).await? {
+        Some(device) => {
+            info!(
+                msg = "Device settings updated",
+                desired_refresh_rate = ?device.desired_refresh_rate,
+                target_fw_version = ?device.target_fw_version
+            );
+            Ok(Json(
+                device_info(&device_group_repo, &status_settings, device).await?,
+            ))
+        }
+        _ => Err(ApiError::NotFound("Device not found")),
+    }
+}
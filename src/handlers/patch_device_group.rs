@@ -0,0 +1,56 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    handlers::patch_device::validate_settings,
+    models::{DeviceGroup, DeviceGroupPatch},
+    repositories::{device_group::DeviceGroupRepo, firmware::FirmwareRepo},
+};
+
+/// Group settings apply to every member that does not set its own
+#[instrument(
+    name = "handlers.patch_device_group",
+    skip(device_group_repo, firmware_repo, id, patch),
+    fields(group_id = %id)
+)]
+pub async fn patch_device_group_handler(
+    Path(id): Path<String>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+    Json(mut patch): Json<DeviceGroupPatch>,
+) -> Result<Json<DeviceGroup>, ApiError> {
+    if let Some(name) = &mut patch.name {
+        *name = name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(ApiError::Validation("Group name is required"));
+        }
+    }
+
+    validate_settings(
+        &firmware_repo,
+        patch.desired_refresh_rate.flatten(),
+        patch.target_fw_version.as_ref().and_then(Option::as_deref),
+    )
+    .await?;
+
+    if !device_group_repo.update(&id, &patch).await? {
+        return Err(ApiError::NotFound("Device group not found"));
+    }
+
+    match device_group_repo.get(&id).await? {
+        Some(group) => {
+            info!(
+                msg = "Device group settings updated",
+                desired_refresh_rate = ?group.desired_refresh_rate,
+                target_fw_version = ?group.target_fw_version
+            );
+            Ok(Json(group))
+        }
+        _ => Err(ApiError::NotFound("Device group not found")),
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Extension, Path},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    layout::{message_screen, render_to_bmp},
+    models::ApprovalStatus,
+    repositories::device::DeviceRepo,
+};
+
+/// Renders a [`message_screen`] as the bitmap devices download
+pub(crate) async fn message_screen_response(
+    title: &'static str,
+    subtitle: String,
+) -> Result<impl IntoResponse, ApiError> {
+    let spec = message_screen(title, &subtitle);
+
+    let bitmap =
+        tokio::task::spawn_blocking(move || render_to_bmp(&spec, &HashMap::new())).await??;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/bmp"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        bitmap,
+    ))
+}
+
+/// Screen telling whoever holds the device which ID to approve
+#[instrument(name = "handlers.pending_approval", skip(device_repo, id), fields(device_id = %id))]
+pub async fn pending_approval_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Only pending devices get a screen so the route cannot render arbitrary text
+    device_repo
+        .get_by_id(&id)
+        .await?
+        .filter(|device| device.approval_status == ApprovalStatus::Pending)
+        .ok_or(ApiError::NotFound("Device not found"))?;
+
+    message_screen_response("Waiting for approval", format!("Device {id}")).await
+}
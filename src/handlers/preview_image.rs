@@ -0,0 +1,21 @@
+use axum::{body::Bytes, extract::Query, http::header, response::IntoResponse};
+use tracing::instrument;
+
+use crate::{
+    convert::{ConversionOptions, convert_to_bmp},
+    error::ApiError,
+};
+
+#[instrument(name = "handlers.preview_image", skip(body))]
+pub async fn preview_image_handler(
+    Query(options): Query<ConversionOptions>,
+    body: Bytes,
+) -> Result<impl IntoResponse, ApiError> {
+    options.validate().map_err(ApiError::Validation)?;
+
+    let bitmap = tokio::task::spawn_blocking(move || convert_to_bmp(&body, &options))
+        .await?
+        .map_err(|_| ApiError::Validation("Image could not be decoded"))?;
+
+    Ok(([(header::CONTENT_TYPE, "image/bmp")], bitmap))
+}
@@ -0,0 +1,19 @@
+use axum::{Json, extract::Extension, http::header, response::IntoResponse};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError, handlers::render_layout::render_spec, layout::LayoutSpec,
+    repositories::image::ImageRepo,
+};
+
+#[instrument(name = "handlers.preview_layout", skip(image_repo, spec))]
+pub async fn preview_layout_handler(
+    Extension(image_repo): Extension<ImageRepo>,
+    Json(spec): Json<LayoutSpec>,
+) -> Result<impl IntoResponse, ApiError> {
+    spec.validate().map_err(ApiError::Validation)?;
+
+    let bitmap = render_spec(&image_repo, spec).await?;
+
+    Ok(([(header::CONTENT_TYPE, "image/bmp")], bitmap))
+}
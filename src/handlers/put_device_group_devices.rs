@@ -0,0 +1,35 @@
+use axum::{Extension, Json, extract::Path};
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo},
+};
+
+/// Replaces the members of a group in one go. Listed devices move over from any other group,
+/// members that are not listed are left without a group.
+#[instrument(
+    name = "handlers.put_device_group_devices",
+    skip(device_repo, device_group_repo, id, device_ids),
+    fields(group_id = %id)
+)]
+pub async fn put_device_group_devices_handler(
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Path(id): Path<String>,
+    Json(device_ids): Json<Vec<String>>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    if device_group_repo.get(&id).await?.is_none() {
+        return Err(ApiError::NotFound("Device group not found"));
+    }
+
+    if !device_repo.set_group_members(&id, &device_ids).await? {
+        return Err(ApiError::Validation("Unknown device ID"));
+    }
+
+    info!(
+        msg = "Device group members replaced",
+        members = device_ids.len()
+    );
+    Ok(Json(device_ids))
+}
@@ -0,0 +1,25 @@
+use axum::{Extension, Json, extract::Path};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    handlers::put_device_images::validate_entries,
+    repositories::{device_group::DeviceGroupRepo, image::ImageRepo, layout::LayoutRepo},
+};
+
+/// Members with an empty rotation of their own show the group rotation
+#[instrument(name = "handlers.put_device_group_images", skip(device_group_repo, image_repo, layout_repo, id, images), fields(group_id = %id))]
+pub async fn put_device_group_images_handler(
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Path(id): Path<String>,
+    Json(images): Json<Vec<String>>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    validate_entries(&image_repo, &layout_repo, &images).await?;
+
+    match device_group_repo.update_images(&id, &images).await? {
+        true => Ok(Json(images)),
+        false => Err(ApiError::NotFound("Device group not found")),
+    }
+}
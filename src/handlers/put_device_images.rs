@@ -1,18 +1,44 @@
-use crate::repositories::device::DeviceRepo;
+use crate::{
+    error::ApiError,
+    repositories::{device::DeviceRepo, image::ImageRepo, layout::LayoutRepo},
+    utils::{image_reference, layout_reference},
+};
 
-use axum::{Extension, Json, extract::Path, http::StatusCode};
+use axum::{Extension, Json, extract::Path};
 use tracing::instrument;
 
-#[instrument(name = "handlers.put_device_images", skip(device_repo, id, images), fields(device_id = %id))]
+/// Checks that the `image:` and `layout:` references among `entries` exist
+pub(crate) async fn validate_entries(
+    image_repo: &ImageRepo,
+    layout_repo: &LayoutRepo,
+    entries: &[String],
+) -> Result<(), ApiError> {
+    for hash in entries.iter().filter_map(|entry| image_reference(entry)) {
+        if image_repo.get(hash).await?.is_none() {
+            return Err(ApiError::Validation("Unknown image reference"));
+        }
+    }
+
+    for layout_id in entries.iter().filter_map(|entry| layout_reference(entry)) {
+        if layout_repo.get(layout_id).await?.is_none() {
+            return Err(ApiError::Validation("Unknown layout reference"));
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument(name = "handlers.put_device_images", skip(device_repo, image_repo, layout_repo, id, images), fields(device_id = %id))]
 pub async fn put_device_images_handler(
     Extension(device_repo): Extension<DeviceRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Extension(layout_repo): Extension<LayoutRepo>,
     Path(id): Path<String>,
     Json(images): Json<Vec<String>>,
-) -> Result<Json<Vec<String>>, (StatusCode, &'static str)> {
-    device_repo
-        .update_images(&id, &images)
-        .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?;
+) -> Result<Json<Vec<String>>, ApiError> {
+    validate_entries(&image_repo, &layout_repo, &images).await?;
+
+    device_repo.update_images(&id, &images).await?;
 
     Ok(Json(images))
 }
@@ -0,0 +1,43 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    error::ApiError,
+    handlers::put_device_images::validate_entries,
+    repositories::{device::DeviceRepo, image::ImageRepo, layout::LayoutRepo},
+    schedule::{ScheduleRule, validate_schedule},
+};
+
+/// Rules are evaluated in order and the first one matching the local time selects the rotation
+#[instrument(
+    name = "handlers.put_device_schedule",
+    skip(device_repo, image_repo, layout_repo, id, schedule),
+    fields(device_id = %id)
+)]
+pub async fn put_device_schedule_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Json(schedule): Json<Vec<ScheduleRule>>,
+) -> Result<Json<Vec<ScheduleRule>>, ApiError> {
+    validate_schedule(&schedule).map_err(ApiError::Validation)?;
+
+    let entries: Vec<String> = schedule
+        .iter()
+        .flat_map(|rule| rule.images.iter().cloned())
+        .collect();
+
+    validate_entries(&image_repo, &layout_repo, &entries).await?;
+
+    if !device_repo.update_schedule(&id, &schedule).await? {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    info!(msg = "Device schedule updated", rules = schedule.len());
+
+    Ok(Json(schedule))
+}
@@ -0,0 +1,46 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Extension, Path},
+    http::StatusCode,
+};
+use semver::Version;
+use sha2::{Digest, Sha256};
+use tracing::{info, instrument};
+
+use crate::{error::ApiError, models::Firmware, repositories::firmware::FirmwareRepo};
+
+/// Upper bound for uploaded firmware binaries, comfortably above the ESP32 flash partition size
+pub const MAX_FIRMWARE_SIZE: usize = 16 * 1024 * 1024;
+
+#[instrument(name = "handlers.put_firmware", skip(firmware_repo, version, body), fields(version = %version))]
+pub async fn put_firmware_handler(
+    Path(version): Path<String>,
+    Extension(firmware_repo): Extension<FirmwareRepo>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<Firmware>), ApiError> {
+    if Version::parse(&version).is_err() {
+        return Err(ApiError::Validation(
+            "Firmware version must be a valid semantic version",
+        ));
+    }
+
+    if body.is_empty() {
+        return Err(ApiError::Validation("Firmware binary is empty"));
+    }
+
+    if firmware_repo.get(&version).await?.is_some() {
+        return Err(ApiError::Conflict("Firmware version already exists"));
+    }
+
+    let checksum = format!("{:x}", Sha256::digest(&body));
+
+    firmware_repo.create(&version, &checksum, &body).await?;
+
+    info!(msg = "Firmware uploaded", %checksum, size = body.len());
+
+    match firmware_repo.get(&version).await? {
+        Some(firmware) => Ok((StatusCode::CREATED, Json(firmware))),
+        _ => Err(anyhow::anyhow!("Firmware {version} is missing after it was stored").into()),
+    }
+}
@@ -0,0 +1,33 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    handlers::create_layout::validate_layout,
+    models::{Layout, LayoutRequest},
+    repositories::{image::ImageRepo, layout::LayoutRepo},
+};
+
+#[instrument(name = "handlers.put_layout", skip(layout_repo, image_repo, id, request), fields(layout_id = %id))]
+pub async fn put_layout_handler(
+    Path(id): Path<String>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+    Json(request): Json<LayoutRequest>,
+) -> Result<Json<Layout>, ApiError> {
+    let name = request.name.trim();
+
+    validate_layout(&image_repo, name, &request.spec).await?;
+
+    if !layout_repo.update(&id, name, &request.spec).await? {
+        return Err(ApiError::NotFound("Layout not found"));
+    }
+
+    match layout_repo.get(&id).await? {
+        Some(layout) => Ok(Json(layout)),
+        _ => Err(ApiError::NotFound("Layout not found")),
+    }
+}
@@ -0,0 +1,103 @@
+use axum::{Extension, Json, http::StatusCode};
+use tracing::{instrument, warn};
+
+use crate::{
+    db::latest_migration_version,
+    models::{ComponentHealth, HealthStatus, ReadinessComponents, ReadinessReport},
+    repositories::{device::DeviceRepo, health::HealthRepo},
+};
+
+fn component(status: HealthStatus, message: Option<String>) -> ComponentHealth {
+    ComponentHealth { status, message }
+}
+
+#[instrument(name = "handlers.readyz", skip(health_repo, device_repo))]
+pub async fn readyz_handler(
+    Extension(health_repo): Extension<HealthRepo>,
+    Extension(device_repo): Extension<DeviceRepo>,
+) -> (StatusCode, Json<ReadinessReport>) {
+    let database = match health_repo.ping().await {
+        Ok(()) => component(HealthStatus::Ok, None),
+        Err(error) => {
+            warn!(msg = "Database is not responding", %error);
+            component(
+                HealthStatus::Error,
+                Some("Database is not responding".to_string()),
+            )
+        }
+    };
+
+    let expected = latest_migration_version();
+    let migrations = match health_repo.applied_migration().await {
+        Ok(applied) if applied >= expected => component(HealthStatus::Ok, None),
+        Ok(applied) => component(
+            HealthStatus::Error,
+            Some(format!(
+                "Database is at migration {}, expected {}",
+                applied.unwrap_or_default(),
+                expected.unwrap_or_default()
+            )),
+        ),
+        Err(error) => {
+            warn!(msg = "Failed to read applied migrations", %error);
+            component(
+                HealthStatus::Error,
+                Some("Applied migrations could not be read".to_string()),
+            )
+        }
+    };
+
+    // The device store may be another database, migrated from its own set of migrations
+    let devices = match device_repo.ping().await {
+        Ok(()) => {
+            let expected = device_repo.latest_migration();
+            match device_repo.applied_migration().await {
+                Ok(applied) if applied >= expected => component(HealthStatus::Ok, None),
+                Ok(applied) => component(
+                    HealthStatus::Error,
+                    Some(format!(
+                        "Device store is at migration {}, expected {}",
+                        applied.unwrap_or_default(),
+                        expected.unwrap_or_default()
+                    )),
+                ),
+                Err(error) => {
+                    warn!(msg = "Failed to read applied device store migrations", %error);
+                    component(
+                        HealthStatus::Error,
+                        Some("Applied device store migrations could not be read".to_string()),
+                    )
+                }
+            }
+        }
+        Err(error) => {
+            warn!(msg = "Device store is not responding", %error);
+            component(
+                HealthStatus::Error,
+                Some("Device store is not responding".to_string()),
+            )
+        }
+    };
+
+    let ready = [&database, &migrations, &devices]
+        .iter()
+        .all(|component| component.status == HealthStatus::Ok);
+
+    let (status_code, status) = if ready {
+        (StatusCode::OK, HealthStatus::Ok)
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, HealthStatus::Degraded)
+    };
+
+    (
+        status_code,
+        Json(ReadinessReport {
+            status,
+            components: ReadinessComponents {
+                database,
+                migrations,
+                devices,
+            },
+        }),
+    )
+}
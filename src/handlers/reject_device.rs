@@ -0,0 +1,36 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::instrument;
+
+use crate::{
+    config::StatusSettings,
+    error::ApiError,
+    handlers::approve_device::set_approval_status,
+    models::{ApprovalStatus, DeviceInfo},
+    repositories::{device::DeviceRepo, device_group::DeviceGroupRepo},
+};
+
+/// Rejected devices are refused images and cannot register again with the same MAC address
+/// until they are deleted
+#[instrument(
+    name = "handlers.reject_device",
+    skip(device_repo, device_group_repo, status_settings, id),
+    fields(device_id = %id)
+)]
+pub async fn reject_device_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+    Extension(device_group_repo): Extension<DeviceGroupRepo>,
+    Extension(status_settings): Extension<StatusSettings>,
+) -> Result<Json<DeviceInfo>, ApiError> {
+    set_approval_status(
+        &device_repo,
+        &device_group_repo,
+        &status_settings,
+        &id,
+        ApprovalStatus::Rejected,
+    )
+    .await
+}
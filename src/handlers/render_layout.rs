@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Extension, Path},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError,
+    layout::{LayoutSpec, render_to_bmp},
+    repositories::{image::ImageRepo, layout::LayoutRepo},
+};
+
+/// Loads the images a layout draws and renders it into a BMP
+pub(crate) async fn render_spec(
+    image_repo: &ImageRepo,
+    spec: LayoutSpec,
+) -> Result<Vec<u8>, ApiError> {
+    let mut images = HashMap::new();
+
+    for image_id in spec.image_ids() {
+        let content = image_repo
+            .get_content(image_id)
+            .await?
+            .ok_or(ApiError::Validation("Layout references an unknown image"))?;
+
+        images.insert(image_id.to_string(), content.data);
+    }
+
+    // Rendering is CPU bound so keep it off the async workers
+    tokio::task::spawn_blocking(move || render_to_bmp(&spec, &images))
+        .await?
+        .map_err(|_| ApiError::Validation("Image could not be decoded"))
+}
+
+#[instrument(name = "handlers.render_layout", skip(layout_repo, image_repo, id), fields(layout_id = %id))]
+pub async fn render_layout_handler(
+    Path(id): Path<String>,
+    Extension(layout_repo): Extension<LayoutRepo>,
+    Extension(image_repo): Extension<ImageRepo>,
+) -> Result<impl IntoResponse, ApiError> {
+    let layout = layout_repo
+        .get(&id)
+        .await?
+        .ok_or(ApiError::NotFound("Layout not found"))?;
+
+    let bitmap = render_spec(&image_repo, layout.spec).await?;
+
+    // Layouts can be edited in place so devices must not reuse an old render
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/bmp"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        bitmap,
+    ))
+}
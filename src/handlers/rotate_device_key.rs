@@ -0,0 +1,28 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    auth::generate_device_api_key, error::ApiError, models::DeviceApiKey,
+    repositories::device::DeviceRepo,
+};
+
+/// The old key is rejected from now on, which makes the firmware run setup again and pick up
+/// the new key for its MAC address
+#[instrument(name = "handlers.rotate_device_key", skip(device_repo, id), fields(device_id = %id))]
+pub async fn rotate_device_key_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+) -> Result<Json<DeviceApiKey>, ApiError> {
+    let api_key = generate_device_api_key();
+
+    if !device_repo.rotate_api_key(&id, &api_key).await? {
+        return Err(ApiError::NotFound("Device not found"));
+    }
+
+    info!(msg = "Device API key rotated");
+
+    Ok(Json(DeviceApiKey { id, api_key }))
+}
@@ -1,32 +1,44 @@
-use axum::{
-    Json,
-    extract::Extension,
-    http::{HeaderMap, StatusCode},
-};
-use rand::{Rng, distr::Alphanumeric};
+use axum::{Json, extract::Extension, http::HeaderMap};
 use tracing::{info, instrument};
 use uuid::Uuid;
 
 use crate::{
-    config::AppSettings, headers::HEADER_MAC, models::SetupResponse,
-    repositories::device::DeviceRepo, utils::get_optional_header,
+    auth::generate_device_api_key,
+    config::{AppSettings, ApprovalSettings},
+    error::ApiError,
+    headers::HEADER_MAC,
+    models::{ApprovalStatus, SetupResponse},
+    repositories::device::DeviceRepo,
+    utils::get_optional_header,
 };
 
-#[instrument(name = "handlers.setup", skip(headers, device_repo, settings))]
+#[instrument(
+    name = "handlers.setup",
+    skip(headers, device_repo, settings, approval)
+)]
 pub async fn setup_handler(
     headers: HeaderMap,
     Extension(device_repo): Extension<DeviceRepo>,
     Extension(settings): Extension<AppSettings>,
-) -> Result<Json<SetupResponse>, (StatusCode, &'static str)> {
+    Extension(approval): Extension<ApprovalSettings>,
+) -> Result<Json<SetupResponse>, ApiError> {
     let mac = get_optional_header(&headers, &HEADER_MAC);
 
     match mac {
-        Some(mac)
-            if device_repo
-                .exists_by_mac(mac)
-                .await
-                .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))? =>
-        {
+        Some(mac) if device_repo.exists_by_mac(mac).await? => {
+            // A rotated key is handed out once to the device that was sent back through setup
+            if let Some(device) = device_repo.take_pending_setup(mac).await? {
+                info!(msg = "Device set up with rotated API key", ?mac, id = %device.id);
+
+                return Ok(Json(SetupResponse {
+                    status: 200,
+                    api_key: Some(device._api_key),
+                    friendly_id: Some(device.id),
+                    image_url: Some(settings.setup_logo_url.clone()),
+                    filename: Some("empty_state".to_string()),
+                }));
+            }
+
             info!(msg = "Device setup attempted for existing device", ?mac);
 
             return Ok(Json(SetupResponse {
@@ -38,24 +50,27 @@ pub async fn setup_handler(
             }));
         }
         _ => {
-            let api_key: String = rand::rng()
-                .sample_iter(&Alphanumeric)
-                .take(22)
-                .map(char::from)
-                .collect();
+            let api_key = generate_device_api_key();
 
             let id = Uuid::new_v4().simple().to_string()[..6].to_uppercase();
 
+            // The device still gets its key so it can poll for the outcome of the approval
+            let approval_status = if approval.requires_approval(mac) {
+                ApprovalStatus::Pending
+            } else {
+                ApprovalStatus::Approved
+            };
+
             // Insert into DB
             device_repo
-                .create(&id, mac, &api_key)
-                .await
-                .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong"))?;
+                .create(&id, mac, &api_key, approval_status)
+                .await?;
 
             info!(
                 msg = "Device successfully registered",
                 ?mac,
-                %id
+                %id,
+                approval_status = approval_status.as_str()
             );
 
             Ok(Json(SetupResponse {
@@ -0,0 +1,26 @@
+use axum::{
+    extract::{Extension, Path},
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use crate::{
+    error::ApiError, handlers::pending_approval::message_screen_response,
+    repositories::device::DeviceRepo,
+};
+
+/// Screen shown for the length of the quiet window
+#[instrument(name = "handlers.sleeping_screen", skip(device_repo, id), fields(device_id = %id))]
+pub async fn sleeping_screen_handler(
+    Path(id): Path<String>,
+    Extension(device_repo): Extension<DeviceRepo>,
+) -> Result<impl IntoResponse, ApiError> {
+    let quiet_hours = device_repo
+        .get_by_id(&id)
+        .await?
+        .and_then(|device| device.quiet_hours)
+        .filter(|quiet_hours| quiet_hours.sleep_screen)
+        .ok_or(ApiError::NotFound("Device not found"))?;
+
+    message_screen_response("Sleeping", format!("Back at {}", quiet_hours.end)).await
+}
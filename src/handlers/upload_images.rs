@@ -0,0 +1,99 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Extension, Multipart},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use sha2::{Digest, Sha256};
+use tracing::{info, instrument};
+
+use crate::{error::ApiError, models::Image, repositories::image::ImageRepo};
+
+/// Upper bound for a single upload request, e-paper images are tiny so this leaves plenty of room
+pub const MAX_IMAGE_UPLOAD_SIZE: usize = 10 * 1024 * 1024;
+
+/// Detects the formats the device firmware can display from the file signature
+fn detect_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"BM") {
+        Some("image/bmp")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else {
+        None
+    }
+}
+
+/// Reads the file parts of a multipart upload, any other form fields are ignored
+pub(crate) async fn read_files(mut multipart: Multipart) -> Result<Vec<Bytes>, ApiError> {
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| ApiError::BadRequest("Invalid multipart body"))?
+    {
+        if field.file_name().is_none() {
+            continue;
+        }
+
+        files.push(
+            field
+                .bytes()
+                .await
+                .map_err(|_| ApiError::BadRequest("Invalid multipart body"))?,
+        );
+    }
+
+    if files.is_empty() {
+        return Err(ApiError::Validation("No images uploaded"));
+    }
+
+    Ok(files)
+}
+
+/// Stores an image under the hash of its content and returns it
+pub(crate) async fn store_image(
+    image_repo: &ImageRepo,
+    content_type: &str,
+    data: &[u8],
+) -> Result<Image, ApiError> {
+    let id = format!("{:x}", Sha256::digest(data));
+
+    image_repo.create(&id, content_type, data).await?;
+
+    info!(msg = "Image uploaded", %id, %content_type, size = data.len());
+
+    image_repo
+        .get(&id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Image {id} is missing after it was stored").into())
+}
+
+#[instrument(name = "handlers.upload_images", skip(image_repo, multipart))]
+pub async fn upload_images_handler(
+    Extension(image_repo): Extension<ImageRepo>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    let files = read_files(multipart).await?;
+
+    // Check every file before storing any so a bad upload leaves nothing behind
+    let uploads = files
+        .iter()
+        .map(|data| {
+            detect_content_type(data)
+                .map(|content_type| (content_type, data))
+                .ok_or(ApiError::UnsupportedMediaType(
+                    "Images must be BMP or PNG files",
+                ))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut images = Vec::with_capacity(uploads.len());
+
+    for (content_type, data) in uploads {
+        images.push(store_image(&image_repo, content_type, data).await?);
+    }
+
+    Ok((StatusCode::CREATED, Json(images)))
+}
@@ -6,3 +6,4 @@ pub const HEADER_FW_VERSION: HeaderName = HeaderName::from_static("fw-version");
 pub const HEADER_BATTERY_VOLTAGE: HeaderName = HeaderName::from_static("battery-voltage");
 pub const HEADER_REFRESH_RATE: HeaderName = HeaderName::from_static("refresh-rate");
 pub const HEADER_RSSI: HeaderName = HeaderName::from_static("rssi");
+pub const HEADER_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::alert::{AlertRepo, SqliteAlertRepo};
+
+#[derive(Clone)]
+pub struct AlertRepoLayer(pub AlertRepo);
+
+impl AlertRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteAlertRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for AlertRepoLayer {
+    type Service = AddExtension<S, AlertRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
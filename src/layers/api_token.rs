@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::api_token::{ApiTokenRepo, SqliteApiTokenRepo};
+
+#[derive(Clone)]
+pub struct ApiTokenRepoLayer(pub ApiTokenRepo);
+
+impl ApiTokenRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteApiTokenRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for ApiTokenRepoLayer {
+    type Service = AddExtension<S, ApiTokenRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
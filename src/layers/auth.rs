@@ -0,0 +1,47 @@
+use axum::{
+    extract::{Extension, Request},
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::info;
+
+use crate::{auth::hash_api_token, error::ApiError, repositories::api_token::ApiTokenRepo};
+
+fn unauthorized() -> Response {
+    let mut response = ApiError::Unauthorized("Invalid or missing API token").into_response();
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
+
+/// Rejects requests without a valid management API token in the `Authorization` header.
+///
+/// Applied to management routes only, devices authenticate with their `access-token` instead.
+pub async fn require_api_token(
+    Extension(api_token_repo): Extension<ApiTokenRepo>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty());
+
+    let Some(token) = token else {
+        return unauthorized();
+    };
+
+    match api_token_repo.get_by_hash(&hash_api_token(token)).await {
+        Ok(Some(api_token)) => {
+            info!(msg = "Authenticated API token", token_id = %api_token.id);
+            next.run(request).await
+        }
+        Ok(None) => unauthorized(),
+        Err(error) => ApiError::from(error).into_response(),
+    }
+}
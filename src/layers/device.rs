@@ -3,7 +3,9 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use tower::Layer;
 
-use crate::repositories::device::{DeviceRepo, SqliteDeviceRepo};
+#[cfg(feature = "postgres")]
+use crate::repositories::device::PostgresDeviceRepo;
+use crate::repositories::device::{DeviceRepo, InMemoryDeviceRepo, SqliteDeviceRepo};
 
 #[derive(Clone)]
 pub struct DeviceRepoLayer(pub DeviceRepo);
@@ -12,6 +14,15 @@ impl DeviceRepoLayer {
     pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
         Self(Arc::new(SqliteDeviceRepo::new(pool)))
     }
+
+    pub fn in_memory() -> Self {
+        Self(Arc::new(InMemoryDeviceRepo::new()))
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn postgres(pool: Arc<sqlx::PgPool>) -> Self {
+        Self(Arc::new(PostgresDeviceRepo::new(pool)))
+    }
 }
 
 impl<S> Layer<S> for DeviceRepoLayer {
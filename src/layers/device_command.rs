@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::device_command::{DeviceCommandRepo, SqliteDeviceCommandRepo};
+
+#[derive(Clone)]
+pub struct DeviceCommandRepoLayer(pub DeviceCommandRepo);
+
+impl DeviceCommandRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteDeviceCommandRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for DeviceCommandRepoLayer {
+    type Service = AddExtension<S, DeviceCommandRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
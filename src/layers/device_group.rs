@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::device_group::{DeviceGroupRepo, SqliteDeviceGroupRepo};
+
+#[derive(Clone)]
+pub struct DeviceGroupRepoLayer(pub DeviceGroupRepo);
+
+impl DeviceGroupRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteDeviceGroupRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for DeviceGroupRepoLayer {
+    type Service = AddExtension<S, DeviceGroupRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
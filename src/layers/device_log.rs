@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::device_log::{DeviceLogRepo, SqliteDeviceLogRepo};
+
+#[derive(Clone)]
+pub struct DeviceLogRepoLayer(pub DeviceLogRepo);
+
+impl DeviceLogRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteDeviceLogRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for DeviceLogRepoLayer {
+    type Service = AddExtension<S, DeviceLogRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::firmware::{FirmwareRepo, SqliteFirmwareRepo};
+
+#[derive(Clone)]
+pub struct FirmwareRepoLayer(pub FirmwareRepo);
+
+impl FirmwareRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteFirmwareRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for FirmwareRepoLayer {
+    type Service = AddExtension<S, FirmwareRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
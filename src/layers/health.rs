@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::health::{HealthRepo, SqliteHealthRepo};
+
+#[derive(Clone)]
+pub struct HealthRepoLayer(pub HealthRepo);
+
+impl HealthRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteHealthRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for HealthRepoLayer {
+    type Service = AddExtension<S, HealthRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
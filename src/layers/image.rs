@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::image::{ImageRepo, SqliteImageRepo};
+
+#[derive(Clone)]
+pub struct ImageRepoLayer(pub ImageRepo);
+
+impl ImageRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteImageRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for ImageRepoLayer {
+    type Service = AddExtension<S, ImageRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::layout::{LayoutRepo, SqliteLayoutRepo};
+
+#[derive(Clone)]
+pub struct LayoutRepoLayer(pub LayoutRepo);
+
+impl LayoutRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteLayoutRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for LayoutRepoLayer {
+    type Service = AddExtension<S, LayoutRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::{Method, Response},
+    middleware::Next,
+};
+
+use crate::metrics::{HttpMetrics, UNMATCHED_ROUTE};
+
+/// Method and route template of the request a response answers, so `/api/devices/{id}` is a
+/// single series rather than one per device
+#[derive(Debug, Clone)]
+pub struct RouteLabels {
+    method: Method,
+    route: String,
+}
+
+/// Copies the request's [`RouteLabels`] onto its response, as the `TraceLayer` callbacks only
+/// get to see the response
+pub async fn tag_route(
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response<Body> {
+    let labels = RouteLabels {
+        method: request.method().clone(),
+        route: matched_path
+            .as_ref()
+            .map_or(UNMATCHED_ROUTE, MatchedPath::as_str)
+            .to_string(),
+    };
+
+    let mut response = next.run(request).await;
+    response.extensions_mut().insert(labels);
+    response
+}
+
+/// Records a response with the latency measured by the `TraceLayer`, called from its
+/// `on_response` callback
+pub fn record_response<B>(metrics: &HttpMetrics, response: &Response<B>, latency: Duration) {
+    // Only responses that passed through `tag_route` carry labels
+    if let Some(labels) = response.extensions().get::<RouteLabels>() {
+        metrics.record(
+            labels.method.as_str(),
+            &labels.route,
+            response.status().as_u16(),
+            latency,
+        );
+    }
+}
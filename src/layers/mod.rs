@@ -1 +1,14 @@
+pub mod alert;
+pub mod api_token;
+pub mod auth;
 pub mod device;
+pub mod device_command;
+pub mod device_group;
+pub mod device_log;
+pub mod firmware;
+pub mod health;
+pub mod image;
+pub mod layout;
+pub mod metrics;
+pub mod problem;
+pub mod telemetry;
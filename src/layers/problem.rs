@@ -0,0 +1,76 @@
+use axum::{
+    Json,
+    body::to_bytes,
+    extract::Request,
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{error::Problem, headers::HEADER_REQUEST_ID, utils::get_optional_header};
+
+/// Rejection messages are a single line, anything longer is not passed on
+const MAX_REJECTION_SIZE: usize = 4096;
+
+/// Turns the plain text responses of rejected extractors, such as a JSON body of the wrong
+/// shape or a query parameter that does not parse, into problem details like the errors
+/// handlers return
+pub async fn problem_for_rejections(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+
+    let is_plain_text = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/plain"));
+
+    if !response.status().is_client_error() || !is_plain_text {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let detail = match to_bytes(body, MAX_REJECTION_SIZE).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(_) => parts
+            .status
+            .canonical_reason()
+            .unwrap_or_default()
+            .to_string(),
+    };
+
+    let mut problem = Problem::from_rejection(parts.status, detail).into_response();
+
+    // Headers of the rejection other than those describing the old body are kept
+    parts.headers.remove(header::CONTENT_TYPE);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    problem.headers_mut().extend(parts.headers);
+
+    problem
+}
+
+/// Adds the `x-request-id` of the request to problem details responses, so a caller reporting an
+/// error can point at the matching logs and traces
+pub async fn attach_request_id(request: Request, next: Next) -> Response {
+    let request_id = get_optional_header(request.headers(), &HEADER_REQUEST_ID).map(str::to_string);
+
+    let response = next.run(request).await;
+
+    let Some(request_id) = request_id else {
+        return response;
+    };
+    let Some(problem) = response.extensions().get::<Problem>().cloned() else {
+        return response;
+    };
+
+    let problem = Problem {
+        request_id: Some(request_id),
+        ..problem
+    };
+
+    // Only the body changes, headers such as `WWW-Authenticate` are kept
+    let (mut parts, _) = response.into_parts();
+    let body = Json(&problem).into_response().into_body();
+    parts.extensions.insert(problem);
+
+    Response::from_parts(parts, body)
+}
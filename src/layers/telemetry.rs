@@ -0,0 +1,23 @@
+use axum::{Extension, middleware::AddExtension};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::Layer;
+
+use crate::repositories::telemetry::{SqliteTelemetryRepo, TelemetryRepo};
+
+#[derive(Clone)]
+pub struct TelemetryRepoLayer(pub TelemetryRepo);
+
+impl TelemetryRepoLayer {
+    pub fn sqlite(pool: Arc<SqlitePool>) -> Self {
+        Self(Arc::new(SqliteTelemetryRepo::new(pool)))
+    }
+}
+
+impl<S> Layer<S> for TelemetryRepoLayer {
+    type Service = AddExtension<S, TelemetryRepo>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.0.clone()).layer(inner)
+    }
+}
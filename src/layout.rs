@@ -0,0 +1,652 @@
+use std::collections::HashMap;
+
+use font8x8::{BASIC_FONTS, LATIN_FONTS, UnicodeFonts};
+use serde::{Deserialize, Serialize};
+
+use crate::convert::{
+    ConversionOptions, DISPLAY_HEIGHT, DISPLAY_WIDTH, DitherMode, ResizeMode, convert_to_pixels,
+    default_height, default_width, encode_bmp, valid_display_size,
+};
+
+/// Upper bounds keeping a single layout from tying up the server while rendering
+const MAX_ELEMENTS: usize = 500;
+const MAX_SIZE: u32 = 256;
+const MAX_THICKNESS: u32 = 64;
+/// Coordinates may reach this many display sizes past the origin in either direction, enough to
+/// place elements partly off screen
+const MAX_OFFSCREEN_FACTOR: i32 = 4;
+
+/// Glyphs and icons are drawn from 8x8 bitmaps scaled by whole multiples
+const GLYPH_SIZE: u32 = 8;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    #[default]
+    Black,
+    White,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Font {
+    #[default]
+    Regular,
+    Bold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Icon {
+    ArrowDown,
+    ArrowUp,
+    Battery,
+    BatteryEmpty,
+    Check,
+    Cloud,
+    Cross,
+    Rain,
+    Sun,
+    Warning,
+    Wifi,
+}
+
+impl Icon {
+    fn bitmap(self) -> [&'static str; 8] {
+        match self {
+            Icon::ArrowDown => [
+                "...##...", "...##...", "...##...", "...##...", "########", ".######.", "..####..",
+                "...##...",
+            ],
+            Icon::ArrowUp => [
+                "...##...", "..####..", ".######.", "########", "...##...", "...##...", "...##...",
+                "...##...",
+            ],
+            Icon::Battery => [
+                "........", "#######.", "#.###.#.", "#.###.##", "#.###.##", "#.###.#.", "#######.",
+                "........",
+            ],
+            Icon::BatteryEmpty => [
+                "........", "#######.", "#.....#.", "#.....##", "#.....##", "#.....#.", "#######.",
+                "........",
+            ],
+            Icon::Check => [
+                "........", ".......#", "......##", ".....##.", "#...##..", "##.##...", ".###....",
+                "..#.....",
+            ],
+            Icon::Cloud => [
+                "........", "...##...", "..#..##.", ".#.....#", "#......#", "#......#", ".######.",
+                "........",
+            ],
+            Icon::Cross => [
+                "##....##", ".##..##.", "..####..", "...##...", "..####..", ".##..##.", "##....##",
+                "........",
+            ],
+            Icon::Rain => [
+                "...##...", "..#..##.", ".#.....#", "#......#", ".######.", "........", ".#.#.#..",
+                "#.#.#...",
+            ],
+            Icon::Sun => [
+                "...##...", ".#....#.", "..####..", "#.####.#", "#.####.#", "..####..", ".#....#.",
+                "...##...",
+            ],
+            Icon::Warning => [
+                "...##...", "..#..#..", ".#.##.#.", ".#.##.#.", "#..##..#", "#......#", "#..##..#",
+                "########",
+            ],
+            Icon::Wifi => [
+                "........", ".######.", "#......#", "..####..", ".#....#.", "...##...", "...##...",
+                "........",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Element {
+    Text {
+        x: i32,
+        y: i32,
+        /// Width of the box the text is wrapped and aligned in, without it text is aligned on `x`
+        width: Option<u32>,
+        text: String,
+        #[serde(default)]
+        font: Font,
+        /// Height of a line in pixels, rounded down to a multiple of 8
+        #[serde(default = "default_text_size")]
+        size: u32,
+        #[serde(default)]
+        align: Align,
+        #[serde(default = "default_wrap")]
+        wrap: bool,
+        #[serde(default)]
+        color: Color,
+    },
+    Line {
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        #[serde(default = "default_thickness")]
+        thickness: u32,
+        #[serde(default)]
+        color: Color,
+    },
+    Rectangle {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        fill: bool,
+        #[serde(default = "default_thickness")]
+        thickness: u32,
+        #[serde(default)]
+        color: Color,
+    },
+    /// An uploaded image, converted to black and white to fit the box
+    Image {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        image: String,
+        #[serde(default)]
+        resize: ResizeMode,
+        #[serde(default)]
+        dither: DitherMode,
+    },
+    Icon {
+        x: i32,
+        y: i32,
+        icon: Icon,
+        /// Size in pixels, rounded down to a multiple of 8
+        #[serde(default = "default_icon_size")]
+        size: u32,
+        #[serde(default)]
+        color: Color,
+    },
+}
+
+impl Element {
+    /// X and Y coordinates the element is positioned by
+    fn coordinates(&self) -> (Vec<i32>, Vec<i32>) {
+        match self {
+            Element::Line { x1, y1, x2, y2, .. } => (vec![*x1, *x2], vec![*y1, *y2]),
+            Element::Text { x, y, .. }
+            | Element::Rectangle { x, y, .. }
+            | Element::Image { x, y, .. }
+            | Element::Icon { x, y, .. } => (vec![*x], vec![*y]),
+        }
+    }
+}
+
+fn default_text_size() -> u32 {
+    16
+}
+
+fn default_wrap() -> bool {
+    true
+}
+
+fn default_thickness() -> u32 {
+    1
+}
+
+fn default_icon_size() -> u32 {
+    16
+}
+
+fn default_background() -> Color {
+    Color::White
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutSpec {
+    #[serde(default = "default_background")]
+    pub background: Color,
+    /// Resolution of the rendered bitmap, the TRMNL panel unless the layout targets another display
+    #[serde(default = "default_width")]
+    pub width: u32,
+    #[serde(default = "default_height")]
+    pub height: u32,
+    pub elements: Vec<Element>,
+}
+
+/// Screen the server shows in place of the rotation, a bold title above a line of detail, both
+/// centered on the display
+pub fn message_screen(title: &str, subtitle: &str) -> LayoutSpec {
+    let text = |y: i32, text: &str, font: Font, size: u32| Element::Text {
+        x: 0,
+        y,
+        width: Some(DISPLAY_WIDTH),
+        text: text.to_string(),
+        font,
+        size,
+        align: Align::Center,
+        wrap: true,
+        color: Color::Black,
+    };
+
+    LayoutSpec {
+        background: Color::White,
+        width: DISPLAY_WIDTH,
+        height: DISPLAY_HEIGHT,
+        elements: vec![
+            text(DISPLAY_HEIGHT as i32 / 2 - 64, title, Font::Bold, 48),
+            text(DISPLAY_HEIGHT as i32 / 2 + 16, subtitle, Font::Regular, 32),
+        ],
+    }
+}
+
+impl LayoutSpec {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.elements.len() > MAX_ELEMENTS {
+            return Err("Layouts can have at most 500 elements");
+        }
+
+        if !valid_display_size(self.width, self.height) {
+            return Err("Width and height must be between 1 and 2048");
+        }
+
+        let max_x = MAX_OFFSCREEN_FACTOR * self.width as i32;
+        let max_y = MAX_OFFSCREEN_FACTOR * self.height as i32;
+
+        for element in &self.elements {
+            let (xs, ys) = element.coordinates();
+
+            if xs.iter().any(|x| x.abs() > max_x) || ys.iter().any(|y| y.abs() > max_y) {
+                return Err("Coordinates must be within 4 display sizes of the origin");
+            }
+
+            match element {
+                Element::Text {
+                    width: Some(width), ..
+                } if *width > max_x as u32 => {
+                    return Err("Text width must be at most 4 display widths");
+                }
+                Element::Text { size, .. } | Element::Icon { size, .. } if *size > MAX_SIZE => {
+                    return Err("Text and icon sizes must be at most 256");
+                }
+                Element::Line { thickness, .. } | Element::Rectangle { thickness, .. }
+                    if *thickness > MAX_THICKNESS =>
+                {
+                    return Err("Line thickness must be at most 64");
+                }
+                Element::Image { width, height, .. }
+                    if *width == 0
+                        || *height == 0
+                        || *width > self.width
+                        || *height > self.height =>
+                {
+                    return Err("Images must be between 1x1 and the display size");
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ids of the uploaded images drawn by this layout
+    pub fn image_ids(&self) -> Vec<&str> {
+        self.elements
+            .iter()
+            .filter_map(|element| match element {
+                Element::Image { image, .. } => Some(image.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Renders a layout into a 1-bit BMP at the resolution of the spec, `images` holding the content
+/// of the images it references by id
+pub fn render_to_bmp(
+    spec: &LayoutSpec,
+    images: &HashMap<String, Vec<u8>>,
+) -> image::ImageResult<Vec<u8>> {
+    let mut canvas = Canvas::new(spec.width, spec.height, spec.background);
+
+    for element in &spec.elements {
+        match element {
+            Element::Text {
+                x,
+                y,
+                width,
+                text,
+                font,
+                size,
+                align,
+                wrap,
+                color,
+            } => canvas.text(*x, *y, *width, text, *font, *size, *align, *wrap, *color),
+            Element::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                thickness,
+                color,
+            } => canvas.line(*x1, *y1, *x2, *y2, *thickness, *color),
+            Element::Rectangle {
+                x,
+                y,
+                width,
+                height,
+                fill,
+                thickness,
+                color,
+            } => {
+                let thickness = if *fill { u32::MAX } else { (*thickness).max(1) };
+                canvas.rectangle(*x, *y, *width, *height, thickness, *color)
+            }
+            Element::Image {
+                x,
+                y,
+                width,
+                height,
+                image,
+                resize,
+                dither,
+            } => {
+                // Missing images are left blank, callers check references before rendering
+                let Some(data) = images.get(image) else {
+                    continue;
+                };
+
+                let options = ConversionOptions {
+                    resize: *resize,
+                    dither: *dither,
+                    ..Default::default()
+                };
+                let pixels = convert_to_pixels(data, *width, *height, &options)?;
+
+                canvas.pixels(*x, *y, *width, &pixels);
+            }
+            Element::Icon {
+                x,
+                y,
+                icon,
+                size,
+                color,
+            } => {
+                let rows = icon.bitmap().map(|row| {
+                    row.bytes()
+                        .enumerate()
+                        .filter(|(_, pixel)| *pixel == b'#')
+                        .fold(0u8, |bits, (i, _)| bits | (1 << i))
+                });
+
+                canvas.glyph(*x as i64, *y as i64, &rows, scale(*size), *color);
+            }
+        }
+    }
+
+    Ok(encode_bmp(&canvas.pixels, canvas.width, canvas.height))
+}
+
+/// Factor an 8x8 bitmap is scaled by to be drawn at `size` pixels
+fn scale(size: u32) -> u32 {
+    (size / GLYPH_SIZE).max(1)
+}
+
+/// Splits text into the lines it is drawn on, wrapping on words to fit `max_chars`
+fn wrap_lines(text: &str, max_chars: Option<usize>) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let Some(max_chars) = max_chars else {
+            lines.push(paragraph.to_string());
+            continue;
+        };
+
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+
+            // Words longer than a line are broken wherever the line ends
+            while word.len() > max_chars {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+
+                lines.push(word.drain(..max_chars).collect());
+            }
+
+            let word: String = word.into_iter().collect();
+            let len = line.chars().count();
+
+            if len > 0 && len + 1 + word.chars().count() > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(&word);
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    /// Row major pixels, `true` being white to match the BMP encoder
+    pixels: Vec<bool>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: Color) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background == Color::White; (width * height) as usize],
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, color: Color) {
+        if x >= 0 && y >= 0 && x < self.width as i64 && y < self.height as i64 {
+            self.pixels[(y as u32 * self.width + x as u32) as usize] = color == Color::White;
+        }
+    }
+
+    fn fill(&mut self, x: i64, y: i64, width: i64, height: i64, color: Color) {
+        // Clip first so huge boxes do not loop over pixels that are never drawn
+        let (left, top) = (x.max(0), y.max(0));
+        let right = (x + width).min(self.width as i64);
+        let bottom = (y + height).min(self.height as i64);
+
+        for y in top..bottom {
+            for x in left..right {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    fn rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, thickness: u32, color: Color) {
+        let (x, y, width, height) = (x as i64, y as i64, width as i64, height as i64);
+        let thickness = (thickness as i64).min(width).min(height);
+
+        self.fill(x, y, width, thickness, color);
+        self.fill(x, y + height - thickness, width, thickness, color);
+        self.fill(x, y, thickness, height, color);
+        self.fill(x + width - thickness, y, thickness, height, color);
+    }
+
+    /// Bresenham line, stamping a square of `thickness` on every point
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, thickness: u32, color: Color) {
+        let thickness = thickness.max(1) as i64;
+        let offset = (thickness - 1) / 2;
+
+        // Only the part that can touch the canvas is stepped through
+        let Some(((mut x, mut y), (x2, y2))) =
+            self.clip_line((x1 as i64, y1 as i64), (x2 as i64, y2 as i64), thickness)
+        else {
+            return;
+        };
+
+        let (dx, dy) = ((x2 - x).abs(), -(y2 - y).abs());
+        let (sx, sy) = (if x < x2 { 1 } else { -1 }, if y < y2 { 1 } else { -1 });
+        let mut error = dx + dy;
+
+        loop {
+            self.fill(x - offset, y - offset, thickness, thickness, color);
+
+            if x == x2 && y == y2 {
+                break;
+            }
+
+            let doubled = 2 * error;
+
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Cuts a line down to the canvas widened by `margin` on every side (Liang-Barsky), `None`
+    /// when it misses the canvas entirely
+    fn clip_line(
+        &self,
+        (x1, y1): (i64, i64),
+        (x2, y2): (i64, i64),
+        margin: i64,
+    ) -> Option<((i64, i64), (i64, i64))> {
+        let (left, top) = (-margin, -margin);
+        let (right, bottom) = (self.width as i64 + margin, self.height as i64 + margin);
+        let (dx, dy) = ((x2 - x1) as f64, (y2 - y1) as f64);
+        let (mut start, mut end) = (0.0f64, 1.0f64);
+
+        for (p, q) in [
+            (-dx, (x1 - left) as f64),
+            (dx, (right - x1) as f64),
+            (-dy, (y1 - top) as f64),
+            (dy, (bottom - y1) as f64),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                start = start.max(q / p);
+            } else {
+                end = end.min(q / p);
+            }
+        }
+
+        if start > end {
+            return None;
+        }
+
+        let point = |t: f64| (x1 + (dx * t).round() as i64, y1 + (dy * t).round() as i64);
+
+        Some((point(start), point(end)))
+    }
+
+    /// Draws an 8x8 bitmap, bit 0 of each row being its leftmost pixel
+    fn glyph(&mut self, x: i64, y: i64, rows: &[u8; 8], scale: u32, color: Color) {
+        let scale = scale as i64;
+
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..GLYPH_SIZE as i64 {
+                if bits & (1 << column) != 0 {
+                    self.fill(
+                        x + column * scale,
+                        y + row as i64 * scale,
+                        scale,
+                        scale,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn text(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: Option<u32>,
+        text: &str,
+        font: Font,
+        size: u32,
+        align: Align,
+        wrap: bool,
+        color: Color,
+    ) {
+        let scale = scale(size);
+        let advance = GLYPH_SIZE * scale;
+        let line_height = (GLYPH_SIZE + 2) * scale;
+        let max_chars = width
+            .filter(|_| wrap)
+            .map(|width| ((width / advance) as usize).max(1));
+
+        // Positions are worked out in i64, long lines of large text overflow i32
+        let (x, y, advance, line_height) = (x as i64, y as i64, advance as i64, line_height as i64);
+
+        for (index, line) in wrap_lines(text, max_chars).iter().enumerate() {
+            let line_width = line.chars().count() as i64 * advance;
+            let line_x = match (align, width) {
+                (Align::Left, _) => x,
+                (Align::Center, Some(width)) => x + (width as i64 - line_width) / 2,
+                (Align::Right, Some(width)) => x + width as i64 - line_width,
+                (Align::Center, None) => x - line_width / 2,
+                (Align::Right, None) => x - line_width,
+            };
+            let line_y = y + index as i64 * line_height;
+
+            for (column, character) in line.chars().enumerate() {
+                let rows = BASIC_FONTS
+                    .get(character)
+                    .or_else(|| LATIN_FONTS.get(character))
+                    .or_else(|| BASIC_FONTS.get('?'))
+                    .unwrap_or_default();
+                let glyph_x = line_x + column as i64 * advance;
+
+                self.glyph(glyph_x, line_y, &rows, scale, color);
+
+                // Bold is faked by drawing the glyph again one scaled pixel to the right
+                if font == Font::Bold {
+                    self.glyph(glyph_x + scale as i64, line_y, &rows, scale, color);
+                }
+            }
+        }
+    }
+
+    /// Copies converted image pixels into the canvas
+    fn pixels(&mut self, x: i32, y: i32, width: u32, pixels: &[bool]) {
+        for (index, white) in pixels.iter().enumerate() {
+            let color = if *white { Color::White } else { Color::Black };
+
+            self.set(
+                x as i64 + (index as u32 % width) as i64,
+                y as i64 + (index as u32 / width) as i64,
+                color,
+            );
+        }
+    }
+}
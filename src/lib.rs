@@ -1,9 +1,20 @@
+pub mod alerts;
 pub mod app;
+pub mod auth;
+pub mod cli;
 pub mod config;
+pub mod convert;
 pub mod db;
+pub mod error;
 pub mod handlers;
 pub mod headers;
 pub mod layers;
+pub mod layout;
+pub mod metrics;
 pub mod models;
+pub mod notifier;
+pub mod otel;
 pub mod repositories;
+pub mod schedule;
+pub mod shutdown;
 pub mod utils;
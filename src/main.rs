@@ -1,56 +1,63 @@
-use std::{iter::once, sync::Arc};
+use std::{future::IntoFuture, sync::Arc};
 
-use axum::{Extension, ServiceExt, extract::Request, http::HeaderName};
-use opentelemetry::trace::TracerProvider;
-use opentelemetry_otlp::{SpanExporter, WithExportConfig};
-use opentelemetry_sdk::{
-    Resource,
-    trace::{BatchConfig, BatchSpanProcessor, TracerProviderBuilder},
+use axum::{
+    Extension, ServiceExt,
+    extract::Request,
+    http::{HeaderName, header},
+    middleware,
 };
+use clap::Parser;
+use opentelemetry::trace::TracerProvider;
+use tokio::sync::Notify;
 use tower_http::{
     request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
     sensitive_headers::SetSensitiveRequestHeadersLayer,
     trace::TraceLayer,
 };
-use tracing::{Span, info};
+use tracing::{Span, info, warn};
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
 use trmnl_server::{
+    alerts::{AlertEngine, run_sweeps},
     app::App,
-    config::{LogFormat, ServerConfig},
-    db::{apply_migrations, connect},
-    layers::device::DeviceRepoLayer,
+    auth::{ensure_bootstrap_token, generate_api_token, hash_api_token},
+    cli::{Cli, Command},
+    config::{DatabaseBackend, DatabaseSettings, LogFormat, ServerConfig},
+    db::{apply_migrations, connect, connect_in_memory},
+    layers::metrics::{record_response, tag_route},
+    layers::{
+        alert::AlertRepoLayer, api_token::ApiTokenRepoLayer, device::DeviceRepoLayer,
+        device_command::DeviceCommandRepoLayer, device_group::DeviceGroupRepoLayer,
+        device_log::DeviceLogRepoLayer, firmware::FirmwareRepoLayer, health::HealthRepoLayer,
+        image::ImageRepoLayer, layout::LayoutRepoLayer, telemetry::TelemetryRepoLayer,
+    },
+    metrics::HttpMetrics,
+    notifier::{LogNotifier, NotifierRef, QueuedNotifier, WebhookNotifier},
+    otel::{TracerProviderGuard, tracer_provider},
+    repositories::api_token::{ApiTokenRepo, SqliteApiTokenRepo},
+    shutdown::shutdown_signal,
     utils::get_request_id,
 };
+use uuid::Uuid;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let settings = ServerConfig::load()?;
+    let cli = Cli::parse();
+    let settings = ServerConfig::load(&cli.config)?;
+    settings.status.validate()?;
+    settings.alerts.validate()?;
     info!(
         msg = "Loaded configuration",
-        database_path = settings.database.path,
+        database_path = settings.database.sqlite_path(),
         setup_logo_url = settings.app.setup_logo_url
     );
 
-    let exporter = SpanExporter::builder()
-        .with_http()
-        .with_endpoint("http://localhost:4318/v1/traces")
-        .build()
-        .expect("Failed to create span exporter");
-
-    let provider = TracerProviderBuilder::default()
-        .with_resource(
-            Resource::builder()
-                .with_service_name("trmnl-server")
-                .build(),
-        )
-        .with_span_processor(BatchSpanProcessor::new(exporter, BatchConfig::default()))
-        .build();
-
-    let tracer = provider.tracer("trmnl-server");
-
-    let otel_layer = OpenTelemetryLayer::new(tracer); // Now the trait bound is satisfied
+    let tracer_provider = tracer_provider(&settings.telemetry)?;
+    let otel_layer = tracer_provider
+        .as_ref()
+        .map(|provider| OpenTelemetryLayer::new(provider.tracer("trmnl-server")));
+    let tracer_provider_guard = tracer_provider.map(TracerProviderGuard);
 
     tracing_subscriber::registry()
         .with(match settings.logging.format {
@@ -61,14 +68,93 @@ async fn main() -> anyhow::Result<()> {
         .with(otel_layer)
         .try_init()?;
 
-    let pool = Arc::new(connect(&settings.database.path).await?);
+    let backend = settings.database.backend()?;
+
+    let pool = Arc::new(match backend {
+        DatabaseBackend::Memory => connect_in_memory().await?,
+        _ => {
+            connect(
+                settings.database.sqlite_path(),
+                backend == DatabaseBackend::Sqlite,
+            )
+            .await?
+        }
+    });
     apply_migrations(&pool).await?;
-    info!(msg = "Initialized database", path = %settings.database.path);
+
+    if backend == DatabaseBackend::Memory {
+        warn!(msg = "Initialized in-memory database, all data is lost on shutdown");
+    } else {
+        info!(msg = "Initialized database", path = %settings.database.sqlite_path());
+    }
+
+    let device_repo_layer = match backend {
+        DatabaseBackend::Sqlite => DeviceRepoLayer::sqlite(pool.clone()),
+        DatabaseBackend::Postgres => postgres_device_repo_layer(&settings.database).await?,
+        DatabaseBackend::Memory => DeviceRepoLayer::in_memory(),
+    };
+
+    let api_token_repo: ApiTokenRepo = Arc::new(SqliteApiTokenRepo::new(pool.clone()));
+
+    if let Some(Command::CreateToken { name }) = cli.command {
+        let id = Uuid::new_v4().to_string();
+        let token = generate_api_token();
+
+        api_token_repo
+            .create(&id, &name, &hash_api_token(&token))
+            .await?;
+
+        println!("Created API token {id} ({name}), it will not be shown again:\n{token}");
+        return Ok(());
+    }
+
+    if let Some(token) = &settings.auth.bootstrap_token {
+        ensure_bootstrap_token(&api_token_repo, token).await?;
+    }
+
+    let device_group_repo_layer = DeviceGroupRepoLayer::sqlite(pool.clone());
+    let alert_repo_layer = AlertRepoLayer::sqlite(pool.clone());
+
+    let notifier: NotifierRef = match &settings.alerts.webhook_url {
+        Some(url) => Arc::new(WebhookNotifier::new(url)?),
+        None => Arc::new(LogNotifier),
+    };
+    // Delivery happens off the request path, devices get their image without waiting on it
+    let alert_engine = Arc::new(AlertEngine::new(
+        alert_repo_layer.0.clone(),
+        Arc::new(QueuedNotifier::spawn(notifier)),
+        settings.alerts.clone(),
+    ));
+
+    // Devices that stop polling are only noticed by the periodic sweep
+    let sweeper = tokio::spawn(run_sweeps(
+        alert_engine.clone(),
+        device_repo_layer.0.clone(),
+        device_group_repo_layer.0.clone(),
+        settings.alerts.sweep_interval(),
+    ));
+
+    let http_metrics = Arc::new(HttpMetrics::new());
 
     let app = App::new()
         .router()
-        .layer(Extension(ServerConfig::load()?))
-        .layer(DeviceRepoLayer::sqlite(pool.clone()))
+        .layer(Extension(settings.app.clone()))
+        .layer(Extension(settings.approval.clone()))
+        .layer(Extension(settings.status.clone()))
+        .layer(Extension(alert_engine))
+        .layer(device_repo_layer)
+        .layer(ApiTokenRepoLayer(api_token_repo))
+        .layer(DeviceLogRepoLayer::sqlite(pool.clone()))
+        .layer(DeviceCommandRepoLayer::sqlite(pool.clone()))
+        .layer(device_group_repo_layer)
+        .layer(alert_repo_layer)
+        .layer(TelemetryRepoLayer::sqlite(pool.clone()))
+        .layer(FirmwareRepoLayer::sqlite(pool.clone()))
+        .layer(HealthRepoLayer::sqlite(pool.clone()))
+        .layer(ImageRepoLayer::sqlite(pool.clone()))
+        .layer(LayoutRepoLayer::sqlite(pool.clone()))
+        .layer(middleware::from_fn(tag_route))
+        .layer(Extension(http_metrics.clone()))
         .layer(PropagateRequestIdLayer::x_request_id())
         .layer(
             TraceLayer::new_for_http()
@@ -81,9 +167,10 @@ async fn main() -> anyhow::Result<()> {
                     )
                 })
                 .on_response(
-                    |res: &axum::response::Response<_>,
-                     latency: std::time::Duration,
-                     span: &Span| {
+                    move |res: &axum::response::Response<_>,
+                          latency: std::time::Duration,
+                          span: &Span| {
+                        record_response(&http_metrics, res, latency);
                         span.in_scope(|| {
                             if res.status().is_server_error() {
                                 tracing::error!(
@@ -100,18 +187,71 @@ async fn main() -> anyhow::Result<()> {
                     },
                 ),
         )
-        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid::default()))
-        .layer(SetSensitiveRequestHeadersLayer::new(once(
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .layer(SetSensitiveRequestHeadersLayer::new([
             HeaderName::from_static("access-token"),
-        )));
+            header::AUTHORIZATION,
+        ]));
+
+    let addr = cli.bind.unwrap_or_else(|| settings.server.socket_addr());
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+
+    info!(msg = "Starting server", %addr);
+
+    let stop = Arc::new(Notify::new());
+    let mut server = tokio::spawn(
+        axum::serve(listener, ServiceExt::<Request>::into_make_service(app))
+            .with_graceful_shutdown({
+                let stop = stop.clone();
+                async move { stop.notified().await }
+            })
+            .into_future(),
+    );
+
+    tokio::select! {
+        result = &mut server => result??,
+        () = shutdown_signal() => {
+            let drain_timeout = settings.server.drain_timeout();
+            info!(msg = "Shutting down, waiting for in-flight requests", drain_timeout_secs = drain_timeout.as_secs());
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+            // New connections are refused from here on while open ones finish their requests
+            stop.notify_one();
 
-    info!(msg = "Starting server", addr = "0.0.0.0:3000");
+            match tokio::time::timeout(drain_timeout, &mut server).await {
+                Ok(result) => result??,
+                Err(_) => {
+                    warn!(msg = "Drain timeout elapsed, dropping remaining requests");
+                    server.abort();
+                }
+            }
+        }
+    }
 
-    axum::serve(listener, ServiceExt::<Request>::into_make_service(app))
-        .await
-        .unwrap();
+    sweeper.abort();
+    pool.close().await;
+    info!(msg = "Server stopped");
+
+    // Spans of the last requests are exported before the process exits
+    drop(tracer_provider_guard);
 
     Ok(())
 }
+
+#[cfg(feature = "postgres")]
+async fn postgres_device_repo_layer(
+    settings: &DatabaseSettings,
+) -> anyhow::Result<DeviceRepoLayer> {
+    use trmnl_server::db::{apply_postgres_migrations, connect_postgres};
+
+    let url = settings.url.as_deref().unwrap_or_default();
+    let pool = Arc::new(connect_postgres(url).await?);
+    apply_postgres_migrations(&pool).await?;
+    info!(msg = "Initialized PostgreSQL database for devices");
+
+    Ok(DeviceRepoLayer::postgres(pool))
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn postgres_device_repo_layer(_: &DatabaseSettings) -> anyhow::Result<DeviceRepoLayer> {
+    anyhow::bail!("PostgreSQL URLs require building with the postgres feature")
+}
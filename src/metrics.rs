@@ -0,0 +1,200 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+use crate::models::Device;
+
+/// Upper bounds of the request latency histogram buckets, in seconds
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Route label of requests that did not match any route, so unknown paths cannot blow up the
+/// number of series
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    method: String,
+    route: String,
+    status: u16,
+}
+
+#[derive(Debug, Default)]
+struct RequestStats {
+    /// Requests per bucket of [`LATENCY_BUCKETS`], not cumulative
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+/// Request counts and latencies per route, kept in memory since the server started
+#[derive(Debug, Default)]
+pub struct HttpMetrics(Mutex<BTreeMap<RequestKey, RequestStats>>);
+
+impl HttpMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        let seconds = latency.as_secs_f64();
+        let mut requests = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let stats = requests
+            .entry(RequestKey {
+                method: method.to_string(),
+                route: route.to_string(),
+                status,
+            })
+            .or_default();
+
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            stats.buckets[bucket] += 1;
+        }
+        stats.count += 1;
+        stats.sum += seconds;
+    }
+
+    /// Appends the request metrics in the Prometheus text format
+    pub fn render(&self, out: &mut String) {
+        let requests = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        out.push_str("# HELP trmnl_http_requests_total Number of HTTP requests handled.\n");
+        out.push_str("# TYPE trmnl_http_requests_total counter\n");
+        for (key, stats) in requests.iter() {
+            let _ = writeln!(
+                out,
+                "trmnl_http_requests_total{{{}}} {}",
+                request_labels(key),
+                stats.count
+            );
+        }
+
+        out.push_str(
+            "# HELP trmnl_http_request_duration_seconds Time taken to handle HTTP requests.\n",
+        );
+        out.push_str("# TYPE trmnl_http_request_duration_seconds histogram\n");
+        for (key, stats) in requests.iter() {
+            let labels = request_labels(key);
+            let mut cumulative = 0;
+
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(stats.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "trmnl_http_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "trmnl_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                stats.count
+            );
+            let _ = writeln!(
+                out,
+                "trmnl_http_request_duration_seconds_sum{{{labels}}} {}",
+                stats.sum
+            );
+            let _ = writeln!(
+                out,
+                "trmnl_http_request_duration_seconds_count{{{labels}}} {}",
+                stats.count
+            );
+        }
+    }
+}
+
+fn request_labels(key: &RequestKey) -> String {
+    format!(
+        "method=\"{}\",route=\"{}\",status=\"{}\"",
+        escape_label(&key.method),
+        escape_label(&key.route),
+        key.status
+    )
+}
+
+/// Appends gauges describing every device in the Prometheus text format
+pub fn render_devices(out: &mut String, devices: &[Device], now: i64) {
+    gauge(
+        out,
+        "trmnl_device_battery_voltage",
+        "Battery voltage last reported by the device.",
+        devices,
+        |device| device.battery_voltage,
+    );
+    gauge(
+        out,
+        "trmnl_device_rssi",
+        "Wi-Fi signal strength last reported by the device, in dBm.",
+        devices,
+        |device| device.rssi.map(|rssi| rssi as f64),
+    );
+    gauge(
+        out,
+        "trmnl_device_refresh_rate_seconds",
+        "Refresh rate configured for the device, or last reported by it when unset.",
+        devices,
+        |device| {
+            device
+                .desired_refresh_rate
+                .or(device.refresh_rate)
+                .map(|rate| rate as f64)
+        },
+    );
+    gauge(
+        out,
+        "trmnl_device_seconds_since_last_poll",
+        "Time since the device last polled for an image.",
+        devices,
+        |device| {
+            device
+                .last_seen_at
+                .map(|last_seen_at| (now - last_seen_at).max(0) as f64)
+        },
+    );
+
+    out.push_str("# HELP trmnl_device_info Firmware version last reported by the device.\n");
+    out.push_str("# TYPE trmnl_device_info gauge\n");
+    for device in devices {
+        let _ = writeln!(
+            out,
+            "trmnl_device_info{{device_id=\"{}\",fw_version=\"{}\"}} 1",
+            escape_label(&device.id),
+            escape_label(device.fw_version.as_deref().unwrap_or_default())
+        );
+    }
+}
+
+/// Writes one sample per device that has a value
+fn gauge(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    devices: &[Device],
+    value: impl Fn(&Device) -> Option<f64>,
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+
+    for device in devices {
+        if let Some(value) = value(device) {
+            let _ = writeln!(
+                out,
+                "{name}{{device_id=\"{}\"}} {value}",
+                escape_label(&device.id)
+            );
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,4 +1,14 @@
-use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    config::StatusSettings,
+    layout::LayoutSpec,
+    schedule::{QuietHours, ScheduleRule, parse_timezone},
+};
 
 #[derive(Serialize)]
 pub struct SetupResponse {
@@ -28,8 +38,522 @@ pub struct DeviceInfo {
     pub battery_voltage: Option<f64>,
     pub fw_version: Option<String>,
     pub refresh_rate: Option<i64>,
+    pub desired_refresh_rate: Option<i64>,
+    pub target_fw_version: Option<String>,
+    pub approval_status: ApprovalStatus,
+    pub timezone: Option<String>,
+    pub quiet_hours: Option<QuietHours>,
+    pub group_id: Option<String>,
+    pub first_seen_at: Option<i64>,
+    pub last_seen_at: Option<i64>,
+    pub status: DeviceStatus,
+}
+
+impl DeviceInfo {
+    /// Describes `device` with its connection status as of `now`. The status takes the settings
+    /// inherited from `group` into account, while the other fields show the device's own.
+    pub fn new(
+        device: Device,
+        group: Option<&DeviceGroup>,
+        settings: &StatusSettings,
+        now: i64,
+    ) -> Self {
+        let status = match group {
+            Some(group) => device.clone().with_group(group).status(settings, now),
+            None => device.status(settings, now),
+        };
+
+        DeviceInfo {
+            status,
+            id: device.id,
+            mac: device.mac,
+            rssi: device.rssi,
+            battery_voltage: device.battery_voltage,
+            fw_version: device.fw_version,
+            refresh_rate: device.refresh_rate,
+            desired_refresh_rate: device.desired_refresh_rate,
+            target_fw_version: device.target_fw_version,
+            approval_status: device.approval_status,
+            timezone: device.timezone,
+            quiet_hours: device.quiet_hours,
+            group_id: device.group_id,
+            first_seen_at: device.first_seen_at,
+            last_seen_at: device.last_seen_at,
+        }
+    }
+}
+
+/// Whether a device polls as often as its refresh rate says it should
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceStatus {
+    /// Polled within the grace period of its refresh rate
+    Online,
+    /// Missed its expected poll but not for long enough to be considered gone
+    Late,
+    /// Has not polled for a long time, or never did
+    Offline,
+}
+
+/// Whether a device may use the server, new devices start as pending when approval is required
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalStatus {
+    Pending,
+    #[default]
+    Approved,
+    Rejected,
+}
+
+impl ApprovalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl FromStr for ApprovalStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ApprovalStatus::Pending),
+            "approved" => Ok(ApprovalStatus::Approved),
+            "rejected" => Ok(ApprovalStatus::Rejected),
+            _ => anyhow::bail!("Unknown approval status: {s}"),
+        }
+    }
+}
+
+/// Query for `GET /api/devices`
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct DeviceListQuery {
+    pub approval_status: Option<ApprovalStatus>,
+    /// Only list the devices assigned to this group
+    pub group: Option<String>,
+}
+
+/// Partial update of the server controlled device settings.
+///
+/// Absent fields are left untouched, while an explicit `null` clears the setting.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct DevicePatch {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub desired_refresh_rate: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub target_fw_version: Option<Option<String>>,
+    /// IANA timezone name used to evaluate the schedule, UTC when unset
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub timezone: Option<Option<String>>,
+    /// Nightly window during which the device sleeps instead of polling
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub quiet_hours: Option<Option<QuietHours>>,
+    /// Group the device inherits its rotation, refresh rate and firmware target from
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub group_id: Option<Option<String>>,
+}
+
+/// Distinguishes an explicit `null` from a missing field when used with `#[serde(default)]`
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer).map(Some)
+}
+
+/// Log upload sent by the firmware to `/api/log`.
+///
+/// Older firmware wraps the entries as `{"log": {"logs_array": [...]}}` while newer
+/// releases send `{"logs": [...]}`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum DeviceLogPayload {
+    Current { logs: Vec<DeviceLogEntry> },
+    Legacy { log: LegacyDeviceLogs },
+}
+
+impl DeviceLogPayload {
+    pub fn into_entries(self) -> Vec<DeviceLogEntry> {
+        match self {
+            DeviceLogPayload::Current { logs } => logs,
+            DeviceLogPayload::Legacy { log } => log.logs_array,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LegacyDeviceLogs {
+    pub logs_array: Vec<DeviceLogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceLogEntry {
+    pub creation_timestamp: Option<i64>,
+    #[serde(default)]
+    pub log_message: String,
+    pub log_level: Option<String>,
+    pub log_sourcefile: Option<String>,
+    pub log_codeline: Option<i64>,
+    pub device_status_stamp: Option<DeviceStatusStamp>,
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceStatusStamp {
+    pub wifi_rssi_level: Option<i64>,
+    pub wifi_status: Option<String>,
+    pub battery_voltage: Option<f64>,
+    pub wakeup_reason: Option<String>,
+    pub current_fw_version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewDeviceLog {
+    pub created_at: i64,
+    pub level: String,
+    pub message: String,
+    pub source_file: Option<String>,
+    pub source_line: Option<i64>,
+    pub wake_reason: Option<String>,
+    pub battery_voltage: Option<f64>,
+    pub wifi_status: Option<String>,
+    pub wifi_rssi: Option<i64>,
+    pub fw_version: Option<String>,
+    pub additional_info: Option<String>,
+}
+
+impl NewDeviceLog {
+    /// Firmware without a synced clock reports a zero timestamp, so fall back to `received_at`
+    pub fn from_entry(entry: DeviceLogEntry, received_at: i64) -> Self {
+        let status = entry.device_status_stamp;
+
+        NewDeviceLog {
+            created_at: entry
+                .creation_timestamp
+                .filter(|ts| *ts > 0)
+                .unwrap_or(received_at),
+            level: entry
+                .log_level
+                .map(|level| level.to_lowercase())
+                .unwrap_or_else(|| "info".to_string()),
+            message: entry.log_message,
+            source_file: entry.log_sourcefile,
+            source_line: entry.log_codeline,
+            wake_reason: status.as_ref().and_then(|s| s.wakeup_reason.clone()),
+            battery_voltage: status.as_ref().and_then(|s| s.battery_voltage),
+            wifi_status: status.as_ref().and_then(|s| s.wifi_status.clone()),
+            wifi_rssi: status.as_ref().and_then(|s| s.wifi_rssi_level),
+            fw_version: status.and_then(|s| s.current_fw_version),
+            additional_info: entry.additional_info.map(|info| info.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceLog {
+    pub id: i64,
+    pub device_id: String,
+    pub created_at: i64,
+    pub received_at: i64,
+    pub level: String,
+    pub message: String,
+    pub source_file: Option<String>,
+    pub source_line: Option<i64>,
+    pub wake_reason: Option<String>,
+    pub battery_voltage: Option<f64>,
+    pub wifi_status: Option<String>,
+    pub wifi_rssi: Option<i64>,
+    pub fw_version: Option<String>,
+    pub additional_info: Option<serde_json::Value>,
+}
+
+/// Filters for `GET /api/devices/{id}/logs`, timestamps are unix seconds
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct DeviceLogQuery {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub level: Option<String>,
+    pub q: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A single reading reported by a device when polling `/api/display`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetrySample {
+    pub recorded_at: i64,
+    pub rssi: Option<i64>,
+    pub battery_voltage: Option<f64>,
+    pub fw_version: Option<String>,
+    pub refresh_rate: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryStats {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+/// Readings downsampled into a fixed width time bucket starting at `start`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryBucket {
+    pub start: i64,
+    pub samples: i64,
+    pub rssi: Option<TelemetryStats>,
+    pub battery_voltage: Option<TelemetryStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TelemetrySeries {
+    Raw(Vec<TelemetrySample>),
+    Bucketed(Vec<TelemetryBucket>),
+}
+
+/// Query for `GET /api/devices/{id}/telemetry`, timestamps are unix seconds
+///
+/// `bucket` is a width such as `900`, `15m`, `1h` or `1d`. Raw samples are returned when omitted.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct TelemetryQuery {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub bucket: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+}
+
+/// Returned once on creation, the plain token is never stored
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatedApiToken {
+    pub id: String,
+    pub name: String,
+    pub token: String,
+}
+
+/// Returned when a device API key is rotated
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeviceApiKey {
+    pub id: String,
+    pub api_key: String,
+}
+
+/// One-shot instruction delivered to a device on its next `/api/display` call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeviceCommand {
+    ResetFirmware,
+    UpdateFirmware {
+        version: String,
+    },
+    /// Shows a URL or an `image:`/`layout:` reference once without advancing the rotation
+    ShowImage {
+        image: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Returned by `/readyz`, the instance is ready once every component is ok
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadinessReport {
+    pub status: HealthStatus,
+    pub components: ReadinessComponents,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadinessComponents {
+    pub database: ComponentHealth,
+    pub migrations: ComponentHealth,
+    /// Store the devices are kept in, which is a separate database with PostgreSQL
+    pub devices: ComponentHealth,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedDeviceCommand {
+    pub id: i64,
+    pub device_id: String,
+    pub command: DeviceCommand,
+    pub created_at: i64,
+    /// Unset until the command is handed to the device
+    pub delivered_at: Option<i64>,
+}
+
+/// Condition the alert engine checks every device against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertRule {
+    LowBattery,
+    WeakSignal,
+    Offline,
+    FirmwareMismatch,
+}
+
+impl AlertRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertRule::LowBattery => "low_battery",
+            AlertRule::WeakSignal => "weak_signal",
+            AlertRule::Offline => "offline",
+            AlertRule::FirmwareMismatch => "firmware_mismatch",
+        }
+    }
+}
+
+impl FromStr for AlertRule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low_battery" => Ok(AlertRule::LowBattery),
+            "weak_signal" => Ok(AlertRule::WeakSignal),
+            "offline" => Ok(AlertRule::Offline),
+            "firmware_mismatch" => Ok(AlertRule::FirmwareMismatch),
+            _ => anyhow::bail!("Unknown alert rule: {s}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+impl AlertStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatus::Firing => "firing",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+}
+
+impl FromStr for AlertStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "firing" => Ok(AlertStatus::Firing),
+            "resolved" => Ok(AlertStatus::Resolved),
+            _ => anyhow::bail!("Unknown alert status: {s}"),
+        }
+    }
+}
+
+/// A rule violated by a device, from when it started firing until the device recovered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: i64,
+    pub device_id: String,
+    pub rule: AlertRule,
+    pub status: AlertStatus,
+    pub message: String,
+    pub fired_at: i64,
+    /// Unset while the alert is firing
+    pub resolved_at: Option<i64>,
+}
+
+/// Filters for `GET /api/alerts`
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct AlertListQuery {
+    pub status: Option<AlertStatus>,
+    pub device_id: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct Firmware {
+    pub version: String,
+    pub checksum: String,
+    pub size: i64,
+    pub created_at: i64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Image {
+    pub id: String,
+    pub content_type: String,
+    pub size: i64,
+    pub created_at: i64,
+}
+
+#[derive(Debug)]
+pub struct ImageContent {
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Layout {
+    pub id: String,
+    pub name: String,
+    pub spec: LayoutSpec,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LayoutRequest {
+    pub name: String,
+    pub spec: LayoutSpec,
+}
+
+/// Rotation, refresh rate and firmware target shared by the devices assigned to the group
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeviceGroup {
+    pub id: String,
+    pub name: String,
+    pub images: Vec<String>,
+    pub desired_refresh_rate: Option<i64>,
+    pub target_fw_version: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceGroupRequest {
+    pub name: String,
+}
+
+/// Partial update of the group settings, with the same semantics as [`DevicePatch`]
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct DeviceGroupPatch {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub desired_refresh_rate: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub target_fw_version: Option<Option<String>>,
+}
+
+/// Refresh rate in seconds the firmware uses until the server tells it otherwise
+pub const DEFAULT_REFRESH_RATE: i64 = 1800;
+
 #[derive(Clone)]
 pub struct Device {
     pub id: String,
@@ -40,4 +564,117 @@ pub struct Device {
     pub fw_version: Option<String>,
     pub refresh_rate: Option<i64>,
     pub images: Vec<String>,
+    pub image_cursor: i64,
+    pub desired_refresh_rate: Option<i64>,
+    pub target_fw_version: Option<String>,
+    pub approval_status: ApprovalStatus,
+    pub schedule: Vec<ScheduleRule>,
+    pub timezone: Option<String>,
+    pub quiet_hours: Option<QuietHours>,
+    pub group_id: Option<String>,
+    /// Unix timestamp of the first poll for an image
+    pub first_seen_at: Option<i64>,
+    /// Unix timestamp of the latest poll for an image
+    pub last_seen_at: Option<i64>,
+}
+
+impl Device {
+    /// Seconds the device was told to sleep after its last poll. That is the rest of its quiet
+    /// hours if it polled during them, else its refresh rate as configured for it or else as last
+    /// reported by it.
+    pub fn expected_poll_interval(&self) -> i64 {
+        let refresh_rate = self
+            .desired_refresh_rate
+            .or(self.refresh_rate)
+            .unwrap_or(DEFAULT_REFRESH_RATE)
+            .max(1);
+
+        self.last_seen_at
+            .and_then(|last_seen_at| DateTime::from_timestamp(last_seen_at, 0))
+            .and_then(|last_seen_at| self.quiet_hours_sleep(last_seen_at))
+            .map_or(refresh_rate, |(_, sleep)| sleep.max(1))
+    }
+
+    /// Timezone schedules and quiet hours are evaluated in, UTC unless a valid one is set
+    pub fn local_timezone(&self) -> Tz {
+        self.timezone
+            .as_deref()
+            .and_then(parse_timezone)
+            .unwrap_or(Tz::UTC)
+    }
+
+    /// Quiet hours the device is in at `now`, with the seconds until they end
+    pub fn quiet_hours_sleep(&self, now: DateTime<Utc>) -> Option<(&QuietHours, i64)> {
+        let quiet_hours = self.quiet_hours.as_ref()?;
+
+        Some((
+            quiet_hours,
+            quiet_hours.remaining(self.local_timezone(), now)?,
+        ))
+    }
+
+    /// Connection status as of `now`, judged by how many poll intervals passed since the last poll
+    pub fn status(&self, settings: &StatusSettings, now: i64) -> DeviceStatus {
+        let Some(last_seen_at) = self.last_seen_at else {
+            return DeviceStatus::Offline;
+        };
+
+        let missed = (now - last_seen_at).max(0) as f64 / self.expected_poll_interval() as f64;
+
+        if missed <= settings.grace_factor {
+            DeviceStatus::Online
+        } else if missed <= settings.offline_factor {
+            DeviceStatus::Late
+        } else {
+            DeviceStatus::Offline
+        }
+    }
+
+    /// Takes over what the device reported when polling, like `DeviceRepository::update_status`
+    /// does for the stored device
+    pub fn apply_status(
+        &mut self,
+        seen_at: i64,
+        rssi: Option<i32>,
+        battery_voltage: Option<f32>,
+        fw_version: Option<&str>,
+        refresh_rate: Option<i32>,
+    ) {
+        if let Some(rssi) = rssi {
+            self.rssi = Some(rssi.into());
+        }
+        if let Some(battery_voltage) = battery_voltage {
+            self.battery_voltage = Some(battery_voltage.into());
+        }
+        if let Some(fw_version) = fw_version {
+            self.fw_version = Some(fw_version.to_string());
+        }
+        if let Some(refresh_rate) = refresh_rate {
+            self.refresh_rate = Some(refresh_rate.into());
+        }
+        self.first_seen_at = self.first_seen_at.or(Some(seen_at));
+        self.last_seen_at = Some(seen_at);
+    }
+
+    /// Entries of the default rotation and of every schedule rule
+    pub fn rotation_entries(&self) -> impl Iterator<Item = &String> {
+        self.images
+            .iter()
+            .chain(self.schedule.iter().flat_map(|rule| &rule.images))
+    }
+
+    /// Fills in the settings the device leaves unset from its group, so device settings act as
+    /// overrides. An empty rotation counts as unset.
+    pub fn with_group(mut self, group: &DeviceGroup) -> Self {
+        if self.images.is_empty() {
+            self.images = group.images.clone();
+        }
+
+        self.desired_refresh_rate = self.desired_refresh_rate.or(group.desired_refresh_rate);
+        self.target_fw_version = self
+            .target_fw_version
+            .or_else(|| group.target_fw_version.clone());
+
+        self
+    }
 }
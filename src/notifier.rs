@@ -0,0 +1,112 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use mockall::automock;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::models::{Alert, AlertStatus};
+
+/// Delivers fired and resolved alerts to whoever needs to act on them
+#[async_trait]
+#[automock]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()>;
+}
+
+pub type NotifierRef = Arc<dyn Notifier + Send + Sync>;
+
+/// Alerts waiting for delivery before new ones are dropped
+const QUEUE_CAPACITY: usize = 1024;
+
+const WEBHOOK_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hands alerts to a background task delivering them through another notifier, so a slow
+/// delivery never holds up the request that changed the alert
+pub struct QueuedNotifier(mpsc::Sender<Alert>);
+
+impl QueuedNotifier {
+    /// Starts the delivery task, which runs until the notifier is dropped
+    pub fn spawn(inner: NotifierRef) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<Alert>(QUEUE_CAPACITY);
+
+        tokio::spawn(async move {
+            while let Some(alert) = receiver.recv().await {
+                if let Err(error) = inner.notify(&alert).await {
+                    warn!(msg = "Failed to deliver alert notification", alert_id = alert.id, %error);
+                }
+            }
+        });
+
+        Self(sender)
+    }
+}
+
+#[async_trait]
+impl Notifier for QueuedNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        self.0
+            .try_send(alert.clone())
+            .map_err(|error| anyhow::anyhow!("Alert notification queue unavailable: {error}"))
+    }
+}
+
+/// Writes alerts to the server log, used when no other delivery is configured
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        match alert.status {
+            AlertStatus::Firing => warn!(
+                msg = "Alert firing",
+                device_id = %alert.device_id,
+                rule = alert.rule.as_str(),
+                message = %alert.message
+            ),
+            AlertStatus::Resolved => info!(
+                msg = "Alert resolved",
+                device_id = %alert.device_id,
+                rule = alert.rule.as_str()
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+/// Posts every alert as JSON to a URL, such as a chat or paging webhook
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> anyhow::Result<Self> {
+        // An endpoint that never answers must not pile up deliveries forever
+        let client = reqwest::Client::builder()
+            .connect_timeout(WEBHOOK_CONNECT_TIMEOUT)
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: url.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, alert: &Alert) -> anyhow::Result<()> {
+        self.client
+            .post(&self.url)
+            .json(alert)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
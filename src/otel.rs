@@ -0,0 +1,125 @@
+use std::{collections::HashMap, io::Write};
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry_otlp::{
+    WithExportConfig, WithHttpConfig, WithTonicConfig, tonic_types::metadata::MetadataMap,
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::{OTelSdkError, OTelSdkResult},
+    trace::{Sampler, SdkTracerProvider, SpanData, SpanExporter},
+};
+use serde_json::{Map, Value, json};
+
+use crate::config::{TelemetrySettings, TraceExporter};
+
+/// Builds the tracer provider for the configured exporter, `None` when tracing is disabled
+pub fn tracer_provider(settings: &TelemetrySettings) -> anyhow::Result<Option<SdkTracerProvider>> {
+    if !(0.0..=1.0).contains(&settings.sampling_ratio) {
+        anyhow::bail!("Telemetry sampling ratio must be between 0.0 and 1.0");
+    }
+
+    let builder = SdkTracerProvider::builder()
+        .with_resource(
+            Resource::builder()
+                .with_service_name(settings.service_name.clone())
+                .build(),
+        )
+        // Child spans follow the decision taken for the root span of their request
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            settings.sampling_ratio,
+        ))));
+
+    let builder = match settings.exporter {
+        TraceExporter::None => return Ok(None),
+        TraceExporter::OtlpHttp => {
+            let mut exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_http()
+                .with_headers(settings.headers.clone());
+            if let Some(endpoint) = &settings.endpoint {
+                exporter = exporter.with_endpoint(endpoint);
+            }
+            builder.with_batch_exporter(exporter.build()?)
+        }
+        TraceExporter::OtlpGrpc => {
+            let mut exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .with_metadata(MetadataMap::from_headers(header_map(&settings.headers)?));
+            if let Some(endpoint) = &settings.endpoint {
+                exporter = exporter.with_endpoint(endpoint);
+            }
+            builder.with_batch_exporter(exporter.build()?)
+        }
+        TraceExporter::Stdout => builder.with_simple_exporter(StdoutSpanExporter),
+    };
+
+    Ok(Some(builder.build()))
+}
+
+fn header_map(headers: &HashMap<String, String>) -> anyhow::Result<HeaderMap> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            Ok((
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            ))
+        })
+        .collect()
+}
+
+/// Flushes and shuts down the tracer provider when dropped, so spans of the last requests are
+/// exported before the process exits
+pub struct TracerProviderGuard(pub SdkTracerProvider);
+
+impl Drop for TracerProviderGuard {
+    fn drop(&mut self) {
+        // The subscriber may be exporting through this provider, so report to stderr directly
+        if let Err(error) = self.0.shutdown() {
+            eprintln!("Failed to shut down tracer provider: {error}");
+        }
+    }
+}
+
+/// Writes every finished span as a line of JSON, for debugging without a collector
+#[derive(Debug)]
+pub struct StdoutSpanExporter;
+
+impl SpanExporter for StdoutSpanExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let mut stdout = std::io::stdout().lock();
+
+        for span in batch {
+            writeln!(stdout, "{}", span_json(&span))
+                .map_err(|error| OTelSdkError::InternalFailure(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn span_json(span: &SpanData) -> Value {
+    let attributes: Map<String, Value> = span
+        .attributes
+        .iter()
+        .map(|attribute| {
+            (
+                attribute.key.to_string(),
+                Value::String(attribute.value.to_string()),
+            )
+        })
+        .collect();
+
+    json!({
+        "name": span.name,
+        "trace_id": span.span_context.trace_id().to_string(),
+        "span_id": span.span_context.span_id().to_string(),
+        "parent_span_id": span.parent_span_id.to_string(),
+        "duration_us": span
+            .end_time
+            .duration_since(span.start_time)
+            .unwrap_or_default()
+            .as_micros() as u64,
+        "attributes": attributes,
+    })
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::{Alert, AlertListQuery, AlertRule};
+
+pub mod sqlite;
+pub use sqlite::SqliteAlertRepo;
+
+#[async_trait]
+#[automock]
+pub trait AlertRepository: Send + Sync {
+    /// Start an alert for the rule unless one is already firing for the device, returning the
+    /// new alert
+    async fn fire(
+        &self,
+        device_id: &str,
+        rule: AlertRule,
+        message: &str,
+        fired_at: i64,
+    ) -> anyhow::Result<Option<Alert>>;
+
+    /// Resolve the firing alert for the rule, returning it if there was one
+    async fn resolve(
+        &self,
+        device_id: &str,
+        rule: AlertRule,
+        resolved_at: i64,
+    ) -> anyhow::Result<Option<Alert>>;
+
+    /// List the alerts currently firing for a device
+    async fn list_firing(&self, device_id: &str) -> anyhow::Result<Vec<Alert>>;
+
+    /// List alerts matching the query, most recently fired first
+    async fn list(&self, query: &AlertListQuery) -> anyhow::Result<Vec<Alert>>;
+
+    /// Delete all alerts of a device, firing or resolved
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()>;
+}
+
+pub type AlertRepo = std::sync::Arc<dyn AlertRepository + Send + Sync>;
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::{Alert, AlertListQuery, AlertRule, AlertStatus};
+
+use super::AlertRepository;
+
+pub struct SqliteAlertRepo(Arc<SqlitePool>);
+
+impl SqliteAlertRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl AlertRepository for SqliteAlertRepo {
+    #[instrument(
+        name = "sqlite_alert_repo.fire",
+        skip(self, message),
+        fields(device_id, rule = rule.as_str())
+    )]
+    async fn fire(
+        &self,
+        device_id: &str,
+        rule: AlertRule,
+        message: &str,
+        fired_at: i64,
+    ) -> anyhow::Result<Option<Alert>> {
+        let rule = rule.as_str();
+
+        // The partial unique index turns a second firing alert for the same rule into a no-op
+        let record = sqlx::query!(
+            r#"
+            INSERT INTO alerts (device_id, rule, status, message, fired_at)
+            VALUES (?, ?, 'firing', ?, ?)
+            ON CONFLICT DO NOTHING
+            RETURNING id, device_id, rule, status, message, fired_at, resolved_at
+            "#,
+            device_id,
+            rule,
+            message,
+            fired_at
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        record
+            .map(|record| {
+                Ok(Alert {
+                    id: record.id,
+                    device_id: record.device_id,
+                    rule: record.rule.parse()?,
+                    status: record.status.parse()?,
+                    message: record.message,
+                    fired_at: record.fired_at,
+                    resolved_at: record.resolved_at,
+                })
+            })
+            .transpose()
+    }
+
+    #[instrument(
+        name = "sqlite_alert_repo.resolve",
+        skip(self),
+        fields(device_id, rule = rule.as_str())
+    )]
+    async fn resolve(
+        &self,
+        device_id: &str,
+        rule: AlertRule,
+        resolved_at: i64,
+    ) -> anyhow::Result<Option<Alert>> {
+        let rule = rule.as_str();
+
+        let record = sqlx::query!(
+            r#"
+            UPDATE alerts
+            SET status = 'resolved', resolved_at = ?
+            WHERE device_id = ? AND rule = ? AND status = 'firing'
+            RETURNING id, device_id, rule, status, message, fired_at, resolved_at
+            "#,
+            resolved_at,
+            device_id,
+            rule
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        record
+            .map(|record| {
+                Ok(Alert {
+                    id: record.id,
+                    device_id: record.device_id,
+                    rule: record.rule.parse()?,
+                    status: record.status.parse()?,
+                    message: record.message,
+                    fired_at: record.fired_at,
+                    resolved_at: record.resolved_at,
+                })
+            })
+            .transpose()
+    }
+
+    #[instrument(name = "sqlite_alert_repo.list_firing", skip(self), fields(device_id))]
+    async fn list_firing(&self, device_id: &str) -> anyhow::Result<Vec<Alert>> {
+        sqlx::query!(
+            r#"
+            SELECT id, device_id, rule, status, message, fired_at, resolved_at
+            FROM alerts
+            WHERE device_id = ? AND status = 'firing'
+            ORDER BY id
+            "#,
+            device_id
+        )
+        .fetch_all(&*self.0)
+        .await?
+        .into_iter()
+        .map(|record| {
+            Ok(Alert {
+                id: record.id,
+                device_id: record.device_id,
+                rule: record.rule.parse()?,
+                status: record.status.parse()?,
+                message: record.message,
+                fired_at: record.fired_at,
+                resolved_at: record.resolved_at,
+            })
+        })
+        .collect()
+    }
+
+    #[instrument(name = "sqlite_alert_repo.list", skip(self))]
+    async fn list(&self, query: &AlertListQuery) -> anyhow::Result<Vec<Alert>> {
+        let status = query.status.as_ref().map(AlertStatus::as_str);
+        let device_id = query.device_id.as_deref();
+
+        sqlx::query!(
+            r#"
+            SELECT id, device_id, rule, status, message, fired_at, resolved_at
+            FROM alerts
+            WHERE (?1 IS NULL OR status = ?1)
+                AND (?2 IS NULL OR device_id = ?2)
+            ORDER BY fired_at DESC, id DESC
+            "#,
+            status,
+            device_id
+        )
+        .fetch_all(&*self.0)
+        .await?
+        .into_iter()
+        .map(|record| {
+            Ok(Alert {
+                id: record.id,
+                device_id: record.device_id,
+                rule: record.rule.parse()?,
+                status: record.status.parse()?,
+                message: record.message,
+                fired_at: record.fired_at,
+                resolved_at: record.resolved_at,
+            })
+        })
+        .collect()
+    }
+
+    #[instrument(
+        name = "sqlite_alert_repo.delete_by_device",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()> {
+        sqlx::query!("DELETE FROM alerts WHERE device_id = ?", device_id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+}
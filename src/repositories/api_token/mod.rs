@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::ApiToken;
+
+pub mod sqlite;
+pub use sqlite::SqliteApiTokenRepo;
+
+#[async_trait]
+#[automock]
+pub trait ApiTokenRepository: Send + Sync {
+    /// Store a new management API token by its hash
+    async fn create(&self, id: &str, name: &str, token_hash: &str) -> anyhow::Result<()>;
+
+    /// Get a token by the hash of its value
+    async fn get_by_hash(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>>;
+
+    /// List all tokens
+    async fn list(&self) -> anyhow::Result<Vec<ApiToken>>;
+
+    /// Delete a token, returning whether it existed
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
+}
+
+pub type ApiTokenRepo = std::sync::Arc<dyn ApiTokenRepository + Send + Sync>;
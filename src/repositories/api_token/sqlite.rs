@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::ApiToken;
+
+use super::ApiTokenRepository;
+
+pub struct SqliteApiTokenRepo(Arc<SqlitePool>);
+
+impl SqliteApiTokenRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for SqliteApiTokenRepo {
+    #[instrument(
+        name = "sqlite_api_token_repo.create",
+        skip(self, token_hash),
+        fields(id)
+    )]
+    async fn create(&self, id: &str, name: &str, token_hash: &str) -> anyhow::Result<()> {
+        sqlx::query!(
+            "INSERT INTO api_tokens (id, name, token_hash) VALUES (?, ?, ?)",
+            id,
+            name,
+            token_hash
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_api_token_repo.get_by_hash", skip(self, token_hash))]
+    async fn get_by_hash(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>> {
+        let token = sqlx::query_as!(
+            ApiToken,
+            "SELECT id, name, created_at FROM api_tokens WHERE token_hash = ?",
+            token_hash
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(token)
+    }
+
+    #[instrument(name = "sqlite_api_token_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<ApiToken>> {
+        Ok(sqlx::query_as!(
+            ApiToken,
+            "SELECT id, name, created_at FROM api_tokens ORDER BY created_at, id"
+        )
+        .fetch_all(&*self.0)
+        .await?)
+    }
+
+    #[instrument(name = "sqlite_api_token_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!("DELETE FROM api_tokens WHERE id = ?", id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
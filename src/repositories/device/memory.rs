@@ -0,0 +1,329 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    models::{ApprovalStatus, Device, DevicePatch},
+    schedule::ScheduleRule,
+};
+
+use super::DeviceRepository;
+
+struct StoredDevice {
+    device: Device,
+    setup_pending: bool,
+}
+
+/// Keeps devices in process memory, ordered by ID like the SQL backends. Everything is lost when
+/// the server stops.
+#[derive(Default)]
+pub struct InMemoryDeviceRepo(Mutex<BTreeMap<String, StoredDevice>>);
+
+impl InMemoryDeviceRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn devices(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, StoredDevice>> {
+        // A panic while holding the lock cannot leave a device half updated
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn find(&self, predicate: impl Fn(&Device) -> bool) -> Vec<Device> {
+        self.devices()
+            .values()
+            .filter(|stored| predicate(&stored.device))
+            .map(|stored| stored.device.clone())
+            .collect()
+    }
+
+    /// Applies `update` to the device, returning whether it existed
+    fn modify(&self, id: &str, update: impl FnOnce(&mut StoredDevice)) -> bool {
+        match self.devices().get_mut(id) {
+            Some(stored) => {
+                update(stored);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl DeviceRepository for InMemoryDeviceRepo {
+    #[instrument(name = "in_memory_device_repo.create", skip(self), fields(id))]
+    async fn create(
+        &self,
+        id: &str,
+        mac: Option<&str>,
+        api_key: &str,
+        approval_status: ApprovalStatus,
+    ) -> anyhow::Result<()> {
+        let mut devices = self.devices();
+
+        if devices.contains_key(id) {
+            anyhow::bail!("Device {id} already exists");
+        }
+        if devices
+            .values()
+            .any(|stored| stored.device._api_key == api_key)
+        {
+            anyhow::bail!("API key is already in use");
+        }
+
+        devices.insert(
+            id.to_string(),
+            StoredDevice {
+                device: Device {
+                    id: id.to_string(),
+                    mac: mac.map(str::to_string),
+                    _api_key: api_key.to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                },
+                setup_pending: false,
+            },
+        );
+
+        Ok(())
+    }
+
+    #[instrument(name = "in_memory_device_repo.exists_by_mac", skip(self), fields(mac))]
+    async fn exists_by_mac(&self, mac: &str) -> anyhow::Result<bool> {
+        Ok(self
+            .devices()
+            .values()
+            .any(|stored| stored.device.mac.as_deref() == Some(mac)))
+    }
+
+    #[instrument(name = "in_memory_device_repo.get_by_api_key", skip(self))]
+    async fn get_by_api_key(&self, api_key: &str) -> anyhow::Result<Option<Device>> {
+        Ok(self
+            .find(|device| device._api_key == api_key)
+            .into_iter()
+            .next())
+    }
+
+    #[instrument(name = "in_memory_device_repo.get_by_id", skip(self))]
+    async fn get_by_id(&self, id: &str) -> anyhow::Result<Option<Device>> {
+        Ok(self.devices().get(id).map(|stored| stored.device.clone()))
+    }
+
+    #[instrument(name = "in_memory_device_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<Device>> {
+        Ok(self.find(|_| true))
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.list_by_approval_status",
+        skip(self),
+        fields(status)
+    )]
+    async fn list_by_approval_status(&self, status: ApprovalStatus) -> anyhow::Result<Vec<Device>> {
+        Ok(self.find(|device| device.approval_status == status))
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.list_by_group",
+        skip(self),
+        fields(group_id)
+    )]
+    async fn list_by_group(&self, group_id: &str) -> anyhow::Result<Vec<Device>> {
+        Ok(self.find(|device| device.group_id.as_deref() == Some(group_id)))
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.set_group_members",
+        skip(self, device_ids),
+        fields(group_id)
+    )]
+    async fn set_group_members(
+        &self,
+        group_id: &str,
+        device_ids: &[String],
+    ) -> anyhow::Result<bool> {
+        let mut devices = self.devices();
+
+        if !device_ids.iter().all(|id| devices.contains_key(id)) {
+            return Ok(false);
+        }
+
+        for (id, stored) in devices.iter_mut() {
+            if device_ids.contains(id) {
+                stored.device.group_id = Some(group_id.to_string());
+            } else if stored.device.group_id.as_deref() == Some(group_id) {
+                stored.device.group_id = None;
+            }
+        }
+
+        Ok(true)
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.update_approval_status",
+        skip(self),
+        fields(id)
+    )]
+    async fn update_approval_status(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> anyhow::Result<bool> {
+        Ok(self.modify(id, |stored| stored.device.approval_status = status))
+    }
+
+    #[instrument(name = "in_memory_device_repo.update_images", skip(self), fields(id))]
+    async fn update_images(&self, id: &str, images: &[String]) -> anyhow::Result<()> {
+        // Restart the rotation so the new list is shown from the beginning
+        self.modify(id, |stored| {
+            stored.device.images = images.to_vec();
+            stored.device.image_cursor = 0;
+        });
+
+        Ok(())
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.update_schedule",
+        skip(self, schedule),
+        fields(id)
+    )]
+    async fn update_schedule(&self, id: &str, schedule: &[ScheduleRule]) -> anyhow::Result<bool> {
+        // The active rotation may change, so start it from the beginning
+        Ok(self.modify(id, |stored| {
+            stored.device.schedule = schedule.to_vec();
+            stored.device.image_cursor = 0;
+        }))
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.update_image_cursor",
+        skip(self),
+        fields(id)
+    )]
+    async fn update_image_cursor(&self, id: &str, cursor: i64) -> anyhow::Result<()> {
+        self.modify(id, |stored| stored.device.image_cursor = cursor);
+
+        Ok(())
+    }
+
+    #[instrument(name = "in_memory_device_repo.update", skip(self), fields(id))]
+    async fn update(&self, id: &str, patch: &DevicePatch) -> anyhow::Result<()> {
+        self.modify(id, |stored| {
+            let device = &mut stored.device;
+
+            if let Some(desired_refresh_rate) = patch.desired_refresh_rate {
+                device.desired_refresh_rate = desired_refresh_rate;
+            }
+            if let Some(target_fw_version) = &patch.target_fw_version {
+                device.target_fw_version = target_fw_version.clone();
+            }
+            if let Some(timezone) = &patch.timezone {
+                device.timezone = timezone.clone();
+            }
+            if let Some(quiet_hours) = &patch.quiet_hours {
+                device.quiet_hours = quiet_hours.clone();
+            }
+            if let Some(group_id) = &patch.group_id {
+                device.group_id = group_id.clone();
+            }
+        });
+
+        Ok(())
+    }
+
+    #[instrument(name = "in_memory_device_repo.update_status", skip(self), fields(id))]
+    async fn update_status(
+        &self,
+        id: &str,
+        seen_at: i64,
+        rssi: Option<i32>,
+        battery_voltage: Option<f32>,
+        fw_version: Option<&str>,
+        refresh_rate: Option<i32>,
+    ) -> anyhow::Result<()> {
+        self.modify(id, |stored| {
+            stored
+                .device
+                .apply_status(seen_at, rssi, battery_voltage, fw_version, refresh_rate)
+        });
+
+        Ok(())
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.rotate_api_key",
+        skip(self, api_key),
+        fields(id)
+    )]
+    async fn rotate_api_key(&self, id: &str, api_key: &str) -> anyhow::Result<bool> {
+        let mut devices = self.devices();
+
+        if devices
+            .values()
+            .any(|stored| stored.device.id != id && stored.device._api_key == api_key)
+        {
+            anyhow::bail!("API key is already in use");
+        }
+
+        Ok(match devices.get_mut(id) {
+            Some(stored) => {
+                stored.device._api_key = api_key.to_string();
+                stored.setup_pending = true;
+                true
+            }
+            None => false,
+        })
+    }
+
+    #[instrument(
+        name = "in_memory_device_repo.take_pending_setup",
+        skip(self),
+        fields(mac)
+    )]
+    async fn take_pending_setup(&self, mac: &str) -> anyhow::Result<Option<Device>> {
+        Ok(self
+            .devices()
+            .values_mut()
+            .find(|stored| stored.setup_pending && stored.device.mac.as_deref() == Some(mac))
+            .map(|stored| {
+                stored.setup_pending = false;
+                stored.device.clone()
+            }))
+    }
+
+    #[instrument(name = "in_memory_device_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        Ok(self.devices().remove(id).is_some())
+    }
+
+    #[instrument(name = "in_memory_device_repo.ping", skip(self))]
+    async fn ping(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    #[instrument(name = "in_memory_device_repo.applied_migration", skip(self))]
+    async fn applied_migration(&self) -> anyhow::Result<Option<i64>> {
+        Ok(None)
+    }
+
+    fn latest_migration(&self) -> Option<i64> {
+        None
+    }
+}
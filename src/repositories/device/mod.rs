@@ -1,16 +1,31 @@
 use async_trait::async_trait;
 use mockall::automock;
 
-use crate::models::Device;
+use crate::{
+    models::{ApprovalStatus, Device, DevicePatch},
+    schedule::ScheduleRule,
+};
 
+pub mod memory;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 pub mod sqlite;
+pub use memory::InMemoryDeviceRepo;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresDeviceRepo;
 pub use sqlite::SqliteDeviceRepo;
 
 #[async_trait]
 #[automock]
 pub trait DeviceRepository: Send + Sync {
     /// Create a new device
-    async fn create(&self, id: &str, mac: Option<&str>, api_key: &str) -> anyhow::Result<()>;
+    async fn create(
+        &self,
+        id: &str,
+        mac: Option<&str>,
+        api_key: &str,
+        approval_status: ApprovalStatus,
+    ) -> anyhow::Result<()>;
 
     /// Check if a device exists by its MAC address
     async fn exists_by_mac(&self, mac: &str) -> anyhow::Result<bool>;
@@ -24,27 +39,75 @@ pub trait DeviceRepository: Send + Sync {
     /// List all devices
     async fn list(&self) -> anyhow::Result<Vec<Device>>;
 
+    /// List the devices with the given approval status
+    async fn list_by_approval_status(&self, status: ApprovalStatus) -> anyhow::Result<Vec<Device>>;
+
+    /// List the devices assigned to a group
+    async fn list_by_group(&self, group_id: &str) -> anyhow::Result<Vec<Device>>;
+
+    /// Make the given devices the members of a group, moving them out of any other group and
+    /// taking out current members that are not listed. Returns false and changes nothing when a
+    /// device does not exist.
+    async fn set_group_members(
+        &self,
+        group_id: &str,
+        device_ids: &[String],
+    ) -> anyhow::Result<bool>;
+
+    /// Approve or reject a device, returning whether it existed
+    async fn update_approval_status(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> anyhow::Result<bool>;
+
     /// Update device images
     async fn update_images(&self, id: &str, images: &[String]) -> anyhow::Result<()>;
 
-    /// Update device status
+    /// Replace the schedule of a device, returning whether it existed
+    async fn update_schedule(&self, id: &str, schedule: &[ScheduleRule]) -> anyhow::Result<bool>;
+
+    /// Update the position of the next image to display in the rotation
+    async fn update_image_cursor(&self, id: &str, cursor: i64) -> anyhow::Result<()>;
+
+    /// Update the server controlled device settings
+    async fn update(&self, id: &str, patch: &DevicePatch) -> anyhow::Result<()>;
+
+    /// Update the status a device reported when polling at `seen_at`, also recording when it was
+    /// first and last seen
     async fn update_status(
         &self,
         id: &str,
+        seen_at: i64,
         rssi: Option<i32>,
         battery_voltage: Option<f32>,
         fw_version: Option<&str>,
         refresh_rate: Option<i32>,
     ) -> anyhow::Result<()>;
 
-    // /// Get a device by its MAC address
-    // async fn get_by_mac(&self, mac: &str) -> anyhow::Result<Option<Device>>;
+    /// Replace the API key of a device and allow it to set up again, returning whether it existed
+    async fn rotate_api_key(&self, id: &str, api_key: &str) -> anyhow::Result<bool>;
+
+    /// Get the device with this MAC address if its API key was rotated, clearing the flag so the
+    /// new key is handed out once
+    async fn take_pending_setup(&self, mac: &str) -> anyhow::Result<Option<Device>>;
 
-    // /// Update device fields (mac/api_key)
-    // async fn update(&self, ...) -> anyhow::Result<()>;
+    /// Delete a device by its ID, returning whether it existed. Data other repositories keep for
+    /// the device is deleted through them.
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
 
-    // /// Delete a device by its ID
-    // async fn delete(&self, id: &str) -> anyhow::Result<()>;
+    /// Run a trivial query against the device store to check it answers
+    async fn ping(&self) -> anyhow::Result<()>;
+
+    /// Version of the latest migration applied to the device store, `None` for stores without
+    /// migrations
+    async fn applied_migration(&self) -> anyhow::Result<Option<i64>>;
+
+    /// Version of the newest migration this build bundles for the device store
+    fn latest_migration(&self) -> Option<i64>;
+
+    // /// Get a device by its MAC address
+    // async fn get_by_mac(&self, mac: &str) -> anyhow::Result<Option<Device>>;
 
     // /// Check if a device exists by its ID (works for physical devices)
     // async fn exists_by_id(&self, id: &str) -> anyhow::Result<bool>;
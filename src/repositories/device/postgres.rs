@@ -0,0 +1,461 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::{
+    db::latest_postgres_migration_version,
+    models::{ApprovalStatus, Device, DevicePatch},
+    schedule::ScheduleRule,
+};
+
+use super::DeviceRepository;
+
+// A macro rather than a const so queries can be assembled with `concat!` at compile time
+macro_rules! device_columns {
+    () => {
+        r#"
+        id,
+        mac,
+        api_key,
+        rssi,
+        battery_voltage,
+        fw_version,
+        refresh_rate,
+        images_json,
+        image_cursor,
+        desired_refresh_rate,
+        target_fw_version,
+        approval_status,
+        schedule_json,
+        timezone,
+        quiet_hours_json,
+        group_id,
+        first_seen_at,
+        last_seen_at
+        "#
+    };
+}
+
+/// The query macros check against the single `DATABASE_URL` of the crate, which is SQLite, so
+/// these queries are checked by the shared repository tests running against PostgreSQL in CI
+#[derive(sqlx::FromRow)]
+struct DeviceRow {
+    id: String,
+    mac: Option<String>,
+    api_key: String,
+    rssi: Option<i64>,
+    battery_voltage: Option<f64>,
+    fw_version: Option<String>,
+    refresh_rate: Option<i64>,
+    images_json: String,
+    image_cursor: i64,
+    desired_refresh_rate: Option<i64>,
+    target_fw_version: Option<String>,
+    approval_status: String,
+    schedule_json: String,
+    timezone: Option<String>,
+    quiet_hours_json: Option<String>,
+    group_id: Option<String>,
+    first_seen_at: Option<i64>,
+    last_seen_at: Option<i64>,
+}
+
+impl From<DeviceRow> for Device {
+    fn from(record: DeviceRow) -> Self {
+        Device {
+            id: record.id,
+            mac: record.mac,
+            _api_key: record.api_key,
+            rssi: record.rssi,
+            battery_voltage: record.battery_voltage,
+            fw_version: record.fw_version,
+            refresh_rate: record.refresh_rate,
+            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
+            image_cursor: record.image_cursor,
+            desired_refresh_rate: record.desired_refresh_rate,
+            target_fw_version: record.target_fw_version,
+            approval_status: record
+                .approval_status
+                .parse()
+                .unwrap_or(ApprovalStatus::Pending),
+            schedule: serde_json::from_str::<Vec<ScheduleRule>>(&record.schedule_json)
+                .unwrap_or_default(),
+            timezone: record.timezone,
+            quiet_hours: record
+                .quiet_hours_json
+                .as_deref()
+                .and_then(|json| serde_json::from_str(json).ok()),
+            group_id: record.group_id,
+            first_seen_at: record.first_seen_at,
+            last_seen_at: record.last_seen_at,
+        }
+    }
+}
+
+pub struct PostgresDeviceRepo(Arc<PgPool>);
+
+impl PostgresDeviceRepo {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl DeviceRepository for PostgresDeviceRepo {
+    #[instrument(name = "postgres_device_repo.create", skip(self), fields(id))]
+    async fn create(
+        &self,
+        id: &str,
+        mac: Option<&str>,
+        api_key: &str,
+        approval_status: ApprovalStatus,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO devices (mac, api_key, id, approval_status) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(mac)
+        .bind(api_key)
+        .bind(id)
+        .bind(approval_status.as_str())
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "postgres_device_repo.exists_by_mac", skip(self), fields(mac))]
+    async fn exists_by_mac(&self, mac: &str) -> anyhow::Result<bool> {
+        Ok(
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM devices WHERE mac = $1)")
+                .bind(mac)
+                .fetch_one(&*self.0)
+                .await?,
+        )
+    }
+
+    #[instrument(name = "postgres_device_repo.get_by_api_key", skip(self))]
+    async fn get_by_api_key(&self, api_key: &str) -> anyhow::Result<Option<Device>> {
+        let device = sqlx::query_as::<_, DeviceRow>(concat!(
+            "SELECT ",
+            device_columns!(),
+            " FROM devices WHERE api_key = $1"
+        ))
+        .bind(api_key)
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(device.map(Device::from))
+    }
+
+    #[instrument(name = "postgres_device_repo.get_by_id", skip(self))]
+    async fn get_by_id(&self, id: &str) -> anyhow::Result<Option<Device>> {
+        let device = sqlx::query_as::<_, DeviceRow>(concat!(
+            "SELECT ",
+            device_columns!(),
+            " FROM devices WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(device.map(Device::from))
+    }
+
+    #[instrument(name = "postgres_device_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<Device>> {
+        let devices = sqlx::query_as::<_, DeviceRow>(concat!(
+            "SELECT ",
+            device_columns!(),
+            " FROM devices ORDER BY id"
+        ))
+        .fetch_all(&*self.0)
+        .await?;
+
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.list_by_approval_status",
+        skip(self),
+        fields(status)
+    )]
+    async fn list_by_approval_status(&self, status: ApprovalStatus) -> anyhow::Result<Vec<Device>> {
+        let devices = sqlx::query_as::<_, DeviceRow>(concat!(
+            "SELECT ",
+            device_columns!(),
+            " FROM devices WHERE approval_status = $1 ORDER BY id"
+        ))
+        .bind(status.as_str())
+        .fetch_all(&*self.0)
+        .await?;
+
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.list_by_group",
+        skip(self),
+        fields(group_id)
+    )]
+    async fn list_by_group(&self, group_id: &str) -> anyhow::Result<Vec<Device>> {
+        let devices = sqlx::query_as::<_, DeviceRow>(concat!(
+            "SELECT ",
+            device_columns!(),
+            " FROM devices WHERE group_id = $1 ORDER BY id"
+        ))
+        .bind(group_id)
+        .fetch_all(&*self.0)
+        .await?;
+
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.set_group_members",
+        skip(self, device_ids),
+        fields(group_id)
+    )]
+    async fn set_group_members(
+        &self,
+        group_id: &str,
+        device_ids: &[String],
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.0.begin().await?;
+
+        // Duplicate IDs in the request only count once
+        let found: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) = cardinality(ARRAY(SELECT DISTINCT unnest($1::text[]))) \
+             FROM devices WHERE id = ANY($1)",
+        )
+        .bind(device_ids)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !found {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE devices
+            SET group_id = CASE WHEN id = ANY($2) THEN $1 ELSE NULL END
+            WHERE group_id = $1 OR id = ANY($2)
+            "#,
+        )
+        .bind(group_id)
+        .bind(device_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.update_approval_status",
+        skip(self),
+        fields(id)
+    )]
+    async fn update_approval_status(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query("UPDATE devices SET approval_status = $1 WHERE id = $2")
+            .bind(status.as_str())
+            .bind(id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "postgres_device_repo.update_images", skip(self), fields(id))]
+    async fn update_images(&self, id: &str, images: &[String]) -> anyhow::Result<()> {
+        let images_json = serde_json::to_string(&images)?;
+
+        // Restart the rotation so the new list is shown from the beginning
+        sqlx::query("UPDATE devices SET images_json = $1, image_cursor = 0 WHERE id = $2")
+            .bind(images_json)
+            .bind(id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.update_schedule",
+        skip(self, schedule),
+        fields(id)
+    )]
+    async fn update_schedule(&self, id: &str, schedule: &[ScheduleRule]) -> anyhow::Result<bool> {
+        let schedule_json = serde_json::to_string(schedule)?;
+
+        // The active rotation may change, so start it from the beginning
+        let result =
+            sqlx::query("UPDATE devices SET schedule_json = $1, image_cursor = 0 WHERE id = $2")
+                .bind(schedule_json)
+                .bind(id)
+                .execute(&*self.0)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.update_image_cursor",
+        skip(self),
+        fields(id)
+    )]
+    async fn update_image_cursor(&self, id: &str, cursor: i64) -> anyhow::Result<()> {
+        sqlx::query("UPDATE devices SET image_cursor = $1 WHERE id = $2")
+            .bind(cursor)
+            .bind(id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "postgres_device_repo.update", skip(self), fields(id))]
+    async fn update(&self, id: &str, patch: &DevicePatch) -> anyhow::Result<()> {
+        let quiet_hours_json = patch
+            .quiet_hours
+            .as_ref()
+            .and_then(Option::as_ref)
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        sqlx::query(
+            r#"
+            UPDATE devices
+            SET
+                desired_refresh_rate = CASE WHEN $1 THEN $2 ELSE desired_refresh_rate END,
+                target_fw_version = CASE WHEN $3 THEN $4 ELSE target_fw_version END,
+                timezone = CASE WHEN $5 THEN $6 ELSE timezone END,
+                quiet_hours_json = CASE WHEN $7 THEN $8 ELSE quiet_hours_json END,
+                group_id = CASE WHEN $9 THEN $10 ELSE group_id END
+            WHERE id = $11
+            "#,
+        )
+        .bind(patch.desired_refresh_rate.is_some())
+        .bind(patch.desired_refresh_rate.flatten())
+        .bind(patch.target_fw_version.is_some())
+        .bind(patch.target_fw_version.clone().flatten())
+        .bind(patch.timezone.is_some())
+        .bind(patch.timezone.clone().flatten())
+        .bind(patch.quiet_hours.is_some())
+        .bind(quiet_hours_json)
+        .bind(patch.group_id.is_some())
+        .bind(patch.group_id.clone().flatten())
+        .bind(id)
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "postgres_device_repo.update_status", skip(self), fields(id))]
+    async fn update_status(
+        &self,
+        id: &str,
+        seen_at: i64,
+        rssi: Option<i32>,
+        battery_voltage: Option<f32>,
+        fw_version: Option<&str>,
+        refresh_rate: Option<i32>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE devices
+            SET
+                rssi = COALESCE($1, rssi),
+                battery_voltage = COALESCE($2, battery_voltage),
+                fw_version = COALESCE($3, fw_version),
+                refresh_rate = COALESCE($4, refresh_rate),
+                first_seen_at = COALESCE(first_seen_at, $5),
+                last_seen_at = $5
+            WHERE id = $6
+            "#,
+        )
+        .bind(rssi.map(i64::from))
+        .bind(battery_voltage.map(f64::from))
+        .bind(fw_version)
+        .bind(refresh_rate.map(i64::from))
+        .bind(seen_at)
+        .bind(id)
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.rotate_api_key",
+        skip(self, api_key),
+        fields(id)
+    )]
+    async fn rotate_api_key(&self, id: &str, api_key: &str) -> anyhow::Result<bool> {
+        let result =
+            sqlx::query("UPDATE devices SET api_key = $1, setup_pending = TRUE WHERE id = $2")
+                .bind(api_key)
+                .bind(id)
+                .execute(&*self.0)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(
+        name = "postgres_device_repo.take_pending_setup",
+        skip(self),
+        fields(mac)
+    )]
+    async fn take_pending_setup(&self, mac: &str) -> anyhow::Result<Option<Device>> {
+        let device = sqlx::query_as::<_, DeviceRow>(concat!(
+            "UPDATE devices SET setup_pending = FALSE WHERE mac = $1 AND setup_pending RETURNING ",
+            device_columns!()
+        ))
+        .bind(mac)
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(device.map(Device::from))
+    }
+
+    #[instrument(name = "postgres_device_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM devices WHERE id = $1")
+            .bind(id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "postgres_device_repo.ping", skip(self))]
+    async fn ping(&self) -> anyhow::Result<()> {
+        sqlx::query("SELECT 1 FROM devices LIMIT 1")
+            .fetch_optional(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "postgres_device_repo.applied_migration", skip(self))]
+    async fn applied_migration(&self) -> anyhow::Result<Option<i64>> {
+        Ok(
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+                .fetch_one(&*self.0)
+                .await?,
+        )
+    }
+
+    fn latest_migration(&self) -> Option<i64> {
+        latest_postgres_migration_version()
+    }
+}
@@ -4,10 +4,68 @@ use async_trait::async_trait;
 use sqlx::SqlitePool;
 use tracing::instrument;
 
-use crate::models::Device;
+use crate::{
+    db::latest_migration_version,
+    models::{ApprovalStatus, Device, DevicePatch},
+    schedule::ScheduleRule,
+};
 
 use super::DeviceRepository;
 
+/// Columns of `devices` every query returning whole devices selects
+struct DeviceRow {
+    id: String,
+    mac: Option<String>,
+    api_key: String,
+    rssi: Option<i64>,
+    battery_voltage: Option<f64>,
+    fw_version: Option<String>,
+    refresh_rate: Option<i64>,
+    images_json: String,
+    image_cursor: i64,
+    desired_refresh_rate: Option<i64>,
+    target_fw_version: Option<String>,
+    approval_status: String,
+    schedule_json: String,
+    timezone: Option<String>,
+    quiet_hours_json: Option<String>,
+    group_id: Option<String>,
+    first_seen_at: Option<i64>,
+    last_seen_at: Option<i64>,
+}
+
+impl From<DeviceRow> for Device {
+    fn from(record: DeviceRow) -> Self {
+        Device {
+            id: record.id,
+            mac: record.mac,
+            _api_key: record.api_key,
+            rssi: record.rssi,
+            battery_voltage: record.battery_voltage,
+            fw_version: record.fw_version,
+            refresh_rate: record.refresh_rate,
+            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
+            image_cursor: record.image_cursor,
+            desired_refresh_rate: record.desired_refresh_rate,
+            target_fw_version: record.target_fw_version,
+            approval_status: record
+                .approval_status
+                .parse()
+                .unwrap_or(ApprovalStatus::Pending),
+            schedule: serde_json::from_str::<Vec<ScheduleRule>>(&record.schedule_json)
+                .unwrap_or_default(),
+            timezone: record.timezone,
+            quiet_hours: record
+                .quiet_hours_json
+                .as_deref()
+                .and_then(|json| serde_json::from_str(json).ok()),
+            group_id: record.group_id,
+            first_seen_at: record.first_seen_at,
+            last_seen_at: record.last_seen_at,
+        }
+    }
+}
+
 pub struct SqliteDeviceRepo(Arc<SqlitePool>);
 
 impl SqliteDeviceRepo {
@@ -19,12 +77,21 @@ impl SqliteDeviceRepo {
 #[async_trait]
 impl DeviceRepository for SqliteDeviceRepo {
     #[instrument(name = "sqlite_device_repo.create", skip(self), fields(id))]
-    async fn create(&self, id: &str, mac: Option<&str>, api_key: &str) -> anyhow::Result<()> {
+    async fn create(
+        &self,
+        id: &str,
+        mac: Option<&str>,
+        api_key: &str,
+        approval_status: ApprovalStatus,
+    ) -> anyhow::Result<()> {
+        let approval_status = approval_status.as_str();
+
         sqlx::query!(
-            "INSERT INTO devices (mac, api_key, id) VALUES (?, ?, ?)",
+            "INSERT INTO devices (mac, api_key, id, approval_status) VALUES (?, ?, ?, ?)",
             mac,
             api_key,
-            id
+            id,
+            approval_status
         )
         .execute(&*self.0)
         .await?;
@@ -45,7 +112,8 @@ impl DeviceRepository for SqliteDeviceRepo {
 
     #[instrument(name = "sqlite_device_repo.get_by_api_key", skip(self))]
     async fn get_by_api_key(&self, api_key: &str) -> anyhow::Result<Option<Device>> {
-        let device = sqlx::query!(
+        let device = sqlx::query_as!(
+            DeviceRow,
             r#"
             SELECT
                 id,
@@ -55,31 +123,32 @@ impl DeviceRepository for SqliteDeviceRepo {
                 battery_voltage,
                 fw_version,
                 refresh_rate,
-                images_json
+                images_json,
+                image_cursor,
+                desired_refresh_rate,
+                target_fw_version,
+                approval_status,
+                schedule_json,
+                timezone,
+                quiet_hours_json,
+                group_id,
+                first_seen_at,
+                last_seen_at
             FROM devices
             WHERE api_key = ?
             "#,
             api_key
         )
         .fetch_optional(&*self.0)
-        .await?
-        .map(|record| Device {
-            id: record.id,
-            mac: record.mac,
-            _api_key: record.api_key,
-            rssi: record.rssi,
-            battery_voltage: record.battery_voltage,
-            fw_version: record.fw_version,
-            refresh_rate: record.refresh_rate,
-            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
-        });
+        .await?;
 
-        Ok(device)
+        Ok(device.map(Device::from))
     }
 
     #[instrument(name = "sqlite_device_repo.get_by_id", skip(self), fields(id))]
     async fn get_by_id(&self, id: &str) -> anyhow::Result<Option<Device>> {
-        let device = sqlx::query!(
+        let device = sqlx::query_as!(
+            DeviceRow,
             r#"
             SELECT
                 id,
@@ -89,67 +158,220 @@ impl DeviceRepository for SqliteDeviceRepo {
                 battery_voltage,
                 fw_version,
                 refresh_rate,
-                images_json
+                images_json,
+                image_cursor,
+                desired_refresh_rate,
+                target_fw_version,
+                approval_status,
+                schedule_json,
+                timezone,
+                quiet_hours_json,
+                group_id,
+                first_seen_at,
+                last_seen_at
             FROM devices
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&*self.0)
-        .await?
-        .map(|record| Device {
-            id: record.id,
-            mac: record.mac,
-            _api_key: record.api_key,
-            rssi: record.rssi,
-            battery_voltage: record.battery_voltage,
-            fw_version: record.fw_version,
-            refresh_rate: record.refresh_rate,
-            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
-        });
+        .await?;
 
-        Ok(device)
+        Ok(device.map(Device::from))
     }
 
     #[instrument(name = "sqlite_device_repo.list", skip(self))]
     async fn list(&self) -> anyhow::Result<Vec<Device>> {
-        Ok(sqlx::query!(
+        let devices = sqlx::query_as!(
+            DeviceRow,
+            r#"
+            SELECT
+                id,
+                mac,
+                api_key,
+                rssi,
+                battery_voltage,
+                fw_version,
+                refresh_rate,
+                images_json,
+                image_cursor,
+                desired_refresh_rate,
+                target_fw_version,
+                approval_status,
+                schedule_json,
+                timezone,
+                quiet_hours_json,
+                group_id,
+                first_seen_at,
+                last_seen_at
+            FROM devices
+            ORDER BY id
+            "#
+        )
+        .fetch_all(&*self.0)
+        .await?;
+
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+
+    #[instrument(
+        name = "sqlite_device_repo.list_by_approval_status",
+        skip(self),
+        fields(status)
+    )]
+    async fn list_by_approval_status(&self, status: ApprovalStatus) -> anyhow::Result<Vec<Device>> {
+        let status = status.as_str();
+
+        let devices = sqlx::query_as!(
+            DeviceRow,
             r#"
-                SELECT
-                    id,
-                    mac,
-                    api_key,
-                    rssi,
-                    battery_voltage,
-                    fw_version,
-                    refresh_rate,
-                    images_json
-                FROM devices
-                ORDER BY id
-                "#
+            SELECT
+                id,
+                mac,
+                api_key,
+                rssi,
+                battery_voltage,
+                fw_version,
+                refresh_rate,
+                images_json,
+                image_cursor,
+                desired_refresh_rate,
+                target_fw_version,
+                approval_status,
+                schedule_json,
+                timezone,
+                quiet_hours_json,
+                group_id,
+                first_seen_at,
+                last_seen_at
+            FROM devices
+            WHERE approval_status = ?
+            ORDER BY id
+            "#,
+            status
         )
         .fetch_all(&*self.0)
-        .await?
-        .iter()
-        .map(|record| Device {
-            id: record.id.clone(),
-            mac: record.mac.clone(),
-            _api_key: record.api_key.clone(),
-            rssi: record.rssi,
-            battery_voltage: record.battery_voltage,
-            fw_version: record.fw_version.clone(),
-            refresh_rate: record.refresh_rate,
-            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
-        })
-        .collect())
+        .await?;
+
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+
+    #[instrument(
+        name = "sqlite_device_repo.list_by_group",
+        skip(self),
+        fields(group_id)
+    )]
+    async fn list_by_group(&self, group_id: &str) -> anyhow::Result<Vec<Device>> {
+        let devices = sqlx::query_as!(
+            DeviceRow,
+            r#"
+            SELECT
+                id,
+                mac,
+                api_key,
+                rssi,
+                battery_voltage,
+                fw_version,
+                refresh_rate,
+                images_json,
+                image_cursor,
+                desired_refresh_rate,
+                target_fw_version,
+                approval_status,
+                schedule_json,
+                timezone,
+                quiet_hours_json,
+                group_id,
+                first_seen_at,
+                last_seen_at
+            FROM devices
+            WHERE group_id = ?
+            ORDER BY id
+            "#,
+            group_id
+        )
+        .fetch_all(&*self.0)
+        .await?;
+
+        Ok(devices.into_iter().map(Device::from).collect())
+    }
+
+    #[instrument(
+        name = "sqlite_device_repo.set_group_members",
+        skip(self, device_ids),
+        fields(group_id)
+    )]
+    async fn set_group_members(
+        &self,
+        group_id: &str,
+        device_ids: &[String],
+    ) -> anyhow::Result<bool> {
+        let ids_json = serde_json::to_string(device_ids)?;
+        let mut tx = self.0.begin().await?;
+
+        // Duplicate IDs in the request only count once
+        let found = sqlx::query_scalar!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM devices WHERE id IN (SELECT value FROM json_each(?1)))
+                = (SELECT COUNT(DISTINCT value) FROM json_each(?1)) AS "all_found!: bool"
+            "#,
+            ids_json
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !found {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE devices
+            SET group_id = CASE WHEN id IN (SELECT value FROM json_each(?2)) THEN ?1 ELSE NULL END
+            WHERE group_id = ?1 OR id IN (SELECT value FROM json_each(?2))
+            "#,
+            group_id,
+            ids_json
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    #[instrument(
+        name = "sqlite_device_repo.update_approval_status",
+        skip(self),
+        fields(id)
+    )]
+    async fn update_approval_status(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+    ) -> anyhow::Result<bool> {
+        let status = status.as_str();
+
+        let result = sqlx::query!(
+            "UPDATE devices SET approval_status = ? WHERE id = ?",
+            status,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
     }
 
     #[instrument(name = "sqlite_device_repo.update_images", skip(self), fields(id))]
     async fn update_images(&self, id: &str, images: &[String]) -> anyhow::Result<()> {
         let json_str = serde_json::to_string(&images).unwrap_or_else(|_| "[]".to_string());
 
+        // Restart the rotation so the new list is shown from the beginning
         sqlx::query!(
-            "UPDATE devices SET images_json = ? WHERE id = ?",
+            "UPDATE devices SET images_json = ?, image_cursor = 0 WHERE id = ?",
             json_str,
             id
         )
@@ -159,10 +381,95 @@ impl DeviceRepository for SqliteDeviceRepo {
         Ok(())
     }
 
+    #[instrument(
+        name = "sqlite_device_repo.update_schedule",
+        skip(self, schedule),
+        fields(id)
+    )]
+    async fn update_schedule(&self, id: &str, schedule: &[ScheduleRule]) -> anyhow::Result<bool> {
+        let schedule_json = serde_json::to_string(schedule)?;
+
+        // The active rotation may change, so start it from the beginning
+        let result = sqlx::query!(
+            "UPDATE devices SET schedule_json = ?, image_cursor = 0 WHERE id = ?",
+            schedule_json,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(
+        name = "sqlite_device_repo.update_image_cursor",
+        skip(self),
+        fields(id)
+    )]
+    async fn update_image_cursor(&self, id: &str, cursor: i64) -> anyhow::Result<()> {
+        sqlx::query!(
+            "UPDATE devices SET image_cursor = ? WHERE id = ?",
+            cursor,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_device_repo.update", skip(self), fields(id))]
+    async fn update(&self, id: &str, patch: &DevicePatch) -> anyhow::Result<()> {
+        let set_desired_refresh_rate = patch.desired_refresh_rate.is_some();
+        let desired_refresh_rate = patch.desired_refresh_rate.flatten();
+        let set_target_fw_version = patch.target_fw_version.is_some();
+        let target_fw_version = patch.target_fw_version.clone().flatten();
+        let set_timezone = patch.timezone.is_some();
+        let timezone = patch.timezone.clone().flatten();
+        let set_quiet_hours = patch.quiet_hours.is_some();
+        let quiet_hours_json = patch
+            .quiet_hours
+            .as_ref()
+            .and_then(Option::as_ref)
+            .map(serde_json::to_string)
+            .transpose()?;
+        let set_group_id = patch.group_id.is_some();
+        let group_id = patch.group_id.clone().flatten();
+
+        sqlx::query!(
+            r#"
+            UPDATE devices
+            SET
+                desired_refresh_rate = CASE WHEN ? THEN ? ELSE desired_refresh_rate END,
+                target_fw_version = CASE WHEN ? THEN ? ELSE target_fw_version END,
+                timezone = CASE WHEN ? THEN ? ELSE timezone END,
+                quiet_hours_json = CASE WHEN ? THEN ? ELSE quiet_hours_json END,
+                group_id = CASE WHEN ? THEN ? ELSE group_id END
+            WHERE id = ?
+            "#,
+            set_desired_refresh_rate,
+            desired_refresh_rate,
+            set_target_fw_version,
+            target_fw_version,
+            set_timezone,
+            timezone,
+            set_quiet_hours,
+            quiet_hours_json,
+            set_group_id,
+            group_id,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
     #[instrument(name = "sqlite_device_repo.update_status", skip(self), fields(id))]
     async fn update_status(
         &self,
         id: &str,
+        seen_at: i64,
         rssi: Option<i32>,
         battery_voltage: Option<f32>,
         fw_version: Option<&str>,
@@ -175,13 +482,17 @@ impl DeviceRepository for SqliteDeviceRepo {
                 rssi = COALESCE(?, rssi),
                 battery_voltage = COALESCE(?, battery_voltage),
                 fw_version = COALESCE(?, fw_version),
-                refresh_rate = COALESCE(?, refresh_rate)
+                refresh_rate = COALESCE(?, refresh_rate),
+                first_seen_at = COALESCE(first_seen_at, ?),
+                last_seen_at = ?
             WHERE id = ?
             "#,
             rssi,
             battery_voltage,
             fw_version,
             refresh_rate,
+            seen_at,
+            seen_at,
             id
         )
         .execute(&*self.0)
@@ -189,4 +500,71 @@ impl DeviceRepository for SqliteDeviceRepo {
 
         Ok(())
     }
+
+    #[instrument(
+        name = "sqlite_device_repo.rotate_api_key",
+        skip(self, api_key),
+        fields(id)
+    )]
+    async fn rotate_api_key(&self, id: &str, api_key: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE devices SET api_key = ?, setup_pending = TRUE WHERE id = ?",
+            api_key,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(
+        name = "sqlite_device_repo.take_pending_setup",
+        skip(self),
+        fields(mac)
+    )]
+    async fn take_pending_setup(&self, mac: &str) -> anyhow::Result<Option<Device>> {
+        let id = sqlx::query_scalar!(
+            "UPDATE devices SET setup_pending = FALSE WHERE mac = ? AND setup_pending RETURNING id",
+            mac
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        match id {
+            Some(id) => self.get_by_id(&id).await,
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(name = "sqlite_device_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!("DELETE FROM devices WHERE id = ?", id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "sqlite_device_repo.ping", skip(self))]
+    async fn ping(&self) -> anyhow::Result<()> {
+        sqlx::query!("SELECT 1 AS ok FROM devices LIMIT 1")
+            .fetch_optional(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_device_repo.applied_migration", skip(self))]
+    async fn applied_migration(&self) -> anyhow::Result<Option<i64>> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT MAX(version) AS "version: i64" FROM _sqlx_migrations WHERE success"#
+        )
+        .fetch_one(&*self.0)
+        .await?)
+    }
+
+    fn latest_migration(&self) -> Option<i64> {
+        latest_migration_version()
+    }
 }
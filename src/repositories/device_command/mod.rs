@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::{DeviceCommand, QueuedDeviceCommand};
+
+pub mod sqlite;
+pub use sqlite::SqliteDeviceCommandRepo;
+
+#[async_trait]
+#[automock]
+pub trait DeviceCommandRepository: Send + Sync {
+    /// Add a command to the end of the device's queue
+    async fn enqueue(
+        &self,
+        device_id: &str,
+        command: &DeviceCommand,
+    ) -> anyhow::Result<QueuedDeviceCommand>;
+
+    /// Mark the oldest undelivered command as delivered and return it
+    async fn take_next(&self, device_id: &str) -> anyhow::Result<Option<QueuedDeviceCommand>>;
+
+    /// List all commands of a device, newest first
+    async fn list(&self, device_id: &str) -> anyhow::Result<Vec<QueuedDeviceCommand>>;
+
+    /// Delete all commands of a device, delivered or not
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()>;
+}
+
+pub type DeviceCommandRepo = std::sync::Arc<dyn DeviceCommandRepository + Send + Sync>;
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::{DeviceCommand, QueuedDeviceCommand};
+
+use super::DeviceCommandRepository;
+
+pub struct SqliteDeviceCommandRepo(Arc<SqlitePool>);
+
+impl SqliteDeviceCommandRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl DeviceCommandRepository for SqliteDeviceCommandRepo {
+    #[instrument(
+        name = "sqlite_device_command_repo.enqueue",
+        skip(self, command),
+        fields(device_id)
+    )]
+    async fn enqueue(
+        &self,
+        device_id: &str,
+        command: &DeviceCommand,
+    ) -> anyhow::Result<QueuedDeviceCommand> {
+        let command_json = serde_json::to_string(command)?;
+
+        let record = sqlx::query!(
+            r#"
+            INSERT INTO device_commands (device_id, command_json)
+            VALUES (?, ?)
+            RETURNING id, created_at
+            "#,
+            device_id,
+            command_json
+        )
+        .fetch_one(&*self.0)
+        .await?;
+
+        Ok(QueuedDeviceCommand {
+            id: record.id,
+            device_id: device_id.to_string(),
+            command: command.clone(),
+            created_at: record.created_at,
+            delivered_at: None,
+        })
+    }
+
+    #[instrument(
+        name = "sqlite_device_command_repo.take_next",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn take_next(&self, device_id: &str) -> anyhow::Result<Option<QueuedDeviceCommand>> {
+        // A single statement so concurrent polls never deliver the same command twice
+        let record = sqlx::query!(
+            r#"
+            UPDATE device_commands
+            SET delivered_at = unixepoch()
+            WHERE id = (
+                SELECT id
+                FROM device_commands
+                WHERE device_id = ? AND delivered_at IS NULL
+                ORDER BY id
+                LIMIT 1
+            )
+            RETURNING id, device_id, command_json, created_at, delivered_at
+            "#,
+            device_id
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        record
+            .map(|record| {
+                Ok(QueuedDeviceCommand {
+                    id: record.id,
+                    device_id: record.device_id,
+                    command: serde_json::from_str(&record.command_json)?,
+                    created_at: record.created_at,
+                    delivered_at: Some(record.delivered_at),
+                })
+            })
+            .transpose()
+    }
+
+    #[instrument(
+        name = "sqlite_device_command_repo.list",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn list(&self, device_id: &str) -> anyhow::Result<Vec<QueuedDeviceCommand>> {
+        sqlx::query!(
+            r#"
+            SELECT id, device_id, command_json, created_at, delivered_at
+            FROM device_commands
+            WHERE device_id = ?
+            ORDER BY id DESC
+            "#,
+            device_id
+        )
+        .fetch_all(&*self.0)
+        .await?
+        .into_iter()
+        .map(|record| {
+            Ok(QueuedDeviceCommand {
+                id: record.id,
+                device_id: record.device_id,
+                command: serde_json::from_str(&record.command_json)?,
+                created_at: record.created_at,
+                delivered_at: record.delivered_at,
+            })
+        })
+        .collect()
+    }
+
+    #[instrument(
+        name = "sqlite_device_command_repo.delete_by_device",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()> {
+        sqlx::query!("DELETE FROM device_commands WHERE device_id = ?", device_id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::{DeviceGroup, DeviceGroupPatch};
+
+pub mod sqlite;
+pub use sqlite::SqliteDeviceGroupRepo;
+
+#[async_trait]
+#[automock]
+pub trait DeviceGroupRepository: Send + Sync {
+    /// Create a new group without any settings
+    async fn create(&self, id: &str, name: &str) -> anyhow::Result<()>;
+
+    /// Get a group by its id
+    async fn get(&self, id: &str) -> anyhow::Result<Option<DeviceGroup>>;
+
+    /// List all groups
+    async fn list(&self) -> anyhow::Result<Vec<DeviceGroup>>;
+
+    /// Update the group settings, returning whether it existed
+    async fn update(&self, id: &str, patch: &DeviceGroupPatch) -> anyhow::Result<bool>;
+
+    /// Replace the rotation of a group, returning whether it existed
+    async fn update_images(&self, id: &str, images: &[String]) -> anyhow::Result<bool>;
+
+    /// Delete a group, leaving its devices without a group, returning whether it existed
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
+}
+
+pub type DeviceGroupRepo = std::sync::Arc<dyn DeviceGroupRepository + Send + Sync>;
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::{DeviceGroup, DeviceGroupPatch};
+
+use super::DeviceGroupRepository;
+
+pub struct SqliteDeviceGroupRepo(Arc<SqlitePool>);
+
+impl SqliteDeviceGroupRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl DeviceGroupRepository for SqliteDeviceGroupRepo {
+    #[instrument(name = "sqlite_device_group_repo.create", skip(self), fields(id))]
+    async fn create(&self, id: &str, name: &str) -> anyhow::Result<()> {
+        sqlx::query!(
+            "INSERT INTO device_groups (id, name) VALUES (?, ?)",
+            id,
+            name
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_device_group_repo.get", skip(self), fields(id))]
+    async fn get(&self, id: &str) -> anyhow::Result<Option<DeviceGroup>> {
+        let record = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                name,
+                images_json,
+                desired_refresh_rate,
+                target_fw_version,
+                created_at
+            FROM device_groups
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(record.map(|record| DeviceGroup {
+            id: record.id,
+            name: record.name,
+            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
+            desired_refresh_rate: record.desired_refresh_rate,
+            target_fw_version: record.target_fw_version,
+            created_at: record.created_at,
+        }))
+    }
+
+    #[instrument(name = "sqlite_device_group_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<DeviceGroup>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                id,
+                name,
+                images_json,
+                desired_refresh_rate,
+                target_fw_version,
+                created_at
+            FROM device_groups
+            ORDER BY name, id
+            "#
+        )
+        .fetch_all(&*self.0)
+        .await?
+        .into_iter()
+        .map(|record| DeviceGroup {
+            id: record.id,
+            name: record.name,
+            images: serde_json::from_str::<Vec<String>>(&record.images_json).unwrap_or_default(),
+            desired_refresh_rate: record.desired_refresh_rate,
+            target_fw_version: record.target_fw_version,
+            created_at: record.created_at,
+        })
+        .collect())
+    }
+
+    #[instrument(
+        name = "sqlite_device_group_repo.update",
+        skip(self, patch),
+        fields(id)
+    )]
+    async fn update(&self, id: &str, patch: &DeviceGroupPatch) -> anyhow::Result<bool> {
+        let name = patch.name.as_deref();
+        let set_desired_refresh_rate = patch.desired_refresh_rate.is_some();
+        let desired_refresh_rate = patch.desired_refresh_rate.flatten();
+        let set_target_fw_version = patch.target_fw_version.is_some();
+        let target_fw_version = patch.target_fw_version.clone().flatten();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE device_groups
+            SET
+                name = COALESCE(?, name),
+                desired_refresh_rate = CASE WHEN ? THEN ? ELSE desired_refresh_rate END,
+                target_fw_version = CASE WHEN ? THEN ? ELSE target_fw_version END
+            WHERE id = ?
+            "#,
+            name,
+            set_desired_refresh_rate,
+            desired_refresh_rate,
+            set_target_fw_version,
+            target_fw_version,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(
+        name = "sqlite_device_group_repo.update_images",
+        skip(self, images),
+        fields(id)
+    )]
+    async fn update_images(&self, id: &str, images: &[String]) -> anyhow::Result<bool> {
+        let images_json = serde_json::to_string(images)?;
+
+        let result = sqlx::query!(
+            "UPDATE device_groups SET images_json = ? WHERE id = ?",
+            images_json,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "sqlite_device_group_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!("DELETE FROM device_groups WHERE id = ?", id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::{DeviceLog, DeviceLogQuery, NewDeviceLog};
+
+pub mod sqlite;
+pub use sqlite::SqliteDeviceLogRepo;
+
+#[async_trait]
+#[automock]
+pub trait DeviceLogRepository: Send + Sync {
+    /// Store log entries received from a device
+    async fn create(
+        &self,
+        device_id: &str,
+        received_at: i64,
+        logs: &[NewDeviceLog],
+    ) -> anyhow::Result<()>;
+
+    /// List log entries for a device, newest first
+    async fn list(&self, device_id: &str, query: &DeviceLogQuery)
+    -> anyhow::Result<Vec<DeviceLog>>;
+
+    /// Delete all log entries of a device
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()>;
+}
+
+pub type DeviceLogRepo = std::sync::Arc<dyn DeviceLogRepository + Send + Sync>;
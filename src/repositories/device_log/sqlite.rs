@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::{DeviceLog, DeviceLogQuery, NewDeviceLog};
+
+use super::DeviceLogRepository;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 1000;
+
+pub struct SqliteDeviceLogRepo(Arc<SqlitePool>);
+
+impl SqliteDeviceLogRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl DeviceLogRepository for SqliteDeviceLogRepo {
+    #[instrument(name = "sqlite_device_log_repo.create", skip(self, logs), fields(device_id, count = logs.len()))]
+    async fn create(
+        &self,
+        device_id: &str,
+        received_at: i64,
+        logs: &[NewDeviceLog],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.0.begin().await?;
+
+        for log in logs {
+            sqlx::query!(
+                r#"
+                INSERT INTO device_logs (
+                    device_id,
+                    created_at,
+                    received_at,
+                    level,
+                    message,
+                    source_file,
+                    source_line,
+                    wake_reason,
+                    battery_voltage,
+                    wifi_status,
+                    wifi_rssi,
+                    fw_version,
+                    additional_info
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                device_id,
+                log.created_at,
+                received_at,
+                log.level,
+                log.message,
+                log.source_file,
+                log.source_line,
+                log.wake_reason,
+                log.battery_voltage,
+                log.wifi_status,
+                log.wifi_rssi,
+                log.fw_version,
+                log.additional_info
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_device_log_repo.list", skip(self), fields(device_id))]
+    async fn list(
+        &self,
+        device_id: &str,
+        query: &DeviceLogQuery,
+    ) -> anyhow::Result<Vec<DeviceLog>> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        // Levels are stored lowercased, and a search matches `%` or `_` literally
+        let level = query.level.as_deref().map(str::to_lowercase);
+        let q = query.q.as_deref().map(escape_like);
+
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                id,
+                device_id,
+                created_at,
+                received_at,
+                level,
+                message,
+                source_file,
+                source_line,
+                wake_reason,
+                battery_voltage,
+                wifi_status,
+                wifi_rssi,
+                fw_version,
+                additional_info
+            FROM device_logs
+            WHERE device_id = ?1
+                AND (?2 IS NULL OR created_at >= ?2)
+                AND (?3 IS NULL OR created_at <= ?3)
+                AND (?4 IS NULL OR level = ?4)
+                AND (?5 IS NULL OR message LIKE '%' || ?5 || '%' ESCAPE '\')
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?6
+            "#,
+            device_id,
+            query.from,
+            query.to,
+            level,
+            q,
+            limit
+        )
+        .fetch_all(&*self.0)
+        .await?
+        .into_iter()
+        .map(|record| DeviceLog {
+            id: record.id,
+            device_id: record.device_id,
+            created_at: record.created_at,
+            received_at: record.received_at,
+            level: record.level,
+            message: record.message,
+            source_file: record.source_file,
+            source_line: record.source_line,
+            wake_reason: record.wake_reason,
+            battery_voltage: record.battery_voltage,
+            wifi_status: record.wifi_status,
+            wifi_rssi: record.wifi_rssi,
+            fw_version: record.fw_version,
+            additional_info: record
+                .additional_info
+                .and_then(|info| serde_json::from_str(&info).ok()),
+        })
+        .collect())
+    }
+
+    #[instrument(
+        name = "sqlite_device_log_repo.delete_by_device",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()> {
+        sqlx::query!("DELETE FROM device_logs WHERE device_id = ?", device_id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
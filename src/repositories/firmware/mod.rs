@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::Firmware;
+
+pub mod sqlite;
+pub use sqlite::SqliteFirmwareRepo;
+
+#[async_trait]
+#[automock]
+pub trait FirmwareRepository: Send + Sync {
+    /// Store a new firmware release
+    async fn create(&self, version: &str, checksum: &str, data: &[u8]) -> anyhow::Result<()>;
+
+    /// Get a firmware release by its version
+    async fn get(&self, version: &str) -> anyhow::Result<Option<Firmware>>;
+
+    /// Get the binary of a firmware release by its version
+    async fn get_data(&self, version: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// List all firmware releases
+    async fn list(&self) -> anyhow::Result<Vec<Firmware>>;
+
+    /// Delete a firmware release, returning whether it existed
+    async fn delete(&self, version: &str) -> anyhow::Result<bool>;
+}
+
+pub type FirmwareRepo = std::sync::Arc<dyn FirmwareRepository + Send + Sync>;
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::{models::Firmware, utils::compare_versions};
+
+use super::FirmwareRepository;
+
+pub struct SqliteFirmwareRepo(Arc<SqlitePool>);
+
+impl SqliteFirmwareRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl FirmwareRepository for SqliteFirmwareRepo {
+    #[instrument(
+        name = "sqlite_firmware_repo.create",
+        skip(self, data),
+        fields(version)
+    )]
+    async fn create(&self, version: &str, checksum: &str, data: &[u8]) -> anyhow::Result<()> {
+        let size = data.len() as i64;
+
+        sqlx::query!(
+            "INSERT INTO firmware (version, checksum, size, data) VALUES (?, ?, ?, ?)",
+            version,
+            checksum,
+            size,
+            data
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_firmware_repo.get", skip(self), fields(version))]
+    async fn get(&self, version: &str) -> anyhow::Result<Option<Firmware>> {
+        let firmware = sqlx::query_as!(
+            Firmware,
+            r#"
+            SELECT
+                version,
+                checksum,
+                size,
+                created_at
+            FROM firmware
+            WHERE version = ?
+            "#,
+            version
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(firmware)
+    }
+
+    #[instrument(name = "sqlite_firmware_repo.get_data", skip(self), fields(version))]
+    async fn get_data(&self, version: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let data = sqlx::query_scalar!("SELECT data FROM firmware WHERE version = ?", version)
+            .fetch_optional(&*self.0)
+            .await?;
+
+        Ok(data)
+    }
+
+    #[instrument(name = "sqlite_firmware_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<Firmware>> {
+        let mut firmware = sqlx::query_as!(
+            Firmware,
+            r#"
+                SELECT
+                    version,
+                    checksum,
+                    size,
+                    created_at
+                FROM firmware
+                "#
+        )
+        .fetch_all(&*self.0)
+        .await?;
+
+        // Versions compare as semver, text order would put 1.9.0 above 1.10.0
+        firmware.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| compare_versions(&b.version, &a.version))
+        });
+
+        Ok(firmware)
+    }
+
+    #[instrument(name = "sqlite_firmware_repo.delete", skip(self), fields(version))]
+    async fn delete(&self, version: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!("DELETE FROM firmware WHERE version = ?", version)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
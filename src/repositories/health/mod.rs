@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+pub mod sqlite;
+pub use sqlite::SqliteHealthRepo;
+
+#[async_trait]
+#[automock]
+pub trait HealthRepository: Send + Sync {
+    /// Run a trivial query to check the database answers
+    async fn ping(&self) -> anyhow::Result<()>;
+
+    /// Version of the latest migration that was applied successfully
+    async fn applied_migration(&self) -> anyhow::Result<Option<i64>>;
+}
+
+pub type HealthRepo = std::sync::Arc<dyn HealthRepository + Send + Sync>;
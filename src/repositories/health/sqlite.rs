@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use super::HealthRepository;
+
+pub struct SqliteHealthRepo(Arc<SqlitePool>);
+
+impl SqliteHealthRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl HealthRepository for SqliteHealthRepo {
+    #[instrument(name = "sqlite_health_repo.ping", skip(self))]
+    async fn ping(&self) -> anyhow::Result<()> {
+        sqlx::query!("SELECT 1 AS ok").fetch_one(&*self.0).await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_health_repo.applied_migration", skip(self))]
+    async fn applied_migration(&self) -> anyhow::Result<Option<i64>> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT MAX(version) AS "version: i64" FROM _sqlx_migrations WHERE success"#
+        )
+        .fetch_one(&*self.0)
+        .await?)
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::{Image, ImageContent};
+
+pub mod sqlite;
+pub use sqlite::SqliteImageRepo;
+
+#[async_trait]
+#[automock]
+pub trait ImageRepository: Send + Sync {
+    /// Store an image under its content hash, uploading the same content twice is a no-op
+    async fn create(&self, id: &str, content_type: &str, data: &[u8]) -> anyhow::Result<()>;
+
+    /// Get an image by its content hash
+    async fn get(&self, id: &str) -> anyhow::Result<Option<Image>>;
+
+    /// Get the content of an image by its content hash
+    async fn get_content(&self, id: &str) -> anyhow::Result<Option<ImageContent>>;
+
+    /// List all images
+    async fn list(&self) -> anyhow::Result<Vec<Image>>;
+
+    /// Delete an image, returning whether it existed
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
+}
+
+pub type ImageRepo = std::sync::Arc<dyn ImageRepository + Send + Sync>;
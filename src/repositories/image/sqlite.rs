@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::{Image, ImageContent};
+
+use super::ImageRepository;
+
+pub struct SqliteImageRepo(Arc<SqlitePool>);
+
+impl SqliteImageRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl ImageRepository for SqliteImageRepo {
+    #[instrument(name = "sqlite_image_repo.create", skip(self, data), fields(id))]
+    async fn create(&self, id: &str, content_type: &str, data: &[u8]) -> anyhow::Result<()> {
+        let size = data.len() as i64;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO images (id, content_type, size, data)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+            id,
+            content_type,
+            size,
+            data
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_image_repo.get", skip(self), fields(id))]
+    async fn get(&self, id: &str) -> anyhow::Result<Option<Image>> {
+        let image = sqlx::query_as!(
+            Image,
+            r#"
+            SELECT
+                id,
+                content_type,
+                size,
+                created_at
+            FROM images
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(image)
+    }
+
+    #[instrument(name = "sqlite_image_repo.get_content", skip(self), fields(id))]
+    async fn get_content(&self, id: &str) -> anyhow::Result<Option<ImageContent>> {
+        let content = sqlx::query_as!(
+            ImageContent,
+            "SELECT content_type, data FROM images WHERE id = ?",
+            id
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        Ok(content)
+    }
+
+    #[instrument(name = "sqlite_image_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<Image>> {
+        Ok(sqlx::query_as!(
+            Image,
+            r#"
+                SELECT
+                    id,
+                    content_type,
+                    size,
+                    created_at
+                FROM images
+                ORDER BY created_at DESC, id
+                "#
+        )
+        .fetch_all(&*self.0)
+        .await?)
+    }
+
+    #[instrument(name = "sqlite_image_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!("DELETE FROM images WHERE id = ?", id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::{layout::LayoutSpec, models::Layout};
+
+pub mod sqlite;
+pub use sqlite::SqliteLayoutRepo;
+
+#[async_trait]
+#[automock]
+pub trait LayoutRepository: Send + Sync {
+    /// Store a new layout
+    async fn create(&self, id: &str, name: &str, spec: &LayoutSpec) -> anyhow::Result<()>;
+
+    /// Get a layout by its id
+    async fn get(&self, id: &str) -> anyhow::Result<Option<Layout>>;
+
+    /// List all layouts
+    async fn list(&self) -> anyhow::Result<Vec<Layout>>;
+
+    /// Replace the name and spec of a layout, returning whether it existed
+    async fn update(&self, id: &str, name: &str, spec: &LayoutSpec) -> anyhow::Result<bool>;
+
+    /// Delete a layout, returning whether it existed
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
+}
+
+pub type LayoutRepo = std::sync::Arc<dyn LayoutRepository + Send + Sync>;
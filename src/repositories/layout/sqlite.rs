@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::{layout::LayoutSpec, models::Layout};
+
+use super::LayoutRepository;
+
+pub struct SqliteLayoutRepo(Arc<SqlitePool>);
+
+impl SqliteLayoutRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+#[async_trait]
+impl LayoutRepository for SqliteLayoutRepo {
+    #[instrument(name = "sqlite_layout_repo.create", skip(self, spec), fields(id))]
+    async fn create(&self, id: &str, name: &str, spec: &LayoutSpec) -> anyhow::Result<()> {
+        let spec_json = serde_json::to_string(spec)?;
+
+        sqlx::query!(
+            "INSERT INTO layouts (id, name, spec_json) VALUES (?, ?, ?)",
+            id,
+            name,
+            spec_json
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_layout_repo.get", skip(self), fields(id))]
+    async fn get(&self, id: &str) -> anyhow::Result<Option<Layout>> {
+        let record = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                name,
+                spec_json,
+                created_at,
+                updated_at
+            FROM layouts
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&*self.0)
+        .await?;
+
+        record
+            .map(|record| {
+                Ok(Layout {
+                    id: record.id,
+                    name: record.name,
+                    spec: serde_json::from_str(&record.spec_json)?,
+                    created_at: record.created_at,
+                    updated_at: record.updated_at,
+                })
+            })
+            .transpose()
+    }
+
+    #[instrument(name = "sqlite_layout_repo.list", skip(self))]
+    async fn list(&self) -> anyhow::Result<Vec<Layout>> {
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                name,
+                spec_json,
+                created_at,
+                updated_at
+            FROM layouts
+            ORDER BY name, id
+            "#
+        )
+        .fetch_all(&*self.0)
+        .await?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                Ok(Layout {
+                    id: record.id,
+                    name: record.name,
+                    spec: serde_json::from_str(&record.spec_json)?,
+                    created_at: record.created_at,
+                    updated_at: record.updated_at,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(name = "sqlite_layout_repo.update", skip(self, spec), fields(id))]
+    async fn update(&self, id: &str, name: &str, spec: &LayoutSpec) -> anyhow::Result<bool> {
+        let spec_json = serde_json::to_string(spec)?;
+
+        let result = sqlx::query!(
+            "UPDATE layouts SET name = ?, spec_json = ?, updated_at = unixepoch() WHERE id = ?",
+            name,
+            spec_json,
+            id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "sqlite_layout_repo.delete", skip(self), fields(id))]
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query!("DELETE FROM layouts WHERE id = ?", id)
+            .execute(&*self.0)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
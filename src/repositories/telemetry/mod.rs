@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::models::{TelemetryBucket, TelemetrySample};
+
+pub mod sqlite;
+pub use sqlite::SqliteTelemetryRepo;
+
+#[async_trait]
+#[automock]
+pub trait TelemetryRepository: Send + Sync {
+    /// Append a reading to the device history
+    async fn record(&self, device_id: &str, sample: &TelemetrySample) -> anyhow::Result<()>;
+
+    /// List raw readings between `from` and `to`, oldest first
+    async fn list(
+        &self,
+        device_id: &str,
+        from: i64,
+        to: i64,
+    ) -> anyhow::Result<Vec<TelemetrySample>>;
+
+    /// Downsample readings between `from` and `to` into buckets of `bucket` seconds
+    async fn aggregate(
+        &self,
+        device_id: &str,
+        from: i64,
+        to: i64,
+        bucket: i64,
+    ) -> anyhow::Result<Vec<TelemetryBucket>>;
+
+    /// Delete the reading history of a device
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()>;
+}
+
+pub type TelemetryRepo = std::sync::Arc<dyn TelemetryRepository + Send + Sync>;
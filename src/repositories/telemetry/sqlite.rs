@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+use crate::models::{TelemetryBucket, TelemetrySample, TelemetryStats};
+
+use super::TelemetryRepository;
+
+pub struct SqliteTelemetryRepo(Arc<SqlitePool>);
+
+impl SqliteTelemetryRepo {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self(pool)
+    }
+}
+
+fn stats(min: Option<f64>, avg: Option<f64>, max: Option<f64>) -> Option<TelemetryStats> {
+    Some(TelemetryStats {
+        min: min?,
+        avg: avg?,
+        max: max?,
+    })
+}
+
+#[async_trait]
+impl TelemetryRepository for SqliteTelemetryRepo {
+    #[instrument(
+        name = "sqlite_telemetry_repo.record",
+        skip(self, sample),
+        fields(device_id)
+    )]
+    async fn record(&self, device_id: &str, sample: &TelemetrySample) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO device_telemetry (
+                device_id,
+                recorded_at,
+                rssi,
+                battery_voltage,
+                fw_version,
+                refresh_rate
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            device_id,
+            sample.recorded_at,
+            sample.rssi,
+            sample.battery_voltage,
+            sample.fw_version,
+            sample.refresh_rate
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "sqlite_telemetry_repo.list", skip(self), fields(device_id))]
+    async fn list(
+        &self,
+        device_id: &str,
+        from: i64,
+        to: i64,
+    ) -> anyhow::Result<Vec<TelemetrySample>> {
+        Ok(sqlx::query_as!(
+            TelemetrySample,
+            r#"
+            SELECT
+                recorded_at,
+                rssi,
+                battery_voltage,
+                fw_version,
+                refresh_rate
+            FROM device_telemetry
+            WHERE device_id = ? AND recorded_at >= ? AND recorded_at <= ?
+            ORDER BY recorded_at, id
+            "#,
+            device_id,
+            from,
+            to
+        )
+        .fetch_all(&*self.0)
+        .await?)
+    }
+
+    #[instrument(
+        name = "sqlite_telemetry_repo.aggregate",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn aggregate(
+        &self,
+        device_id: &str,
+        from: i64,
+        to: i64,
+        bucket: i64,
+    ) -> anyhow::Result<Vec<TelemetryBucket>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                (recorded_at / ?4) * ?4 AS "start!: i64",
+                COUNT(*) AS "samples!: i64",
+                CAST(MIN(rssi) AS REAL) AS "rssi_min: f64",
+                AVG(rssi) AS "rssi_avg: f64",
+                CAST(MAX(rssi) AS REAL) AS "rssi_max: f64",
+                MIN(battery_voltage) AS "battery_voltage_min: f64",
+                AVG(battery_voltage) AS "battery_voltage_avg: f64",
+                MAX(battery_voltage) AS "battery_voltage_max: f64"
+            FROM device_telemetry
+            WHERE device_id = ?1 AND recorded_at >= ?2 AND recorded_at <= ?3
+            GROUP BY recorded_at / ?4
+            ORDER BY 1
+            "#,
+            device_id,
+            from,
+            to,
+            bucket
+        )
+        .fetch_all(&*self.0)
+        .await?
+        .into_iter()
+        .map(|record| TelemetryBucket {
+            start: record.start,
+            samples: record.samples,
+            rssi: stats(record.rssi_min, record.rssi_avg, record.rssi_max),
+            battery_voltage: stats(
+                record.battery_voltage_min,
+                record.battery_voltage_avg,
+                record.battery_voltage_max,
+            ),
+        })
+        .collect())
+    }
+
+    #[instrument(
+        name = "sqlite_telemetry_repo.delete_by_device",
+        skip(self),
+        fields(device_id)
+    )]
+    async fn delete_by_device(&self, device_id: &str) -> anyhow::Result<()> {
+        sqlx::query!(
+            "DELETE FROM device_telemetry WHERE device_id = ?",
+            device_id
+        )
+        .execute(&*self.0)
+        .await?;
+
+        Ok(())
+    }
+}
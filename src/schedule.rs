@@ -0,0 +1,213 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+/// Upper bound on the rules of a single device schedule
+pub const MAX_SCHEDULE_RULES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl Weekday {
+    /// The day before, used for windows that run past midnight
+    pub fn pred(self) -> Self {
+        match self {
+            Weekday::Mon => Weekday::Sun,
+            Weekday::Tue => Weekday::Mon,
+            Weekday::Wed => Weekday::Tue,
+            Weekday::Thu => Weekday::Wed,
+            Weekday::Fri => Weekday::Thu,
+            Weekday::Sat => Weekday::Fri,
+            Weekday::Sun => Weekday::Sat,
+        }
+    }
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => Weekday::Mon,
+            chrono::Weekday::Tue => Weekday::Tue,
+            chrono::Weekday::Wed => Weekday::Wed,
+            chrono::Weekday::Thu => Weekday::Thu,
+            chrono::Weekday::Fri => Weekday::Fri,
+            chrono::Weekday::Sat => Weekday::Sat,
+            chrono::Weekday::Sun => Weekday::Sun,
+        }
+    }
+}
+
+/// Wall clock time with minute precision, written as `HH:MM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeOfDay(u16);
+
+impl TimeOfDay {
+    pub fn new(hour: u16, minute: u16) -> Option<Self> {
+        (hour < 24 && minute < 60).then_some(TimeOfDay(hour * 60 + minute))
+    }
+
+    /// Minutes since midnight
+    pub fn minutes(self) -> u16 {
+        self.0
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const INVALID: &str = "Time must be written as HH:MM";
+
+        let (hour, minute) = s.split_once(':').ok_or(INVALID)?;
+
+        if hour.len() != 2 || minute.len() != 2 {
+            return Err(INVALID);
+        }
+
+        TimeOfDay::new(
+            hour.parse().map_err(|_| INVALID)?,
+            minute.parse().map_err(|_| INVALID)?,
+        )
+        .ok_or(INVALID)
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
+impl Serialize for TimeOfDay {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeOfDay {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Rotation shown while the local time is within `start` and `end` on one of `weekdays`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleRule {
+    /// Days the window starts on, every day when empty
+    #[serde(default)]
+    pub weekdays: Vec<Weekday>,
+    pub start: TimeOfDay,
+    /// A window ending before it starts runs past midnight, one ending when it starts lasts all day
+    pub end: TimeOfDay,
+    pub images: Vec<String>,
+}
+
+impl ScheduleRule {
+    fn runs_on(&self, weekday: Weekday) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&weekday)
+    }
+
+    pub fn is_active(&self, weekday: Weekday, time: TimeOfDay) -> bool {
+        if self.start == self.end {
+            self.runs_on(weekday)
+        } else if self.start < self.end {
+            self.runs_on(weekday) && self.start <= time && time < self.end
+        } else {
+            // After midnight the window belongs to the day it started on
+            (self.start <= time && self.runs_on(weekday))
+                || (time < self.end && self.runs_on(weekday.pred()))
+        }
+    }
+}
+
+pub fn validate_schedule(rules: &[ScheduleRule]) -> Result<(), &'static str> {
+    if rules.len() > MAX_SCHEDULE_RULES {
+        return Err("Schedules can have at most 64 rules");
+    }
+
+    if rules.iter().any(|rule| rule.images.is_empty()) {
+        return Err("Schedule rules need at least one image");
+    }
+
+    Ok(())
+}
+
+/// Parses an IANA timezone name such as `Europe/Amsterdam`
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
+
+/// Weekday and wall clock time of `now` in `timezone`
+pub fn local_time(now: DateTime<Utc>, timezone: Tz) -> (Weekday, TimeOfDay) {
+    let local = now.with_timezone(&timezone);
+
+    (
+        local.weekday().into(),
+        TimeOfDay(local.hour() as u16 * 60 + local.minute() as u16),
+    )
+}
+
+/// Rotation of the first rule active at `now`, `None` when no rule applies
+pub fn active_rotation(
+    rules: &[ScheduleRule],
+    timezone: Tz,
+    now: DateTime<Utc>,
+) -> Option<&[String]> {
+    let (weekday, time) = local_time(now, timezone);
+
+    rules
+        .iter()
+        .find(|rule| rule.is_active(weekday, time))
+        .map(|rule| rule.images.as_slice())
+}
+
+/// Window during which a device sleeps instead of polling, evaluated in the device timezone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: TimeOfDay,
+    /// A window ending before it starts runs past midnight
+    pub end: TimeOfDay,
+    /// Show the sleeping screen instead of the rotation while the window lasts
+    #[serde(default)]
+    pub sleep_screen: bool,
+}
+
+impl QuietHours {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.start == self.end {
+            return Err("Quiet hours must start and end at different times");
+        }
+
+        Ok(())
+    }
+
+    /// Seconds until the window ends, `None` when `now` falls outside of it
+    pub fn remaining(&self, timezone: Tz, now: DateTime<Utc>) -> Option<i64> {
+        const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+        let seconds = i64::from(now.with_timezone(&timezone).num_seconds_from_midnight());
+        let start = i64::from(self.start.minutes()) * 60;
+        let end = i64::from(self.end.minutes()) * 60;
+
+        let within = if start < end {
+            start <= seconds && seconds < end
+        } else {
+            start <= seconds || seconds < end
+        };
+
+        within.then(|| (end - seconds).rem_euclid(SECONDS_PER_DAY))
+    }
+}
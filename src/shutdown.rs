@@ -0,0 +1,34 @@
+use tracing::{error, info};
+
+/// Completes once the process is asked to stop with Ctrl+C or, on Unix, `SIGTERM` as sent by
+/// container runtimes
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            // Without a handler the server can still be stopped through SIGTERM or killed
+            error!(msg = "Failed to listen for Ctrl+C", %error);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                error!(msg = "Failed to listen for SIGTERM", %error);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => info!(msg = "Received Ctrl+C"),
+        () = terminate => info!(msg = "Received SIGTERM"),
+    }
+}
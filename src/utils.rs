@@ -1,4 +1,10 @@
+use std::{
+    cmp::Ordering,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
 use axum::http::{Extensions, HeaderMap, HeaderName};
+use semver::Version;
 use tower_http::request_id::RequestId;
 
 pub fn get_request_id(req: &Extensions) -> String {
@@ -26,3 +32,43 @@ pub fn get_header<'a>(headers: &'a HeaderMap, name: &HeaderName) -> &'a str {
 pub fn get_optional_header<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
     headers.get(name).and_then(|h| h.to_str().ok())
 }
+
+/// Returns true when both versions are valid semver and `current` is older than `target`
+pub fn is_older_version(current: &str, target: &str) -> bool {
+    match (Version::parse(current), Version::parse(target)) {
+        (Ok(current), Ok(target)) => current < target,
+        _ => false,
+    }
+}
+
+/// Orders versions by semver, placing invalid versions first and comparing those as text
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    Version::parse(a)
+        .ok()
+        .cmp(&Version::parse(b).ok())
+        .then_with(|| a.cmp(b))
+}
+
+/// Current time as unix seconds
+pub fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Rotation entries with this prefix reference an image uploaded to the server by its hash
+pub const IMAGE_REFERENCE_PREFIX: &str = "image:";
+
+/// Returns the image hash when the rotation entry references an uploaded image
+pub fn image_reference(entry: &str) -> Option<&str> {
+    entry.strip_prefix(IMAGE_REFERENCE_PREFIX)
+}
+
+/// Rotation entries with this prefix reference a layout rendered by the server by its id
+pub const LAYOUT_REFERENCE_PREFIX: &str = "layout:";
+
+/// Returns the layout id when the rotation entry references a layout
+pub fn layout_reference(entry: &str) -> Option<&str> {
+    entry.strip_prefix(LAYOUT_REFERENCE_PREFIX)
+}
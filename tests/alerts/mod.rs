@@ -0,0 +1,439 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use mockall::predicate;
+use trmnl_server::{
+    alerts::{AlertEngine, violations},
+    config::AlertSettings,
+    models::{Alert, AlertRule, AlertStatus, ApprovalStatus, Device, DeviceGroup},
+    notifier::MockNotifier,
+    repositories::{
+        alert::MockAlertRepository,
+        device::{DeviceRepo, MockDeviceRepository},
+        device_group::{DeviceGroupRepo, MockDeviceGroupRepository},
+    },
+    schedule::{QuietHours, TimeOfDay},
+};
+
+const NOW: i64 = 1_700_000_000;
+
+fn settings() -> AlertSettings {
+    AlertSettings {
+        battery_voltage_below: Some(3.4),
+        rssi_below: Some(-80),
+        missed_polls: Some(3.0),
+        firmware_mismatch: true,
+        ..AlertSettings::default()
+    }
+}
+
+/// A device that violates none of the rules of [`settings`]
+fn device() -> Device {
+    Device {
+        id: "dev123".to_string(),
+        mac: None,
+        _api_key: "abc123".to_string(),
+        rssi: Some(-60),
+        battery_voltage: Some(4.1),
+        fw_version: Some("1.6.5".to_string()),
+        refresh_rate: Some(900),
+        images: vec![],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: Some("1.6.5".to_string()),
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: Some(NOW - 900),
+        last_seen_at: Some(NOW - 900),
+    }
+}
+
+fn alert(rule: AlertRule, status: AlertStatus) -> Alert {
+    Alert {
+        id: 1,
+        device_id: "dev123".to_string(),
+        rule,
+        status,
+        message: "Something is wrong".to_string(),
+        fired_at: NOW - 3600,
+        resolved_at: (status == AlertStatus::Resolved).then_some(NOW),
+    }
+}
+
+fn rules(device: &Device, settings: &AlertSettings) -> Vec<AlertRule> {
+    rules_at(device, settings, NOW)
+}
+
+fn rules_at(device: &Device, settings: &AlertSettings, now: i64) -> Vec<AlertRule> {
+    violations(device, settings, now)
+        .into_iter()
+        .map(|(rule, _)| rule)
+        .collect()
+}
+
+#[test]
+fn violations_none_when_healthy() {
+    assert!(rules(&device(), &settings()).is_empty());
+}
+
+#[test]
+fn violations_all_rules() {
+    let device = Device {
+        rssi: Some(-85),
+        battery_voltage: Some(3.2),
+        fw_version: Some("1.6.4".to_string()),
+        last_seen_at: Some(NOW - 2700),
+        ..device()
+    };
+
+    let violations = violations(&device, &settings(), NOW);
+
+    assert_eq!(
+        violations,
+        vec![
+            (
+                AlertRule::LowBattery,
+                "Battery at 3.20 V, below 3.40 V".to_string()
+            ),
+            (
+                AlertRule::WeakSignal,
+                "Signal at -85 dBm, below -80 dBm".to_string()
+            ),
+            (
+                AlertRule::Offline,
+                "No poll for 3.0 refresh intervals".to_string()
+            ),
+            (
+                AlertRule::FirmwareMismatch,
+                "Running firmware 1.6.4, target is 1.6.5".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn violations_disabled_rules() {
+    let device = Device {
+        rssi: Some(-85),
+        battery_voltage: Some(3.2),
+        fw_version: Some("1.6.4".to_string()),
+        last_seen_at: Some(NOW - 2700),
+        ..device()
+    };
+
+    assert!(rules(&device, &AlertSettings::default()).is_empty());
+}
+
+#[test]
+fn violations_offline_uses_desired_refresh_rate() {
+    let device = Device {
+        desired_refresh_rate: Some(3600),
+        last_seen_at: Some(NOW - 2700),
+        ..device()
+    };
+
+    assert!(rules(&device, &settings()).is_empty());
+}
+
+#[test]
+fn violations_offline_not_during_quiet_hours() {
+    // Polled at 23:00 UTC during quiet hours, so told to sleep until 07:00
+    let last_seen_at = NOW - NOW.rem_euclid(86_400) + 23 * 3600;
+    let device = Device {
+        quiet_hours: Some(QuietHours {
+            start: TimeOfDay::new(22, 0).unwrap(),
+            end: TimeOfDay::new(7, 0).unwrap(),
+            sleep_screen: true,
+        }),
+        last_seen_at: Some(last_seen_at),
+        ..device()
+    };
+
+    assert!(rules_at(&device, &settings(), last_seen_at + 7 * 3600).is_empty());
+    assert_eq!(
+        rules_at(&device, &settings(), last_seen_at + 8 * 3600 + 3 * 8 * 3600),
+        vec![AlertRule::Offline]
+    );
+}
+
+#[test]
+fn violations_skip_unknown_values() {
+    // Nothing reported yet is not the same as a flat battery or a lost device
+    let device = Device {
+        rssi: None,
+        battery_voltage: None,
+        fw_version: None,
+        first_seen_at: None,
+        last_seen_at: None,
+        ..device()
+    };
+
+    assert!(rules(&device, &settings()).is_empty());
+}
+
+#[test]
+fn violations_none_when_rejected() {
+    let device = Device {
+        battery_voltage: Some(3.2),
+        approval_status: ApprovalStatus::Rejected,
+        ..device()
+    };
+
+    assert!(rules(&device, &settings()).is_empty());
+}
+
+#[test]
+fn settings_validate() {
+    assert!(settings().validate().is_ok());
+    assert!(
+        AlertSettings {
+            sweep_interval: 0,
+            ..settings()
+        }
+        .validate()
+        .is_err()
+    );
+    assert!(
+        AlertSettings {
+            missed_polls: Some(0.0),
+            ..settings()
+        }
+        .validate()
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn check_fires_new_violation() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(vec![]) }));
+
+    mock_alert_repo
+        .expect_fire()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(AlertRule::LowBattery),
+            predicate::eq("Battery at 3.20 V, below 3.40 V"),
+            predicate::eq(NOW),
+        )
+        .times(1)
+        .returning(|_, rule, _, _| {
+            Box::pin(async move { Ok(Some(alert(rule, AlertStatus::Firing))) })
+        });
+
+    mock_notifier
+        .expect_notify()
+        .withf(|alert| alert.rule == AlertRule::LowBattery && alert.status == AlertStatus::Firing)
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(()) }));
+
+    let engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(mock_notifier),
+        settings(),
+    );
+    let device = Device {
+        battery_voltage: Some(3.2),
+        ..device()
+    };
+
+    engine.check(&device, NOW).await.unwrap();
+}
+
+#[tokio::test]
+async fn check_keeps_firing_alert() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .times(1)
+        .returning(|_| {
+            Box::pin(async { Ok(vec![alert(AlertRule::LowBattery, AlertStatus::Firing)]) })
+        });
+
+    mock_alert_repo.expect_fire().times(0);
+    mock_alert_repo.expect_resolve().times(0);
+    mock_notifier.expect_notify().times(0);
+
+    let engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(mock_notifier),
+        settings(),
+    );
+    let device = Device {
+        battery_voltage: Some(3.2),
+        ..device()
+    };
+
+    engine.check(&device, NOW).await.unwrap();
+}
+
+#[tokio::test]
+async fn check_resolves_recovered() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .times(1)
+        .returning(|_| {
+            Box::pin(async { Ok(vec![alert(AlertRule::Offline, AlertStatus::Firing)]) })
+        });
+
+    mock_alert_repo
+        .expect_resolve()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(AlertRule::Offline),
+            predicate::eq(NOW),
+        )
+        .times(1)
+        .returning(|_, rule, _| {
+            Box::pin(async move { Ok(Some(alert(rule, AlertStatus::Resolved))) })
+        });
+
+    mock_notifier
+        .expect_notify()
+        .withf(|alert| alert.status == AlertStatus::Resolved)
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(()) }));
+
+    let engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(mock_notifier),
+        settings(),
+    );
+
+    engine.check(&device(), NOW).await.unwrap();
+}
+
+#[tokio::test]
+async fn check_ignores_notifier_failure() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(vec![]) }));
+
+    mock_alert_repo
+        .expect_fire()
+        .times(1)
+        .returning(|_, rule, _, _| {
+            Box::pin(async move { Ok(Some(alert(rule, AlertStatus::Firing))) })
+        });
+
+    mock_notifier
+        .expect_notify()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("connection refused")) }));
+
+    let engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(mock_notifier),
+        settings(),
+    );
+    let device = Device {
+        rssi: Some(-90),
+        ..device()
+    };
+
+    engine.check(&device, NOW).await.unwrap();
+}
+
+#[tokio::test]
+async fn check_error() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(MockNotifier::new()),
+        settings(),
+    );
+
+    assert!(engine.check(&device(), NOW).await.is_err());
+}
+
+#[tokio::test]
+async fn sweep_applies_group_target() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+    let mut mock_alert_repo = MockAlertRepository::new();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_device_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![Device {
+                target_fw_version: None,
+                group_id: Some("kitchen".to_string()),
+                ..device()
+            }])
+        })
+    });
+
+    mock_group_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![DeviceGroup {
+                id: "kitchen".to_string(),
+                name: "Kitchen".to_string(),
+                images: vec![],
+                desired_refresh_rate: None,
+                target_fw_version: Some("1.7.0".to_string()),
+                created_at: NOW,
+            }])
+        })
+    });
+
+    mock_alert_repo
+        .expect_list_firing()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(vec![]) }));
+
+    mock_alert_repo
+        .expect_fire()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(AlertRule::FirmwareMismatch),
+            predicate::eq("Running firmware 1.6.5, target is 1.7.0"),
+            predicate::eq(NOW),
+        )
+        .times(1)
+        .returning(|_, rule, _, _| {
+            Box::pin(async move { Ok(Some(alert(rule, AlertStatus::Firing))) })
+        });
+
+    mock_notifier
+        .expect_notify()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(()) }));
+
+    let engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(mock_notifier),
+        settings(),
+    );
+
+    let device_repo: DeviceRepo = Arc::new(mock_device_repo);
+    let device_group_repo: DeviceGroupRepo = Arc::new(mock_group_repo);
+
+    engine
+        .sweep(&device_repo, &device_group_repo, NOW)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,121 @@
+use std::{collections::HashMap, net::SocketAddr, time::Duration};
+
+use trmnl_server::config::{ServerConfig, TraceExporter, environment};
+use uuid::Uuid;
+
+const CONFIG: &str = r#"
+[database]
+path = "trmnl.db"
+
+[app]
+setup_logo_url = "https://example.com/logo.png"
+"#;
+
+/// Writes `contents` to a fresh TOML file and returns its path
+fn config_file(contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("trmnl-config-{}.toml", Uuid::new_v4()));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn load(env: &[(&str, &str)]) -> ServerConfig {
+    let env: HashMap<_, _> = env
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    ServerConfig::load_with_env(&config_file(CONFIG), environment().source(Some(env))).unwrap()
+}
+
+#[test]
+fn success_defaults() {
+    let settings = load(&[]);
+
+    assert_eq!(
+        settings.server.socket_addr(),
+        "0.0.0.0:3000".parse::<SocketAddr>().unwrap()
+    );
+    assert_eq!(settings.server.drain_timeout(), Duration::from_secs(30));
+    assert!(!settings.approval.required);
+    assert_eq!(settings.telemetry.exporter, TraceExporter::None);
+    assert_eq!(settings.status.grace_factor, 1.5);
+    assert_eq!(settings.status.offline_factor, 3.0);
+    assert_eq!(settings.alerts.battery_voltage_below, None);
+    assert!(!settings.alerts.firmware_mismatch);
+    assert_eq!(settings.alerts.sweep_interval(), Duration::from_secs(60));
+}
+
+#[test]
+fn success_server_section() {
+    let path = config_file(&format!(
+        "{CONFIG}\n[server]\naddress = \"127.0.0.1\"\nport = 8080\n"
+    ));
+
+    let settings =
+        ServerConfig::load_with_env(&path, environment().source(Some(HashMap::new()))).unwrap();
+
+    assert_eq!(
+        settings.server.socket_addr(),
+        "127.0.0.1:8080".parse::<SocketAddr>().unwrap()
+    );
+}
+
+#[test]
+fn success_environment_overrides() {
+    let settings = load(&[
+        ("TRMNL_SERVER__PORT", "8080"),
+        ("TRMNL_SERVER__DRAIN_TIMEOUT", "5"),
+        ("TRMNL_DATABASE__URL", "memory:"),
+        ("TRMNL_APP__BASE_URL", "https://trmnl.example.com"),
+        ("TRMNL_AUTH__BOOTSTRAP_TOKEN", "12345"),
+        ("TRMNL_APPROVAL__REQUIRED", "true"),
+        ("TRMNL_TELEMETRY__EXPORTER", "otlp-http"),
+        ("TRMNL_TELEMETRY__SAMPLING_RATIO", "0.25"),
+        ("TRMNL_TELEMETRY__HEADERS__AUTHORIZATION", "Bearer secret"),
+        ("TRMNL_STATUS__GRACE_FACTOR", "2"),
+        ("TRMNL_ALERTS__BATTERY_VOLTAGE_BELOW", "3.3"),
+        ("TRMNL_ALERTS__FIRMWARE_MISMATCH", "true"),
+        (
+            "TRMNL_ALERTS__WEBHOOK_URL",
+            "https://hooks.example.com/trmnl",
+        ),
+        (
+            "TRMNL_APPROVAL__ALLOWLIST",
+            "AA:BB:CC:DD:EE:FF,11:22:33:44:55:66",
+        ),
+    ]);
+
+    assert_eq!(settings.server.port, 8080);
+    assert_eq!(settings.server.drain_timeout(), Duration::from_secs(5));
+    assert_eq!(settings.database.url.as_deref(), Some("memory:"));
+    assert_eq!(settings.database.path, "trmnl.db");
+    assert_eq!(settings.app.base_url, "https://trmnl.example.com");
+    assert_eq!(settings.auth.bootstrap_token.as_deref(), Some("12345"));
+    assert!(settings.approval.required);
+    assert_eq!(settings.telemetry.exporter, TraceExporter::OtlpHttp);
+    assert_eq!(settings.telemetry.sampling_ratio, 0.25);
+    assert_eq!(settings.status.grace_factor, 2.0);
+    assert_eq!(settings.alerts.battery_voltage_below, Some(3.3));
+    assert!(settings.alerts.firmware_mismatch);
+    assert_eq!(
+        settings.alerts.webhook_url.as_deref(),
+        Some("https://hooks.example.com/trmnl")
+    );
+    assert_eq!(
+        settings
+            .telemetry
+            .headers
+            .get("authorization")
+            .map(String::as_str),
+        Some("Bearer secret")
+    );
+    assert_eq!(
+        settings.approval.allowlist,
+        vec!["AA:BB:CC:DD:EE:FF", "11:22:33:44:55:66"]
+    );
+}
+
+#[test]
+fn error_missing_file() {
+    assert!(ServerConfig::load_with_env("does-not-exist", environment()).is_err());
+}
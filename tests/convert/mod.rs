@@ -0,0 +1,204 @@
+use std::io::Cursor;
+
+use image::{GrayAlphaImage, GrayImage, ImageFormat, Luma, LumaA};
+use trmnl_server::convert::{
+    ConversionOptions, DISPLAY_HEIGHT, DISPLAY_WIDTH, DitherMode, ResizeMode, convert_to_bmp,
+};
+
+fn png(image: GrayImage) -> Vec<u8> {
+    let mut data = Cursor::new(Vec::new());
+    image.write_to(&mut data, ImageFormat::Png).unwrap();
+    data.into_inner()
+}
+
+fn solid(width: u32, height: u32, luma: u8) -> Vec<u8> {
+    png(GrayImage::from_pixel(width, height, Luma([luma])))
+}
+
+fn options(resize: ResizeMode, dither: DitherMode) -> ConversionOptions {
+    ConversionOptions {
+        resize,
+        dither,
+        ..Default::default()
+    }
+}
+
+fn decode(bmp: &[u8]) -> GrayImage {
+    image::load_from_memory(bmp).unwrap().to_luma8()
+}
+
+fn white_ratio(image: &GrayImage) -> f32 {
+    let white = image.pixels().filter(|Luma([luma])| *luma == 255).count();
+    white as f32 / (image.width() * image.height()) as f32
+}
+
+#[test]
+fn success_bmp_header() {
+    let bmp = convert_to_bmp(&solid(10, 10, 255), &ConversionOptions::default()).unwrap();
+
+    assert_eq!(&bmp[0..2], b"BM");
+    assert_eq!(bmp.len(), 62 + 100 * 480);
+    assert_eq!(
+        u32::from_le_bytes(bmp[2..6].try_into().unwrap()),
+        bmp.len() as u32
+    );
+    assert_eq!(i32::from_le_bytes(bmp[18..22].try_into().unwrap()), 800);
+    assert_eq!(i32::from_le_bytes(bmp[22..26].try_into().unwrap()), 480);
+    assert_eq!(u16::from_le_bytes(bmp[28..30].try_into().unwrap()), 1);
+
+    let image = decode(&bmp);
+    assert_eq!(image.dimensions(), (DISPLAY_WIDTH, DISPLAY_HEIGHT));
+}
+
+#[test]
+fn success_custom_resolution() {
+    let options = ConversionOptions {
+        width: 1872,
+        height: 1404,
+        ..Default::default()
+    };
+    let bmp = convert_to_bmp(&solid(10, 10, 255), &options).unwrap();
+
+    assert_eq!(bmp.len(), 62 + 236 * 1404);
+    assert_eq!(decode(&bmp).dimensions(), (1872, 1404));
+}
+
+#[test]
+fn success_threshold() {
+    let white = convert_to_bmp(
+        &solid(800, 480, 200),
+        &options(ResizeMode::Fit, DitherMode::Threshold),
+    )
+    .unwrap();
+    let black = convert_to_bmp(
+        &solid(800, 480, 50),
+        &options(ResizeMode::Fit, DitherMode::Threshold),
+    )
+    .unwrap();
+
+    assert_eq!(white_ratio(&decode(&white)), 1.0);
+    assert_eq!(white_ratio(&decode(&black)), 0.0);
+}
+
+#[test]
+fn success_fit_pads_with_white() {
+    let bmp = convert_to_bmp(
+        &solid(100, 100, 0),
+        &options(ResizeMode::Fit, DitherMode::Threshold),
+    )
+    .unwrap();
+    let image = decode(&bmp);
+
+    assert_eq!(image.get_pixel(0, 240).0, [255]);
+    assert_eq!(image.get_pixel(799, 240).0, [255]);
+    assert_eq!(image.get_pixel(400, 240).0, [0]);
+    assert_eq!(image.get_pixel(400, 0).0, [0]);
+}
+
+#[test]
+fn success_fill_covers_display() {
+    let bmp = convert_to_bmp(
+        &solid(100, 100, 0),
+        &options(ResizeMode::Fill, DitherMode::Threshold),
+    )
+    .unwrap();
+
+    assert_eq!(white_ratio(&decode(&bmp)), 0.0);
+}
+
+#[test]
+fn success_stretch_covers_display() {
+    let bmp = convert_to_bmp(
+        &solid(100, 100, 0),
+        &options(ResizeMode::Stretch, DitherMode::Threshold),
+    )
+    .unwrap();
+
+    assert_eq!(white_ratio(&decode(&bmp)), 0.0);
+}
+
+#[test]
+fn success_dithering_preserves_mid_grey() {
+    for dither in [
+        DitherMode::FloydSteinberg,
+        DitherMode::Atkinson,
+        DitherMode::Ordered,
+    ] {
+        let bmp = convert_to_bmp(&solid(800, 480, 128), &options(ResizeMode::Fit, dither)).unwrap();
+        let ratio = white_ratio(&decode(&bmp));
+
+        assert!((0.4..=0.6).contains(&ratio), "{dither:?} gave {ratio}");
+    }
+}
+
+#[test]
+fn success_brightness_and_contrast() {
+    let brightened = convert_to_bmp(
+        &solid(800, 480, 100),
+        &ConversionOptions {
+            dither: DitherMode::Threshold,
+            brightness: 0.2,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let flattened = convert_to_bmp(
+        &solid(800, 480, 250),
+        &ConversionOptions {
+            dither: DitherMode::Threshold,
+            contrast: 0.0,
+            brightness: -0.1,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(white_ratio(&decode(&brightened)), 1.0);
+    assert_eq!(white_ratio(&decode(&flattened)), 0.0);
+}
+
+#[test]
+fn success_transparent_is_white() {
+    let transparent = GrayAlphaImage::from_pixel(800, 480, LumaA([0, 0]));
+    let mut data = Cursor::new(Vec::new());
+    transparent.write_to(&mut data, ImageFormat::Png).unwrap();
+
+    let bmp = convert_to_bmp(
+        &data.into_inner(),
+        &options(ResizeMode::Fit, DitherMode::Threshold),
+    )
+    .unwrap();
+
+    assert_eq!(white_ratio(&decode(&bmp)), 1.0);
+}
+
+#[test]
+fn error_invalid_image() {
+    assert!(convert_to_bmp(b"not an image", &ConversionOptions::default()).is_err());
+}
+
+#[test]
+fn error_invalid_options() {
+    let contrast = ConversionOptions {
+        contrast: 5.0,
+        ..Default::default()
+    };
+    let brightness = ConversionOptions {
+        brightness: -2.0,
+        ..Default::default()
+    };
+    let empty = ConversionOptions {
+        width: 0,
+        ..Default::default()
+    };
+    let too_large = ConversionOptions {
+        height: 4096,
+        ..Default::default()
+    };
+
+    assert!(ConversionOptions::default().validate().is_ok());
+    assert!(contrast.validate().is_err());
+    assert!(brightness.validate().is_err());
+    assert!(empty.validate().is_err());
+    assert!(too_large.validate().is_err());
+}
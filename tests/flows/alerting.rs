@@ -0,0 +1,68 @@
+use axum::{body::Body, http::Request};
+use serde_json::Value;
+use trmnl_server::{
+    config::{AlertSettings, ApprovalSettings},
+    headers::{HEADER_ACCESS_TOKEN, HEADER_BATTERY_VOLTAGE, HEADER_MAC},
+    models::DisplayResponse,
+};
+
+use super::{ephemeral_app_with_alerts, send_json};
+
+fn display_request(api_key: &str, battery_voltage: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/api/display")
+        .header(HEADER_ACCESS_TOKEN, api_key)
+        .header(HEADER_BATTERY_VOLTAGE, battery_voltage)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn list_alerts_request(query: &str) -> Request<Body> {
+    crate::handlers::authorized_request()
+        .uri(format!("/api/alerts{query}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success_low_battery_fires_and_resolves() {
+    let app = ephemeral_app_with_alerts(
+        ApprovalSettings::default(),
+        AlertSettings {
+            battery_voltage_below: Some(3.4),
+            ..AlertSettings::default()
+        },
+    )
+    .await;
+
+    let setup: Value = send_json(
+        &app,
+        Request::builder()
+            .uri("/api/setup")
+            .header(HEADER_MAC, "AA:BB:CC:DD:EE:FF")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let id = setup["friendly_id"].as_str().unwrap();
+    let api_key = setup["api_key"].as_str().unwrap();
+
+    let _: DisplayResponse = send_json(&app, display_request(api_key, "3.2")).await;
+    // Polling again while the battery is still low does not fire a second alert
+    let _: DisplayResponse = send_json(&app, display_request(api_key, "3.1")).await;
+
+    let alerts: Value = send_json(&app, list_alerts_request("?status=firing")).await;
+    assert_eq!(alerts.as_array().unwrap().len(), 1);
+    assert_eq!(alerts[0]["device_id"], id);
+    assert_eq!(alerts[0]["rule"], "low_battery");
+    assert_eq!(alerts[0]["message"], "Battery at 3.20 V, below 3.40 V");
+
+    let _: DisplayResponse = send_json(&app, display_request(api_key, "4.1")).await;
+
+    let alerts: Value = send_json(&app, list_alerts_request("?status=firing")).await;
+    assert_eq!(alerts, Value::Array(vec![]));
+
+    let alerts: Value = send_json(&app, list_alerts_request("")).await;
+    assert_eq!(alerts[0]["status"], "resolved");
+    assert!(alerts[0]["resolved_at"].is_i64());
+}
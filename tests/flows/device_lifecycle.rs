@@ -0,0 +1,210 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use tower::ServiceExt;
+use trmnl_server::{
+    config::{AlertSettings, ApprovalSettings},
+    headers::{HEADER_ACCESS_TOKEN, HEADER_MAC},
+    models::{DeviceApiKey, DeviceLogQuery, DisplayResponse},
+    repositories::{
+        device_command::{DeviceCommandRepository, SqliteDeviceCommandRepo},
+        device_log::{DeviceLogRepository, SqliteDeviceLogRepo},
+        telemetry::{SqliteTelemetryRepo, TelemetryRepository},
+    },
+};
+
+use super::{ephemeral_app, ephemeral_app_with_pool, send_json};
+
+const MAC: &str = "AA:BB:CC:DD:EE:FF";
+
+fn setup_request() -> Request<Body> {
+    Request::builder()
+        .uri("/api/setup")
+        .header(HEADER_MAC, MAC)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn display_request(api_key: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/api/display")
+        .header(HEADER_ACCESS_TOKEN, api_key)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success_setup_then_display() {
+    let app = ephemeral_app(ApprovalSettings::default()).await;
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    assert_eq!(setup["status"], 200);
+    let api_key = setup["api_key"].as_str().unwrap();
+
+    let display: DisplayResponse = send_json(&app, display_request(api_key)).await;
+    assert_eq!(display.status, 0);
+    assert_eq!(display.image_url, "https://example.com/logo.png");
+
+    // The device is known now, so setting up again is refused
+    let setup: Value = send_json(&app, setup_request()).await;
+    assert_eq!(setup["status"], 404);
+}
+
+#[tokio::test]
+async fn success_pending_until_approved() {
+    let app = ephemeral_app(ApprovalSettings {
+        required: true,
+        allowlist: vec![],
+    })
+    .await;
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    let id = setup["friendly_id"].as_str().unwrap();
+    let api_key = setup["api_key"].as_str().unwrap();
+
+    let display: DisplayResponse = send_json(&app, display_request(api_key)).await;
+    assert_eq!(
+        display.image_url,
+        format!("http://localhost:3000/pending/{id}")
+    );
+
+    let _: Value = send_json(
+        &app,
+        crate::handlers::authorized_request()
+            .method("POST")
+            .uri(format!("/api/devices/{id}/approve"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    let display: DisplayResponse = send_json(&app, display_request(api_key)).await;
+    assert_eq!(display.image_url, "https://example.com/logo.png");
+}
+
+#[tokio::test]
+async fn success_rotated_key_handed_out_once() {
+    let app = ephemeral_app(ApprovalSettings::default()).await;
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    let id = setup["friendly_id"].as_str().unwrap();
+    let old_api_key = setup["api_key"].as_str().unwrap();
+
+    let rotated: DeviceApiKey = send_json(
+        &app,
+        crate::handlers::authorized_request()
+            .method("POST")
+            .uri(format!("/api/devices/{id}/rotate-key"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    let display: DisplayResponse = send_json(&app, display_request(old_api_key)).await;
+    assert_eq!(display.status, 500);
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    assert_eq!(setup["api_key"], rotated.api_key.as_str());
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    assert_eq!(setup["status"], 404);
+
+    let display: DisplayResponse = send_json(&app, display_request(&rotated.api_key)).await;
+    assert_eq!(display.status, 0);
+}
+
+#[tokio::test]
+async fn success_online_after_poll() {
+    let app = ephemeral_app(ApprovalSettings::default()).await;
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    let id = setup["friendly_id"].as_str().unwrap().to_string();
+    let api_key = setup["api_key"].as_str().unwrap();
+    let get_device = || {
+        crate::handlers::authorized_request()
+            .uri(format!("/api/devices/{id}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let device: Value = send_json(&app, get_device()).await;
+    assert_eq!(device["status"], "offline");
+    assert_eq!(device["last_seen_at"], Value::Null);
+
+    let _: DisplayResponse = send_json(&app, display_request(api_key)).await;
+
+    let device: Value = send_json(&app, get_device()).await;
+    assert_eq!(device["status"], "online");
+    assert!(device["last_seen_at"].is_i64());
+    assert_eq!(device["first_seen_at"], device["last_seen_at"]);
+}
+
+#[tokio::test]
+async fn success_delete_removes_device_data() {
+    let (app, pool) =
+        ephemeral_app_with_pool(ApprovalSettings::default(), AlertSettings::default()).await;
+    let log_repo = SqliteDeviceLogRepo::new(pool.clone());
+    let telemetry_repo = SqliteTelemetryRepo::new(pool.clone());
+    let command_repo = SqliteDeviceCommandRepo::new(pool);
+
+    let setup: Value = send_json(&app, setup_request()).await;
+    let id = setup["friendly_id"].as_str().unwrap();
+    let api_key = setup["api_key"].as_str().unwrap();
+
+    let _: DisplayResponse = send_json(&app, display_request(api_key)).await;
+    let _: Value = send_json(
+        &app,
+        Request::builder()
+            .method("POST")
+            .uri("/api/log")
+            .header(HEADER_ACCESS_TOKEN, api_key)
+            .body(Body::from(
+                r#"{"logs":[{"creation_timestamp":1700000000,"log_message":"Woke up"}]}"#,
+            ))
+            .unwrap(),
+    )
+    .await;
+    let response = app
+        .clone()
+        .oneshot(
+            crate::handlers::authorized_request()
+                .method("POST")
+                .uri(format!("/api/devices/{id}/commands"))
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"type":"reset_firmware"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let query = DeviceLogQuery::default();
+    assert_eq!(log_repo.list(id, &query).await.unwrap().len(), 1);
+    assert_eq!(telemetry_repo.list(id, 0, i64::MAX).await.unwrap().len(), 1);
+    assert_eq!(command_repo.list(id).await.unwrap().len(), 1);
+
+    let response = app
+        .clone()
+        .oneshot(
+            crate::handlers::authorized_request()
+                .method("DELETE")
+                .uri(format!("/api/devices/{id}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    assert!(log_repo.list(id, &query).await.unwrap().is_empty());
+    assert!(
+        telemetry_repo
+            .list(id, 0, i64::MAX)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(command_repo.list(id).await.unwrap().is_empty());
+}
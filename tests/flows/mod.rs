@@ -0,0 +1,84 @@
+mod alerting;
+mod device_lifecycle;
+
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use serde::de::DeserializeOwned;
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use trmnl_server::{
+    alerts::AlertEngine,
+    app::App,
+    config::{AlertSettings, AppSettings, ApprovalSettings, StatusSettings},
+    db::{apply_migrations, connect_in_memory},
+    layers::{
+        alert::AlertRepoLayer, device::DeviceRepoLayer, device_command::DeviceCommandRepoLayer,
+        device_group::DeviceGroupRepoLayer, device_log::DeviceLogRepoLayer,
+        firmware::FirmwareRepoLayer, image::ImageRepoLayer, layout::LayoutRepoLayer,
+        telemetry::TelemetryRepoLayer,
+    },
+    notifier::LogNotifier,
+};
+
+/// The router as served in ephemeral mode, devices in memory and everything else in an
+/// in-memory SQLite database
+pub async fn ephemeral_app(approval: ApprovalSettings) -> Router {
+    ephemeral_app_with_alerts(approval, AlertSettings::default()).await
+}
+
+/// [`ephemeral_app`] with alert rules enabled
+pub async fn ephemeral_app_with_alerts(
+    approval: ApprovalSettings,
+    alerts: AlertSettings,
+) -> Router {
+    ephemeral_app_with_pool(approval, alerts).await.0
+}
+
+/// [`ephemeral_app_with_alerts`] along with its SQLite database, for checking data the API no
+/// longer shows
+pub async fn ephemeral_app_with_pool(
+    approval: ApprovalSettings,
+    alerts: AlertSettings,
+) -> (Router, Arc<SqlitePool>) {
+    let pool = Arc::new(connect_in_memory().await.unwrap());
+    apply_migrations(&pool).await.unwrap();
+
+    let alert_repo_layer = AlertRepoLayer::sqlite(pool.clone());
+    let alert_engine = AlertEngine::new(alert_repo_layer.0.clone(), Arc::new(LogNotifier), alerts);
+
+    let app = App::new()
+        .router()
+        .layer(super::handlers::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(Extension(AppSettings {
+            setup_logo_url: "https://example.com/logo.png".to_string(),
+            base_url: "http://localhost:3000".to_string(),
+        }))
+        .layer(Extension(approval))
+        .layer(Extension(Arc::new(alert_engine)))
+        .layer(alert_repo_layer)
+        .layer(DeviceRepoLayer::in_memory())
+        .layer(DeviceCommandRepoLayer::sqlite(pool.clone()))
+        .layer(DeviceGroupRepoLayer::sqlite(pool.clone()))
+        .layer(DeviceLogRepoLayer::sqlite(pool.clone()))
+        .layer(TelemetryRepoLayer::sqlite(pool.clone()))
+        .layer(FirmwareRepoLayer::sqlite(pool.clone()))
+        .layer(ImageRepoLayer::sqlite(pool.clone()))
+        .layer(LayoutRepoLayer::sqlite(pool.clone()));
+
+    (app, pool)
+}
+
+/// Sends `request` and decodes the JSON body of a successful response
+pub async fn send_json<T: DeserializeOwned>(app: &Router, request: Request<Body>) -> T {
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    Extension,
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    config::StatusSettings,
+    layers::{device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer},
+    models::{ApprovalStatus, Device, DeviceInfo},
+    repositories::{device::MockDeviceRepository, device_group::MockDeviceGroupRepository},
+};
+
+pub fn device(approval_status: ApprovalStatus) -> Device {
+    Device {
+        id: "dev123".to_string(),
+        mac: Some("AA:BB:CC:DD:EE:FF".to_string()),
+        _api_key: "abc123".to_string(),
+        rssi: None,
+        battery_voltage: None,
+        fw_version: None,
+        refresh_rate: None,
+        images: vec![],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        approval_status,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: None,
+        last_seen_at: None,
+    }
+}
+
+async fn approve(mock_repo: MockDeviceRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/devices/dev123/approve")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_approval_status()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(ApprovalStatus::Approved),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device(ApprovalStatus::Approved))) }));
+
+    let response = approve(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DeviceInfo = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json.id, "dev123");
+    assert_eq!(json.approval_status, ApprovalStatus::Approved);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_approval_status()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    mock_repo.expect_get_by_id().times(0);
+
+    assert_eq!(approve(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_approval_status()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        approve(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
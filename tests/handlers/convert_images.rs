@@ -0,0 +1,134 @@
+use std::{io::Cursor, sync::Arc};
+
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use image::{GrayImage, ImageFormat, Luma};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::image::ImageRepoLayer, models::Image,
+    repositories::image::MockImageRepository,
+};
+
+const BOUNDARY: &str = "trmnl-boundary";
+
+fn multipart_body(data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+
+    body.extend_from_slice(
+        format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"photo.png\"\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+fn png() -> Vec<u8> {
+    let mut data = Cursor::new(Vec::new());
+    GrayImage::from_pixel(40, 24, Luma([0]))
+        .write_to(&mut data, ImageFormat::Png)
+        .unwrap();
+    data.into_inner()
+}
+
+async fn convert(mock_repo: MockImageRepository, query: &str, data: &[u8]) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(ImageRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri(format!("/api/images/convert{query}"))
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={BOUNDARY}"),
+                )
+                .body(Body::from(multipart_body(data)))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_create()
+        .withf(|id, content_type, data| {
+            id.len() == 64 && content_type == "image/bmp" && data.starts_with(b"BM")
+        })
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo.expect_get().times(1).returning(|id| {
+        let id = id.to_string();
+        Box::pin(async move {
+            Ok(Some(Image {
+                id,
+                content_type: "image/bmp".to_string(),
+                size: 48062,
+                created_at: 1_700_000_000,
+            }))
+        })
+    });
+
+    let response = convert(mock_repo, "?resize=stretch&dither=threshold", &png()).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let images: Vec<Image> = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].content_type, "image/bmp");
+}
+
+#[tokio::test]
+async fn error_invalid_image() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = convert(mock_repo, "", b"not an image").await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_invalid_options() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = convert(mock_repo, "?contrast=-1", &png()).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_store() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_create()
+        .with(
+            predicate::always(),
+            predicate::eq("image/bmp"),
+            predicate::always(),
+        )
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow::anyhow!("DB Error")) }));
+
+    let response = convert(mock_repo, "", &png()).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
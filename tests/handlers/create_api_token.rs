@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, auth::hash_api_token, layers::api_token::ApiTokenRepoLayer, models::ApiToken,
+    models::CreatedApiToken, repositories::api_token::MockApiTokenRepository,
+};
+
+use super::API_TOKEN;
+
+fn authenticating_repo() -> MockApiTokenRepository {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo
+        .expect_get_by_hash()
+        .withf(|token_hash| token_hash == hash_api_token(API_TOKEN))
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(ApiToken {
+                    id: "token123".to_string(),
+                    name: "test".to_string(),
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+}
+
+async fn create(mock_repo: MockApiTokenRepository, body: &'static str) -> axum::response::Response {
+    App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/tokens")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo
+        .expect_create()
+        .withf(|id, name, token_hash| !id.is_empty() && name == "ci" && token_hash.len() == 64)
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    let response = create(mock_repo, r#"{"name":" ci "}"#).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: CreatedApiToken = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(created.name, "ci");
+    assert_eq!(created.token.len(), 40);
+}
+
+#[tokio::test]
+async fn error_empty_name() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo.expect_create().times(0);
+
+    let response = create(mock_repo, r#"{"name":"  "}"#).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = create(mock_repo, r#"{"name":"ci"}"#).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
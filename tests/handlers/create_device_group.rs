@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, error::PROBLEM_CONTENT_TYPE, layers::device_group::DeviceGroupRepoLayer,
+    models::DeviceGroup, repositories::device_group::MockDeviceGroupRepository,
+};
+
+use super::get_device_group::device_group;
+
+async fn create(mock_repo: MockDeviceGroupRepository, body: &'static str) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/groups")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_create()
+        .with(predicate::always(), predicate::eq("Lobby"))
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device_group(vec![]))) }));
+
+    let response = create(mock_repo, r#"{"name":"  Lobby "}"#).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let group: DeviceGroup = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(group.name, "Lobby");
+}
+
+#[tokio::test]
+async fn error_empty_name() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    assert_eq!(
+        create(mock_repo, r#"{"name":" "}"#).await.status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
+
+#[tokio::test]
+async fn error_missing_name() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = create(mock_repo, r#"{"title":"Lobby"}"#).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        PROBLEM_CONTENT_TYPE
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        create(mock_repo, r#"{"name":"Lobby"}"#).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{image::ImageRepoLayer, layout::LayoutRepoLayer},
+    layout::LayoutSpec,
+    models::{Image, Layout},
+    repositories::{image::MockImageRepository, layout::MockLayoutRepository},
+};
+
+const SPEC: &str = r#"{"elements":[{"type":"text","x":10,"y":10,"text":"Hello"}]}"#;
+
+async fn create(
+    mock_repo: MockLayoutRepository,
+    image_repo: MockImageRepository,
+    body: String,
+) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(image_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/layouts")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockLayoutRepository::new();
+    let spec: LayoutSpec = serde_json::from_str(SPEC).unwrap();
+    let expected = spec.clone();
+
+    mock_repo
+        .expect_create()
+        .withf(move |id, name, spec| !id.is_empty() && name == "Dashboard" && *spec == expected)
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo.expect_get().times(1).returning(move |id| {
+        let layout = Layout {
+            id: id.to_string(),
+            name: "Dashboard".to_string(),
+            spec: spec.clone(),
+            created_at: 1_700_000_000,
+            updated_at: 1_700_000_000,
+        };
+        Box::pin(async move { Ok(Some(layout)) })
+    });
+
+    let response = create(
+        mock_repo,
+        MockImageRepository::new(),
+        format!(r#"{{"name":" Dashboard ","spec":{SPEC}}}"#),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let layout: Layout = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(layout.name, "Dashboard");
+}
+
+#[tokio::test]
+async fn success_with_image() {
+    let mut mock_repo = MockLayoutRepository::new();
+    let mut image_repo = MockImageRepository::new();
+
+    image_repo
+        .expect_get()
+        .with(predicate::eq("abc123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Image {
+                    id: "abc123".to_string(),
+                    content_type: "image/png".to_string(),
+                    size: 3,
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo.expect_get().times(1).returning(|id| {
+        let layout = Layout {
+            id: id.to_string(),
+            name: "Photo".to_string(),
+            spec: serde_json::from_str(r#"{"elements":[]}"#).unwrap(),
+            created_at: 1_700_000_000,
+            updated_at: 1_700_000_000,
+        };
+        Box::pin(async move { Ok(Some(layout)) })
+    });
+
+    let response = create(
+        mock_repo,
+        image_repo,
+        r#"{"name":"Photo","spec":{"elements":[{"type":"image","x":0,"y":0,"width":100,"height":100,"image":"abc123"}]}}"#.to_string(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn error_unknown_image() {
+    let mut mock_repo = MockLayoutRepository::new();
+    let mut image_repo = MockImageRepository::new();
+
+    image_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo.expect_create().times(0);
+
+    let response = create(
+        mock_repo,
+        image_repo,
+        r#"{"name":"Photo","spec":{"elements":[{"type":"image","x":0,"y":0,"width":100,"height":100,"image":"missing"}]}}"#.to_string(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_empty_name() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = create(
+        mock_repo,
+        MockImageRepository::new(),
+        format!(r#"{{"name":" ","spec":{SPEC}}}"#),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_invalid_spec() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = create(
+        mock_repo,
+        MockImageRepository::new(),
+        r#"{"name":"Big","spec":{"elements":[{"type":"text","x":0,"y":0,"text":"hi","size":4096}]}}"#
+            .to_string(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_coordinates_out_of_range() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = create(
+        mock_repo,
+        MockImageRepository::new(),
+        r#"{"name":"Far","spec":{"elements":[{"type":"line","x1":-2000000000,"y1":0,"x2":2000000000,"y2":0}]}}"#
+            .to_string(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = create(
+        mock_repo,
+        MockImageRepository::new(),
+        format!(r#"{{"name":"Dashboard","spec":{SPEC}}}"#),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{body::Body, http::StatusCode};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, auth::hash_api_token, layers::api_token::ApiTokenRepoLayer, models::ApiToken,
+    repositories::api_token::MockApiTokenRepository,
+};
+
+use super::API_TOKEN;
+
+fn authenticating_repo() -> MockApiTokenRepository {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo
+        .expect_get_by_hash()
+        .withf(|token_hash| token_hash == hash_api_token(API_TOKEN))
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(ApiToken {
+                    id: "token123".to_string(),
+                    name: "test".to_string(),
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+}
+
+async fn delete(mock_repo: MockApiTokenRepository) -> StatusCode {
+    App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("DELETE")
+                .uri("/api/tokens/token456")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("token456"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    assert_eq!(delete(mock_repo).await, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("token456"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(false) }));
+
+    assert_eq!(delete(mock_repo).await, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(delete(mock_repo).await, StatusCode::INTERNAL_SERVER_ERROR);
+}
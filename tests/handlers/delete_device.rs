@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{body::Body, http::StatusCode};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{
+        alert::AlertRepoLayer, device::DeviceRepoLayer, device_command::DeviceCommandRepoLayer,
+        device_log::DeviceLogRepoLayer, telemetry::TelemetryRepoLayer,
+    },
+    repositories::{
+        alert::MockAlertRepository, device::MockDeviceRepository,
+        device_command::MockDeviceCommandRepository, device_log::MockDeviceLogRepository,
+        telemetry::MockTelemetryRepository,
+    },
+};
+
+/// Repositories holding data of the device, each expecting it to be deleted once
+struct DeviceData {
+    log_repo: MockDeviceLogRepository,
+    telemetry_repo: MockTelemetryRepository,
+    command_repo: MockDeviceCommandRepository,
+    alert_repo: MockAlertRepository,
+}
+
+impl DeviceData {
+    fn deleted() -> Self {
+        let mut log_repo = MockDeviceLogRepository::new();
+        log_repo
+            .expect_delete_by_device()
+            .with(predicate::eq("dev123"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let mut telemetry_repo = MockTelemetryRepository::new();
+        telemetry_repo
+            .expect_delete_by_device()
+            .with(predicate::eq("dev123"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let mut command_repo = MockDeviceCommandRepository::new();
+        command_repo
+            .expect_delete_by_device()
+            .with(predicate::eq("dev123"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let mut alert_repo = MockAlertRepository::new();
+        alert_repo
+            .expect_delete_by_device()
+            .with(predicate::eq("dev123"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        Self {
+            log_repo,
+            telemetry_repo,
+            command_repo,
+            alert_repo,
+        }
+    }
+}
+
+async fn delete(mock_repo: MockDeviceRepository, data: DeviceData) -> StatusCode {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(data.log_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(data.telemetry_repo)))
+        .layer(DeviceCommandRepoLayer(Arc::new(data.command_repo)))
+        .layer(AlertRepoLayer(Arc::new(data.alert_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("DELETE")
+                .uri("/api/devices/dev123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    assert_eq!(
+        delete(mock_repo, DeviceData::deleted()).await,
+        StatusCode::NO_CONTENT
+    );
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(false) }));
+
+    assert_eq!(
+        delete(mock_repo, DeviceData::deleted()).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        delete(mock_repo, DeviceData::deleted()).await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
+
+#[tokio::test]
+async fn error_device_data() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_delete().times(0);
+
+    let mut log_repo = MockDeviceLogRepository::new();
+    log_repo
+        .expect_delete_by_device()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let data = DeviceData {
+        log_repo,
+        telemetry_repo: MockTelemetryRepository::new(),
+        command_repo: MockDeviceCommandRepository::new(),
+        alert_repo: MockAlertRepository::new(),
+    };
+
+    assert_eq!(
+        delete(mock_repo, data).await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
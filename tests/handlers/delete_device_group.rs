@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{body::Body, http::StatusCode};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer},
+    models::{ApprovalStatus, DevicePatch},
+    repositories::{device::MockDeviceRepository, device_group::MockDeviceGroupRepository},
+};
+
+/// Device repository for a group without members
+fn no_members() -> MockDeviceRepository {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    mock_device_repo
+        .expect_list_by_group()
+        .returning(|_| Box::pin(async { Ok(vec![]) }));
+    mock_device_repo
+}
+
+async fn delete(
+    mock_device_repo: MockDeviceRepository,
+    mock_repo: MockDeviceGroupRepository,
+) -> StatusCode {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("DELETE")
+                .uri("/api/groups/group123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    assert_eq!(
+        delete(no_members(), mock_repo).await,
+        StatusCode::NO_CONTENT
+    );
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(false) }));
+
+    assert_eq!(delete(no_members(), mock_repo).await, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        delete(no_members(), mock_repo).await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
+
+#[tokio::test]
+async fn success_members_leave_group() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_device_repo
+        .expect_list_by_group()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| {
+            let mut device = super::approve_device::device(ApprovalStatus::Approved);
+            device.group_id = Some("group123".to_string());
+            Box::pin(async move { Ok(vec![device]) })
+        });
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                group_id: Some(None),
+                ..Default::default()
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    assert_eq!(
+        delete(mock_device_repo, mock_repo).await,
+        StatusCode::NO_CONTENT
+    );
+}
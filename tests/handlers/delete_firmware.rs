@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{body::Body, http::StatusCode};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{
+        device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer, firmware::FirmwareRepoLayer,
+    },
+    models::ApprovalStatus,
+    repositories::{
+        device::MockDeviceRepository, device_group::MockDeviceGroupRepository,
+        firmware::MockFirmwareRepository,
+    },
+};
+
+fn device_repo(target_fw_version: Option<&'static str>) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo.expect_list().times(1).returning(move || {
+        let mut device = super::approve_device::device(ApprovalStatus::Approved);
+        device.target_fw_version = target_fw_version.map(str::to_string);
+        Box::pin(async move { Ok(vec![device]) })
+    });
+
+    mock_repo
+}
+
+fn device_group_repo(target_fw_version: Option<&'static str>) -> MockDeviceGroupRepository {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo.expect_list().returning(move || {
+        let mut group = super::get_device_group::device_group(vec![]);
+        group.target_fw_version = target_fw_version.map(str::to_string);
+        Box::pin(async move { Ok(vec![group]) })
+    });
+
+    mock_repo
+}
+
+async fn delete(mock_repo: MockFirmwareRepository) -> StatusCode {
+    delete_with_targets(mock_repo, device_repo(None), device_group_repo(None)).await
+}
+
+async fn delete_with_targets(
+    mock_repo: MockFirmwareRepository,
+    device_repo: MockDeviceRepository,
+    device_group_repo: MockDeviceGroupRepository,
+) -> StatusCode {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(device_group_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("DELETE")
+                .uri("/api/firmware/1.6.5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    assert_eq!(delete(mock_repo).await, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn success_other_version_targeted() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    assert_eq!(
+        delete_with_targets(
+            mock_repo,
+            device_repo(Some("1.6.4")),
+            device_group_repo(Some("1.7.0"))
+        )
+        .await,
+        StatusCode::NO_CONTENT
+    );
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(false) }));
+
+    assert_eq!(delete(mock_repo).await, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error_targeted_by_device() {
+    let mut mock_repo = MockFirmwareRepository::new();
+    mock_repo.expect_delete().times(0);
+
+    assert_eq!(
+        delete_with_targets(
+            mock_repo,
+            device_repo(Some("1.6.5")),
+            device_group_repo(None)
+        )
+        .await,
+        StatusCode::CONFLICT
+    );
+}
+
+#[tokio::test]
+async fn error_targeted_by_group() {
+    let mut mock_repo = MockFirmwareRepository::new();
+    mock_repo.expect_delete().times(0);
+
+    assert_eq!(
+        delete_with_targets(
+            mock_repo,
+            device_repo(None),
+            device_group_repo(Some("1.6.5"))
+        )
+        .await,
+        StatusCode::CONFLICT
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(delete(mock_repo).await, StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{body::Body, http::StatusCode};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{
+        device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer, layout::LayoutRepoLayer,
+    },
+    models::{ApprovalStatus, Device},
+    repositories::{
+        device::MockDeviceRepository, device_group::MockDeviceGroupRepository,
+        layout::MockLayoutRepository,
+    },
+};
+
+fn device_repo(images: Vec<String>) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo.expect_list().times(1).returning(move || {
+        let device = Device {
+            id: "dev123".to_string(),
+            mac: None,
+            _api_key: "key".to_string(),
+            rssi: None,
+            battery_voltage: None,
+            fw_version: None,
+            refresh_rate: None,
+            images: images.clone(),
+            image_cursor: 0,
+            desired_refresh_rate: None,
+            target_fw_version: None,
+            approval_status: ApprovalStatus::Approved,
+            schedule: vec![],
+            timezone: None,
+            quiet_hours: None,
+            group_id: None,
+            first_seen_at: None,
+            last_seen_at: None,
+        };
+        Box::pin(async move { Ok(vec![device]) })
+    });
+
+    mock_repo
+}
+
+fn device_group_repo(images: Vec<&'static str>) -> MockDeviceGroupRepository {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo.expect_list().returning(move || {
+        let group = super::get_device_group::device_group(
+            images.iter().map(|image| image.to_string()).collect(),
+        );
+        Box::pin(async move { Ok(vec![group]) })
+    });
+
+    mock_repo
+}
+
+async fn delete(mock_repo: MockLayoutRepository, device_repo: MockDeviceRepository) -> StatusCode {
+    delete_with_groups(mock_repo, device_repo, device_group_repo(vec![])).await
+}
+
+async fn delete_with_groups(
+    mock_repo: MockLayoutRepository,
+    device_repo: MockDeviceRepository,
+    device_group_repo: MockDeviceGroupRepository,
+) -> StatusCode {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(device_group_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("DELETE")
+                .uri("/api/layouts/layout123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .with(predicate::eq("layout123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    let status = delete(mock_repo, device_repo(vec!["layout:other".to_string()])).await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(false) }));
+
+    assert_eq!(
+        delete(mock_repo, device_repo(vec![])).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error_in_rotation() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo.expect_delete().times(0);
+
+    let status = delete(mock_repo, device_repo(vec!["layout:layout123".to_string()])).await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn error_in_group_rotation() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo.expect_delete().times(0);
+
+    let status = delete_with_groups(
+        mock_repo,
+        device_repo(vec![]),
+        device_group_repo(vec!["layout:layout123"]),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        delete(mock_repo, device_repo(vec![])).await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -4,24 +4,77 @@ use axum::{
     extract::Extension,
     http::{Request, StatusCode},
 };
+use chrono::{Duration, Timelike, Utc};
 use mockall::predicate;
 use std::sync::Arc;
 use tower::ServiceExt;
 use trmnl_server::{
+    alerts::AlertEngine,
     app::App,
-    config::AppSettings,
-    headers::{HEADER_ACCESS_TOKEN, HEADER_BATTERY_VOLTAGE, HEADER_FW_VERSION, HEADER_RSSI},
-    layers::device::DeviceRepoLayer,
-    models::DisplayResponse,
-    repositories::device::MockDeviceRepository,
+    config::{AlertSettings, AppSettings},
+    headers::{
+        HEADER_ACCESS_TOKEN, HEADER_BATTERY_VOLTAGE, HEADER_FW_VERSION, HEADER_REFRESH_RATE,
+        HEADER_RSSI,
+    },
+    layers::{
+        device::DeviceRepoLayer, device_command::DeviceCommandRepoLayer,
+        device_group::DeviceGroupRepoLayer, telemetry::TelemetryRepoLayer,
+    },
+    models::{
+        Alert, AlertRule, AlertStatus, ApprovalStatus, DeviceCommand, DisplayResponse,
+        QueuedDeviceCommand,
+    },
+    notifier::MockNotifier,
+    repositories::{
+        alert::MockAlertRepository, device::MockDeviceRepository,
+        device_command::MockDeviceCommandRepository, device_group::MockDeviceGroupRepository,
+        telemetry::MockTelemetryRepository,
+    },
+    schedule::{QuietHours, ScheduleRule, TimeOfDay},
 };
 
 fn test_settings() -> AppSettings {
     AppSettings {
         setup_logo_url: "https://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
     }
 }
 
+fn telemetry_repo() -> MockTelemetryRepository {
+    let mut mock_repo = MockTelemetryRepository::new();
+
+    mock_repo
+        .expect_record()
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+}
+
+fn command_repo() -> MockDeviceCommandRepository {
+    let mut mock_repo = MockDeviceCommandRepository::new();
+
+    mock_repo
+        .expect_take_next()
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo
+}
+
+/// An engine without rules, so any firing alert would be resolved
+fn alert_engine() -> Arc<AlertEngine> {
+    let mut mock_alert_repo = MockAlertRepository::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .returning(|_| Box::pin(async { Ok(vec![]) }));
+
+    Arc::new(AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(MockNotifier::new()),
+        AlertSettings::default(),
+    ))
+}
+
 #[tokio::test]
 async fn success_found() {
     let mut mock_repo = MockDeviceRepository::new();
@@ -41,6 +94,16 @@ async fn success_found() {
                     fw_version: None,
                     refresh_rate: None,
                     images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
                 }))
             })
         });
@@ -48,11 +111,17 @@ async fn success_found() {
     mock_repo
         .expect_update_status()
         .times(1)
-        .returning(|_, _, _, _, _| Box::pin(async { Ok(()) }));
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
 
     let app = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .layer(Extension(test_settings()));
 
     let response = app
@@ -76,60 +145,137 @@ async fn success_found() {
 
     assert_eq!(json.status, 0);
     assert_eq!(json.image_url, "https://example.com/logo.png");
-    assert_eq!(json.update_firmware, false);
+    assert!(!json.update_firmware);
 }
 
 #[tokio::test]
-async fn success_not_found() {
+async fn success_rotates_images() {
     let mut mock_repo = MockDeviceRepository::new();
 
     mock_repo
         .expect_get_by_api_key()
-        .with(predicate::eq("invalid-token"))
+        .with(predicate::eq("valid-token"))
         .times(1)
-        .returning(|_token| Box::pin(async { Ok(None) }));
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["one.bmp".to_string(), "two.bmp".to_string()],
+                    image_cursor: 1,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_update_image_cursor()
+        .with(predicate::eq("dev123"), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
 
     let app = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .layer(Extension(test_settings()));
 
     let response = app
         .oneshot(
             Request::builder()
                 .uri("/api/display")
-                .header(&HEADER_ACCESS_TOKEN, "invalid-token")
-                .header(&HEADER_RSSI, "-70")
-                .header(&HEADER_FW_VERSION, "1.0.0")
-                .header(&HEADER_BATTERY_VOLTAGE, "3.7")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
                 .body(Body::empty())
                 .unwrap(),
         )
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::OK); // Handler always returns 200
+    assert_eq!(response.status(), StatusCode::OK);
+
     let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
     let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
 
-    assert_eq!(json.status, 500);
+    assert_eq!(json.status, 0);
+    assert_eq!(json.image_url, "two.bmp");
 }
 
 #[tokio::test]
-async fn error_get_by_api_key() {
+async fn success_wraps_stale_cursor() {
     let mut mock_repo = MockDeviceRepository::new();
 
     mock_repo
         .expect_get_by_api_key()
         .with(predicate::eq("valid-token"))
         .times(1)
-        .returning(|_token| Box::pin(async { Err(anyhow!("DB Error")) }));
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["one.bmp".to_string(), "two.bmp".to_string()],
+                    image_cursor: 5,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
 
-    mock_repo.expect_update_status().times(0);
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_update_image_cursor()
+        .with(predicate::eq("dev123"), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
 
     let app = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .layer(Extension(test_settings()));
 
     let response = app
@@ -137,20 +283,170 @@ async fn error_get_by_api_key() {
             Request::builder()
                 .uri("/api/display")
                 .header(&HEADER_ACCESS_TOKEN, "valid-token")
-                .header(&HEADER_RSSI, "-70")
-                .header(&HEADER_FW_VERSION, "1.0.0")
-                .header(&HEADER_BATTERY_VOLTAGE, "3.7")
                 .body(Body::empty())
                 .unwrap(),
         )
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.image_url, "two.bmp");
 }
 
 #[tokio::test]
-async fn error_update_status() {
+async fn success_resolves_uploaded_image() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["image:abc123".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_update_image_cursor()
+        .with(predicate::eq("dev123"), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.image_url, "http://localhost:3000/images/abc123");
+}
+
+#[tokio::test]
+async fn success_resolves_layout() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["layout:layout123".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_update_image_cursor()
+        .with(predicate::eq("dev123"), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.image_url, "http://localhost:3000/layouts/layout123");
+}
+
+#[tokio::test]
+async fn success_desired_refresh_rate() {
     let mut mock_repo = MockDeviceRepository::new();
 
     mock_repo
@@ -168,18 +464,127 @@ async fn error_update_status() {
                     fw_version: None,
                     refresh_rate: None,
                     images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: Some(3600),
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
                 }))
             })
         });
 
     mock_repo
         .expect_update_status()
+        .with(
+            predicate::eq("dev123"),
+            predicate::always(),
+            predicate::always(),
+            predicate::always(),
+            predicate::always(),
+            predicate::eq(Some(900)),
+        )
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .header(&HEADER_REFRESH_RATE, "900")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.refresh_rate, "3600");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("invalid-token"))
+        .times(1)
+        .returning(|_token| Box::pin(async { Ok(None) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "invalid-token")
+                .header(&HEADER_RSSI, "-70")
+                .header(&HEADER_FW_VERSION, "1.0.0")
+                .header(&HEADER_BATTERY_VOLTAGE, "3.7")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK); // Handler always returns 200
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.status, 500);
+}
+
+#[tokio::test]
+async fn error_get_by_api_key() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
         .times(1)
-        .returning(|_, _, _, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+        .returning(|_token| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    mock_repo.expect_update_status().times(0);
 
     let app = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .layer(Extension(test_settings()));
 
     let response = app
@@ -198,3 +603,1079 @@ async fn error_update_status() {
 
     assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
 }
+
+#[tokio::test]
+async fn error_update_status() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .header(&HEADER_RSSI, "-70")
+                .header(&HEADER_FW_VERSION, "1.0.0")
+                .header(&HEADER_BATTERY_VOLTAGE, "3.7")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn success_update_image_cursor_failed() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["one.bmp".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_update_image_cursor()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.image_url, "one.bmp");
+}
+
+async fn display_with_firmware(
+    fw_version: Option<&'static str>,
+    target_fw_version: Option<&'static str>,
+) -> DisplayResponse {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(move |_token| {
+            Box::pin(async move {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: Some("1.0.0".to_string()),
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: target_fw_version.map(str::to_string),
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let mut request = Request::builder()
+        .uri("/api/display")
+        .header(&HEADER_ACCESS_TOKEN, "valid-token");
+
+    if let Some(fw_version) = fw_version {
+        request = request.header(&HEADER_FW_VERSION, fw_version);
+    }
+
+    let response = app
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+#[tokio::test]
+async fn success_firmware_update_available() {
+    let json = display_with_firmware(Some("1.5.2"), Some("1.6.0")).await;
+
+    assert!(json.update_firmware);
+    assert_eq!(
+        json.firmware_url.as_deref(),
+        Some("http://localhost:3000/firmware/1.6.0")
+    );
+}
+
+#[tokio::test]
+async fn success_firmware_update_uses_stored_version() {
+    let json = display_with_firmware(None, Some("1.6.0")).await;
+
+    assert!(json.update_firmware);
+}
+
+#[tokio::test]
+async fn success_firmware_up_to_date() {
+    let json = display_with_firmware(Some("1.10.0"), Some("1.6.0")).await;
+
+    assert!(!json.update_firmware);
+    assert!(json.firmware_url.is_none());
+}
+
+#[tokio::test]
+async fn success_firmware_no_target() {
+    let json = display_with_firmware(Some("1.5.2"), None).await;
+
+    assert!(!json.update_firmware);
+    assert!(json.firmware_url.is_none());
+}
+
+#[tokio::test]
+async fn success_records_telemetry() {
+    let mut mock_repo = MockDeviceRepository::new();
+    let mut mock_telemetry_repo = MockTelemetryRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_telemetry_repo
+        .expect_record()
+        .withf(|device_id, sample| {
+            device_id == "dev123"
+                && sample.rssi == Some(-70)
+                && sample.battery_voltage == Some(3.7)
+                && sample.fw_version.as_deref() == Some("1.0.0")
+                && sample.refresh_rate == Some(900)
+                && sample.recorded_at > 0
+        })
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(mock_telemetry_repo)))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .header(&HEADER_RSSI, "-70")
+                .header(&HEADER_FW_VERSION, "1.0.0")
+                .header(&HEADER_BATTERY_VOLTAGE, "3.7")
+                .header(&HEADER_REFRESH_RATE, "900")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Telemetry failures are logged but do not fail the request
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Display request of a device whose stored battery reading is still healthy
+async fn display_low_battery(
+    mock_alert_repo: MockAlertRepository,
+    notifier: MockNotifier,
+) -> StatusCode {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: Some(4.1),
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let alert_engine = AlertEngine::new(
+        Arc::new(mock_alert_repo),
+        Arc::new(notifier),
+        AlertSettings {
+            battery_voltage_below: Some(3.4),
+            ..AlertSettings::default()
+        },
+    );
+
+    let app = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(Arc::new(alert_engine)))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()));
+
+    app.oneshot(
+        Request::builder()
+            .uri("/api/display")
+            .header(&HEADER_ACCESS_TOKEN, "valid-token")
+            .header(&HEADER_BATTERY_VOLTAGE, "3.2")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+#[tokio::test]
+async fn success_fires_alert_on_reported_values() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(vec![]) }));
+
+    mock_alert_repo
+        .expect_fire()
+        .withf(|device_id, rule, _, fired_at| {
+            device_id == "dev123" && *rule == AlertRule::LowBattery && *fired_at > 0
+        })
+        .times(1)
+        .returning(|device_id, rule, message, fired_at| {
+            let alert = Alert {
+                id: 1,
+                device_id: device_id.to_string(),
+                rule,
+                status: AlertStatus::Firing,
+                message: message.to_string(),
+                fired_at,
+                resolved_at: None,
+            };
+            Box::pin(async move { Ok(Some(alert)) })
+        });
+
+    mock_notifier
+        .expect_notify()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(()) }));
+
+    let status = display_low_battery(mock_alert_repo, mock_notifier).await;
+
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn success_alert_check_failed() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+
+    mock_alert_repo
+        .expect_list_firing()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let status = display_low_battery(mock_alert_repo, MockNotifier::new()).await;
+
+    // Alerting failures are logged but do not fail the request
+    assert_eq!(status, StatusCode::OK);
+}
+
+async fn display_with_approval_status(
+    approval_status: ApprovalStatus,
+    mock_repo: MockDeviceRepository,
+) -> DisplayResponse {
+    let mut mock_repo = mock_repo;
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(move |_token| {
+            Box::pin(async move {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["https://example.com/image1.png".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo.expect_update_image_cursor().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+#[tokio::test]
+async fn success_pending_approval() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let json = display_with_approval_status(ApprovalStatus::Pending, mock_repo).await;
+
+    assert_eq!(json.status, 0);
+    assert_eq!(json.image_url, "http://localhost:3000/pending/dev123");
+}
+
+#[tokio::test]
+async fn success_rejected() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo.expect_update_status().times(0);
+
+    let json = display_with_approval_status(ApprovalStatus::Rejected, mock_repo).await;
+
+    assert_eq!(json.status, 500);
+    assert_eq!(json.image_url, "https://example.com/logo.png");
+}
+
+async fn display_with_command(
+    command: DeviceCommand,
+    mock_repo: MockDeviceRepository,
+) -> DisplayResponse {
+    let mut mock_repo = mock_repo;
+
+    mock_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: Some("1.6.5".to_string()),
+                    refresh_rate: None,
+                    images: vec!["https://example.com/image1.png".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+
+    mock_command_repo
+        .expect_take_next()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(move |_| {
+            let command = command.clone();
+            Box::pin(async move {
+                Ok(Some(QueuedDeviceCommand {
+                    id: 1,
+                    device_id: "dev123".to_string(),
+                    command,
+                    created_at: 1700000000,
+                    delivered_at: Some(1700000100),
+                }))
+            })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(mock_command_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+fn rotating_device_repo() -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_image_cursor()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+}
+
+#[tokio::test]
+async fn success_command_reset_firmware() {
+    let json = display_with_command(DeviceCommand::ResetFirmware, rotating_device_repo()).await;
+
+    assert!(json.reset_firmware);
+    assert!(!json.update_firmware);
+    assert_eq!(json.image_url, "https://example.com/image1.png");
+}
+
+#[tokio::test]
+async fn success_command_delivered_when_cursor_update_fails() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_image_cursor()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let json = display_with_command(DeviceCommand::ResetFirmware, mock_repo).await;
+
+    assert!(json.reset_firmware);
+    assert_eq!(json.image_url, "https://example.com/image1.png");
+}
+
+#[tokio::test]
+async fn success_command_update_firmware() {
+    // Commands bypass the version check so older releases can be installed too
+    let json = display_with_command(
+        DeviceCommand::UpdateFirmware {
+            version: "1.5.0".to_string(),
+        },
+        rotating_device_repo(),
+    )
+    .await;
+
+    assert!(json.update_firmware);
+    assert_eq!(
+        json.firmware_url.as_deref(),
+        Some("http://localhost:3000/firmware/1.5.0")
+    );
+    assert!(!json.reset_firmware);
+}
+
+#[tokio::test]
+async fn success_command_show_image() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_update_image_cursor().times(0);
+
+    let json = display_with_command(
+        DeviceCommand::ShowImage {
+            image: "layout:welcome".to_string(),
+        },
+        mock_repo,
+    )
+    .await;
+
+    assert_eq!(json.image_url, "http://localhost:3000/layouts/welcome");
+    assert!(!json.update_firmware);
+    assert!(!json.reset_firmware);
+}
+
+#[tokio::test]
+async fn success_command_lookup_failed() {
+    let mut mock_repo = rotating_device_repo();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["https://example.com/image1.png".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+
+    mock_command_repo
+        .expect_take_next()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    // Commands are optional, the device still gets its next image
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(mock_command_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.status, 0);
+    assert_eq!(json.image_url, "https://example.com/image1.png");
+}
+
+#[tokio::test]
+async fn success_active_schedule() {
+    let mut mock_repo = rotating_device_repo();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(|_token| {
+            Box::pin(async {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["https://example.com/image1.png".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    // Matches around the clock so the test does not depend on when it runs
+                    schedule: vec![ScheduleRule {
+                        weekdays: vec![],
+                        start: "00:00".parse().unwrap(),
+                        end: "00:00".parse().unwrap(),
+                        images: vec!["layout:meetings".to_string()],
+                    }],
+                    timezone: Some("Europe/Amsterdam".to_string()),
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DisplayResponse = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.image_url, "http://localhost:3000/layouts/meetings");
+}
+
+/// Window from an hour ago until an hour from now in UTC, so the test does not depend on when it runs
+fn current_quiet_hours(sleep_screen: bool) -> QuietHours {
+    let time = |at: chrono::DateTime<Utc>| TimeOfDay::new(at.hour() as u16, at.minute() as u16);
+    let now = Utc::now();
+
+    QuietHours {
+        start: time(now - Duration::hours(1)).unwrap(),
+        end: time(now + Duration::hours(1)).unwrap(),
+        sleep_screen,
+    }
+}
+
+fn display_with_quiet_hours(quiet_hours: QuietHours) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(move |_token| {
+            let quiet_hours = quiet_hours.clone();
+            Box::pin(async move {
+                Ok(Some(trmnl_server::models::Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec!["https://example.com/image1.png".to_string()],
+                    image_cursor: 0,
+                    desired_refresh_rate: Some(300),
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: Some(quiet_hours),
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+}
+
+async fn display_quiet(mock_repo: MockDeviceRepository) -> DisplayResponse {
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(Extension(test_settings()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+#[tokio::test]
+async fn success_quiet_hours() {
+    let json = display_quiet(display_with_quiet_hours(current_quiet_hours(false))).await;
+
+    // Sleeps until the window ends instead of using the desired refresh rate
+    let refresh_rate: i64 = json.refresh_rate.parse().unwrap();
+    assert!((3540..=3600).contains(&refresh_rate), "{refresh_rate}");
+    assert_eq!(json.image_url, "https://example.com/image1.png");
+}
+
+#[tokio::test]
+async fn success_quiet_hours_keeps_rotation() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(|_token| {
+            let mut device = super::approve_device::device(ApprovalStatus::Approved);
+            device.images = vec!["one.bmp".to_string(), "two.bmp".to_string()];
+            device.image_cursor = 1;
+            device.quiet_hours = Some(current_quiet_hours(false));
+            Box::pin(async move { Ok(Some(device)) })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    // The image at the cursor is shown, but overnight polls do not move the rotation along
+    mock_repo.expect_update_image_cursor().never();
+
+    let json = display_quiet(mock_repo).await;
+
+    assert_eq!(json.image_url, "two.bmp");
+}
+
+#[tokio::test]
+async fn success_quiet_hours_sleep_screen() {
+    // The rotation is paused, so the cursor is left alone
+    let json = display_quiet(display_with_quiet_hours(current_quiet_hours(true))).await;
+
+    let refresh_rate: i64 = json.refresh_rate.parse().unwrap();
+    assert!((3540..=3600).contains(&refresh_rate), "{refresh_rate}");
+    assert_eq!(json.image_url, "http://localhost:3000/sleeping/dev123");
+}
+
+#[tokio::test]
+async fn success_outside_quiet_hours() {
+    let mut quiet_hours = current_quiet_hours(true);
+    // Swapping the ends gives the rest of the day, which excludes the current hour
+    std::mem::swap(&mut quiet_hours.start, &mut quiet_hours.end);
+
+    let mut mock_repo = display_with_quiet_hours(quiet_hours);
+
+    mock_repo
+        .expect_update_image_cursor()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    let json = display_quiet(mock_repo).await;
+
+    assert_eq!(json.refresh_rate, "300");
+    assert_eq!(json.image_url, "https://example.com/image1.png");
+}
+
+async fn display_with_group(
+    images: Vec<String>,
+    desired_refresh_rate: Option<i64>,
+) -> DisplayResponse {
+    let mut mock_repo = rotating_device_repo();
+
+    mock_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(move |_token| {
+            let mut device = super::approve_device::device(ApprovalStatus::Approved);
+            device.images = images.clone();
+            device.desired_refresh_rate = desired_refresh_rate;
+            device.group_id = Some("group123".to_string());
+            Box::pin(async move { Ok(Some(device)) })
+        });
+
+    mock_repo
+        .expect_update_status()
+        .times(1)
+        .returning(|_, _, _, _, _, _| Box::pin(async { Ok(()) }));
+
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+
+    mock_group_repo
+        .expect_get()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| {
+            let mut group = super::get_device_group::device_group(vec![
+                "https://example.com/group.png".to_string(),
+            ]);
+            group.desired_refresh_rate = Some(600);
+            group.target_fw_version = Some("1.6.5".to_string());
+            Box::pin(async move { Ok(Some(group)) })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo())))
+        .layer(Extension(alert_engine()))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo())))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_group_repo)))
+        .layer(Extension(test_settings()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/display")
+                .header(&HEADER_ACCESS_TOKEN, "valid-token")
+                .header(&HEADER_FW_VERSION, "1.6.0")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+#[tokio::test]
+async fn success_group_settings() {
+    let json = display_with_group(vec![], None).await;
+
+    assert_eq!(json.image_url, "https://example.com/group.png");
+    assert_eq!(json.refresh_rate, "600");
+    assert_eq!(
+        json.firmware_url.as_deref(),
+        Some("http://localhost:3000/firmware/1.6.5")
+    );
+}
+
+#[tokio::test]
+async fn success_device_overrides_group() {
+    let json = display_with_group(vec!["https://example.com/own.png".to_string()], Some(300)).await;
+
+    assert_eq!(json.image_url, "https://example.com/own.png");
+    assert_eq!(json.refresh_rate, "300");
+    // Settings the device leaves unset still come from the group
+    assert!(json.update_firmware);
+}
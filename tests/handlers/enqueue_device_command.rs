@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{
+        device::DeviceRepoLayer, device_command::DeviceCommandRepoLayer,
+        firmware::FirmwareRepoLayer, image::ImageRepoLayer, layout::LayoutRepoLayer,
+    },
+    models::{ApprovalStatus, Device, DeviceCommand, Firmware, QueuedDeviceCommand},
+    repositories::{
+        device::MockDeviceRepository, device_command::MockDeviceCommandRepository,
+        firmware::MockFirmwareRepository, image::MockImageRepository, layout::MockLayoutRepository,
+    },
+};
+
+fn device_repo(found: bool) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(move |_| {
+            Box::pin(async move {
+                Ok(found.then(|| Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+}
+
+async fn enqueue(
+    device_repo: MockDeviceRepository,
+    command_repo: MockDeviceCommandRepository,
+    firmware_repo: MockFirmwareRepository,
+    layout_repo: MockLayoutRepository,
+    body: &'static str,
+) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo)))
+        .layer(FirmwareRepoLayer(Arc::new(firmware_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(layout_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/devices/dev123/commands")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn command_repo(command: DeviceCommand) -> MockDeviceCommandRepository {
+    let mut mock_repo = MockDeviceCommandRepository::new();
+
+    mock_repo
+        .expect_enqueue()
+        .with(predicate::eq("dev123"), predicate::eq(command))
+        .times(1)
+        .returning(|device_id, command| {
+            let queued = QueuedDeviceCommand {
+                id: 1,
+                device_id: device_id.to_string(),
+                command: command.clone(),
+                created_at: 1_700_000_000,
+                delivered_at: None,
+            };
+            Box::pin(async move { Ok(queued) })
+        });
+
+    mock_repo
+}
+
+#[tokio::test]
+async fn success_reset_firmware() {
+    let response = enqueue(
+        device_repo(true),
+        command_repo(DeviceCommand::ResetFirmware),
+        MockFirmwareRepository::new(),
+        MockLayoutRepository::new(),
+        r#"{"type": "reset_firmware"}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: QueuedDeviceCommand = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json.id, 1);
+    assert_eq!(json.command, DeviceCommand::ResetFirmware);
+    assert_eq!(json.delivered_at, None);
+}
+
+#[tokio::test]
+async fn success_update_firmware() {
+    let mut firmware_repo = MockFirmwareRepository::new();
+
+    firmware_repo
+        .expect_get()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|version| {
+            let firmware = Firmware {
+                version: version.to_string(),
+                checksum: "abc".to_string(),
+                size: 3,
+                created_at: 0,
+            };
+            Box::pin(async move { Ok(Some(firmware)) })
+        });
+
+    let response = enqueue(
+        device_repo(true),
+        command_repo(DeviceCommand::UpdateFirmware {
+            version: "1.6.5".to_string(),
+        }),
+        firmware_repo,
+        MockLayoutRepository::new(),
+        r#"{"type": "update_firmware", "version": "1.6.5"}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn error_unknown_firmware() {
+    let mut firmware_repo = MockFirmwareRepository::new();
+
+    firmware_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+    mock_command_repo.expect_enqueue().times(0);
+
+    let response = enqueue(
+        device_repo(true),
+        mock_command_repo,
+        firmware_repo,
+        MockLayoutRepository::new(),
+        r#"{"type": "update_firmware", "version": "9.9.9"}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_unknown_layout() {
+    let mut layout_repo = MockLayoutRepository::new();
+
+    layout_repo
+        .expect_get()
+        .with(predicate::eq("missing"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+    mock_command_repo.expect_enqueue().times(0);
+
+    let response = enqueue(
+        device_repo(true),
+        mock_command_repo,
+        MockFirmwareRepository::new(),
+        layout_repo,
+        r#"{"type": "show_image", "image": "layout:missing"}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_device_not_found() {
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+    mock_command_repo.expect_enqueue().times(0);
+
+    let response = enqueue(
+        device_repo(false),
+        mock_command_repo,
+        MockFirmwareRepository::new(),
+        MockLayoutRepository::new(),
+        r#"{"type": "reset_firmware"}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+
+    mock_command_repo
+        .expect_enqueue()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = enqueue(
+        device_repo(true),
+        mock_command_repo,
+        MockFirmwareRepository::new(),
+        MockLayoutRepository::new(),
+        r#"{"type": "show_image", "image": "https://example.com/image.png"}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
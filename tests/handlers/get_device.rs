@@ -2,14 +2,19 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use axum::{
+    Extension,
     body::{Body, to_bytes},
-    http::{Request, StatusCode},
+    http::StatusCode,
 };
 use mockall::predicate;
 use tower::ServiceExt;
 use trmnl_server::{
-    app::App, layers::device::DeviceRepoLayer, models::Device,
-    repositories::device::MockDeviceRepository,
+    app::App,
+    config::StatusSettings,
+    layers::{device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer},
+    models::{ApprovalStatus, Device},
+    repositories::{device::MockDeviceRepository, device_group::MockDeviceGroupRepository},
+    utils::unix_timestamp,
 };
 
 #[tokio::test]
@@ -23,6 +28,16 @@ async fn success_found() {
         fw_version: Some("1.0.0".to_string()),
         refresh_rate: Some(60),
         images: vec!["image_one.jpg".to_string(), "image_two.jpg".to_string()],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: None,
+        last_seen_at: None,
     };
 
     let mut mock_device_repo = MockDeviceRepository::new();
@@ -38,9 +53,14 @@ async fn success_found() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
         .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices/dev123")
                 .body(Body::empty())
                 .unwrap(),
@@ -69,9 +89,14 @@ async fn success_not_found() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
         .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices/dev123")
                 .body(Body::empty())
                 .unwrap(),
@@ -94,9 +119,14 @@ async fn error() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
         .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices/dev123")
                 .body(Body::empty())
                 .unwrap(),
@@ -106,3 +136,55 @@ async fn error() {
 
     assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
 }
+
+#[tokio::test]
+async fn success_status_with_group_settings() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_id| {
+            let mut device = super::approve_device::device(ApprovalStatus::Approved);
+            device.group_id = Some("group123".to_string());
+            device.refresh_rate = Some(900);
+            device.last_seen_at = Some(unix_timestamp() - 2000);
+            Box::pin(async move { Ok(Some(device)) })
+        });
+
+    mock_group_repo
+        .expect_get()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_id| {
+            let mut group = super::get_device_group::device_group(vec![]);
+            group.desired_refresh_rate = Some(3600);
+            Box::pin(async move { Ok(Some(group)) })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_group_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices/dev123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    // The device's own settings are shown, but its status follows the group's refresh rate
+    assert_eq!(json["desired_refresh_rate"], serde_json::Value::Null);
+    assert_eq!(json["status"], "online");
+}
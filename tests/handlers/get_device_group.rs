@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::device_group::DeviceGroupRepoLayer, models::DeviceGroup,
+    repositories::device_group::MockDeviceGroupRepository,
+};
+
+pub fn device_group(images: Vec<String>) -> DeviceGroup {
+    DeviceGroup {
+        id: "group123".to_string(),
+        name: "Lobby".to_string(),
+        images,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        created_at: 1_700_000_000,
+    }
+}
+
+async fn get(mock_repo: MockDeviceGroupRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/groups/group123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device_group(vec!["a.png".to_string()]))) }));
+
+    let response = get(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let group: DeviceGroup = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(group, device_group(vec!["a.png".to_string()]));
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(get(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        get(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -3,12 +3,14 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use axum::{
     body::{Body, to_bytes},
-    http::{Request, StatusCode},
+    http::StatusCode,
 };
 use mockall::predicate;
 use tower::ServiceExt;
 use trmnl_server::{
-    app::App, layers::device::DeviceRepoLayer, models::Device,
+    app::App,
+    layers::device::DeviceRepoLayer,
+    models::{ApprovalStatus, Device},
     repositories::device::MockDeviceRepository,
 };
 
@@ -23,6 +25,16 @@ async fn success_found() {
         fw_version: Some("1.0.0".to_string()),
         refresh_rate: Some(60),
         images: vec!["image_one.jpg".to_string(), "image_two.jpg".to_string()],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: None,
+        last_seen_at: None,
     };
 
     let mut mock_repo = MockDeviceRepository::new();
@@ -37,9 +49,10 @@ async fn success_found() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices/dev123/images")
                 .body(Body::empty())
                 .unwrap(),
@@ -68,9 +81,10 @@ async fn success_not_found() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices/dev123/images")
                 .body(Body::empty())
                 .unwrap(),
@@ -92,9 +106,10 @@ async fn error() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices/dev123/images")
                 .body(Body::empty())
                 .unwrap(),
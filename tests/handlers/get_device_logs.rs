@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device::DeviceRepoLayer, device_log::DeviceLogRepoLayer},
+    models::{ApprovalStatus, Device, DeviceLog, DeviceLogQuery},
+    repositories::{device::MockDeviceRepository, device_log::MockDeviceLogRepository},
+};
+
+fn device() -> Device {
+    Device {
+        id: "dev123".to_string(),
+        mac: None,
+        _api_key: "valid-token".to_string(),
+        rssi: None,
+        battery_voltage: None,
+        fw_version: None,
+        refresh_rate: None,
+        images: vec![],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: None,
+        last_seen_at: None,
+    }
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device())) }));
+
+    mock_log_repo
+        .expect_list()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DeviceLogQuery {
+                from: Some(1_700_000_000),
+                to: None,
+                level: Some("error".to_string()),
+                q: Some("wifi".to_string()),
+                limit: None,
+            }),
+        )
+        .times(1)
+        .returning(|_, _| {
+            Box::pin(async {
+                Ok(vec![DeviceLog {
+                    id: 1,
+                    device_id: "dev123".to_string(),
+                    created_at: 1_700_000_100,
+                    received_at: 1_700_000_200,
+                    level: "error".to_string(),
+                    message: "wifi connect failed".to_string(),
+                    source_file: None,
+                    source_line: None,
+                    wake_reason: Some("timer".to_string()),
+                    battery_voltage: None,
+                    wifi_status: None,
+                    wifi_rssi: None,
+                    fw_version: None,
+                    additional_info: None,
+                }])
+            })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices/dev123/logs?from=1700000000&level=error&q=wifi")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json[0]["message"], "wifi connect failed");
+    assert_eq!(json[0]["wake_reason"], "timer");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_log_repo.expect_list().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices/dev123/logs")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device())) }));
+
+    mock_log_repo
+        .expect_list()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices/dev123/logs")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::device::DeviceRepoLayer,
+    models::{ApprovalStatus, Device},
+    repositories::device::MockDeviceRepository,
+    schedule::{ScheduleRule, Weekday},
+};
+
+async fn get_schedule(mock_repo: MockDeviceRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices/dev123/schedule")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "abc123".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![ScheduleRule {
+                        weekdays: vec![Weekday::Sat, Weekday::Sun],
+                        start: "10:00".parse().unwrap(),
+                        end: "22:30".parse().unwrap(),
+                        images: vec!["image1.png".to_string()],
+                    }],
+                    timezone: Some("Europe/Amsterdam".to_string()),
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    let response = get_schedule(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!([{
+            "weekdays": ["sat", "sun"],
+            "start": "10:00",
+            "end": "22:30",
+            "images": ["image1.png"]
+        }])
+    );
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(
+        get_schedule(mock_repo).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        get_schedule(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
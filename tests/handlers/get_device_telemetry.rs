@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device::DeviceRepoLayer, telemetry::TelemetryRepoLayer},
+    models::{ApprovalStatus, Device, TelemetryBucket, TelemetrySample, TelemetryStats},
+    repositories::{device::MockDeviceRepository, telemetry::MockTelemetryRepository},
+};
+
+fn device_repo(found: bool) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .returning(move |_| {
+            let device = found.then(|| Device {
+                id: "dev123".to_string(),
+                mac: None,
+                _api_key: "valid-token".to_string(),
+                rssi: None,
+                battery_voltage: None,
+                fw_version: None,
+                refresh_rate: None,
+                images: vec![],
+                image_cursor: 0,
+                desired_refresh_rate: None,
+                target_fw_version: None,
+                approval_status: ApprovalStatus::Approved,
+                schedule: vec![],
+                timezone: None,
+                quiet_hours: None,
+                group_id: None,
+                first_seen_at: None,
+                last_seen_at: None,
+            });
+            Box::pin(async move { Ok(device) })
+        });
+
+    mock_repo
+}
+
+async fn get(
+    uri: &str,
+    device_repo: MockDeviceRepository,
+    telemetry_repo: MockTelemetryRepository,
+) -> (StatusCode, serde_json::Value) {
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .layer(TelemetryRepoLayer(Arc::new(telemetry_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn success_raw() {
+    let mut mock_telemetry_repo = MockTelemetryRepository::new();
+
+    mock_telemetry_repo
+        .expect_list()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(1_700_000_000),
+            predicate::eq(1_700_086_400),
+        )
+        .times(1)
+        .returning(|_, _, _| {
+            Box::pin(async {
+                Ok(vec![TelemetrySample {
+                    recorded_at: 1_700_000_900,
+                    rssi: Some(-60),
+                    battery_voltage: Some(3.9),
+                    fw_version: Some("1.6.5".to_string()),
+                    refresh_rate: Some(900),
+                }])
+            })
+        });
+
+    let (status, json) = get(
+        "/api/devices/dev123/telemetry?from=1700000000&to=1700086400",
+        device_repo(true),
+        mock_telemetry_repo,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json[0]["recorded_at"], 1_700_000_900);
+    assert_eq!(json[0]["rssi"], -60);
+}
+
+#[tokio::test]
+async fn success_bucketed() {
+    let mut mock_telemetry_repo = MockTelemetryRepository::new();
+
+    mock_telemetry_repo
+        .expect_aggregate()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(1_700_000_000),
+            predicate::eq(1_700_086_400),
+            predicate::eq(3600),
+        )
+        .times(1)
+        .returning(|_, _, _, _| {
+            Box::pin(async {
+                Ok(vec![TelemetryBucket {
+                    start: 1_699_999_200,
+                    samples: 4,
+                    rssi: Some(TelemetryStats {
+                        min: -70.0,
+                        avg: -65.0,
+                        max: -60.0,
+                    }),
+                    battery_voltage: None,
+                }])
+            })
+        });
+
+    let (status, json) = get(
+        "/api/devices/dev123/telemetry?from=1700000000&to=1700086400&bucket=1h",
+        device_repo(true),
+        mock_telemetry_repo,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json[0]["samples"], 4);
+    assert_eq!(json[0]["rssi"]["avg"], -65.0);
+    assert!(json[0]["battery_voltage"].is_null());
+}
+
+#[tokio::test]
+async fn error_invalid_bucket() {
+    let mut mock_telemetry_repo = MockTelemetryRepository::new();
+
+    mock_telemetry_repo.expect_aggregate().times(0);
+
+    let (status, _) = get(
+        "/api/devices/dev123/telemetry?bucket=0h",
+        device_repo(true),
+        mock_telemetry_repo,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_inverted_range() {
+    let (status, _) = get(
+        "/api/devices/dev123/telemetry?from=1700086400&to=1700000000",
+        device_repo(true),
+        MockTelemetryRepository::new(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_telemetry_repo = MockTelemetryRepository::new();
+
+    mock_telemetry_repo.expect_list().times(0);
+
+    let (status, _) = get(
+        "/api/devices/dev123/telemetry",
+        device_repo(false),
+        mock_telemetry_repo,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_telemetry_repo = MockTelemetryRepository::new();
+
+    mock_telemetry_repo
+        .expect_list()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let (status, _) = get(
+        "/api/devices/dev123/telemetry",
+        device_repo(true),
+        mock_telemetry_repo,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
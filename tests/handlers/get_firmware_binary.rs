@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::firmware::FirmwareRepoLayer, repositories::firmware::MockFirmwareRepository,
+};
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_get_data()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(b"firmware".to_vec())) }));
+
+    let response = App::new()
+        .router()
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            Request::builder()
+                .uri("/firmware/1.6.5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/octet-stream"
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"firmware");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_get_data()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let response = App::new()
+        .router()
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            Request::builder()
+                .uri("/firmware/1.6.5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_get_data()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            Request::builder()
+                .uri("/firmware/1.6.5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
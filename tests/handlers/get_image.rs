@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::image::ImageRepoLayer, models::ImageContent,
+    repositories::image::MockImageRepository,
+};
+
+fn image_repo() -> MockImageRepository {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_get_content()
+        .with(predicate::eq("abc123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(ImageContent {
+                    content_type: "image/bmp".to_string(),
+                    data: b"BMbitmap".to_vec(),
+                }))
+            })
+        });
+
+    mock_repo
+}
+
+async fn get(mock_repo: MockImageRepository, if_none_match: Option<&str>) -> Response {
+    let mut request = Request::builder().uri("/images/abc123");
+
+    if let Some(etag) = if_none_match {
+        request = request.header(header::IF_NONE_MATCH, etag);
+    }
+
+    App::new()
+        .router()
+        .layer(ImageRepoLayer(Arc::new(mock_repo)))
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let response = get(image_repo(), None).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/bmp");
+    assert_eq!(response.headers()[header::ETAG], "\"abc123\"");
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "public, max-age=31536000, immutable"
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"BMbitmap");
+}
+
+#[tokio::test]
+async fn success_not_modified() {
+    let response = get(image_repo(), Some("\"abc123\"")).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_get_content()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let response = get(mock_repo, None).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_get_content()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = get(mock_repo, None).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,88 @@
+use std::{io::Cursor, sync::Arc};
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+    response::Response,
+};
+use image::{GrayImage, ImageFormat, Luma};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::image::ImageRepoLayer, models::ImageContent,
+    repositories::image::MockImageRepository,
+};
+
+async fn preview(mock_repo: MockImageRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(ImageRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/images/abc123/preview?dither=ordered")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_get_content()
+        .with(predicate::eq("abc123"))
+        .times(1)
+        .returning(|_| {
+            let mut data = Cursor::new(Vec::new());
+            GrayImage::from_pixel(40, 24, Luma([128]))
+                .write_to(&mut data, ImageFormat::Png)
+                .unwrap();
+
+            Box::pin(async move {
+                Ok(Some(ImageContent {
+                    content_type: "image/png".to_string(),
+                    data: data.into_inner(),
+                }))
+            })
+        });
+
+    let response = preview(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/bmp");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[0..2], b"BM");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_get_content()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(preview(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_get_content()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        preview(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
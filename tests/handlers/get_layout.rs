@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::layout::LayoutRepoLayer, models::Layout,
+    repositories::layout::MockLayoutRepository,
+};
+
+async fn get(mock_repo: MockLayoutRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/layouts/layout123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("layout123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Layout {
+                    id: "layout123".to_string(),
+                    name: "Dashboard".to_string(),
+                    spec: serde_json::from_str(r#"{"elements":[]}"#).unwrap(),
+                    created_at: 1_700_000_000,
+                    updated_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    let response = get(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let layout: Layout = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(layout.name, "Dashboard");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(get(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        get(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
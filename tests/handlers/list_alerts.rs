@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    error::PROBLEM_CONTENT_TYPE,
+    layers::alert::AlertRepoLayer,
+    models::{Alert, AlertListQuery, AlertRule, AlertStatus},
+    repositories::alert::MockAlertRepository,
+};
+
+#[tokio::test]
+async fn success() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+
+    mock_alert_repo
+        .expect_list()
+        .with(predicate::eq(AlertListQuery {
+            status: Some(AlertStatus::Firing),
+            device_id: Some("dev123".to_string()),
+        }))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(vec![Alert {
+                    id: 1,
+                    device_id: "dev123".to_string(),
+                    rule: AlertRule::LowBattery,
+                    status: AlertStatus::Firing,
+                    message: "Battery at 3.20 V, below 3.40 V".to_string(),
+                    fired_at: 1_700_000_000,
+                    resolved_at: None,
+                }])
+            })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(AlertRepoLayer(Arc::new(mock_alert_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/alerts?status=firing&device_id=dev123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json[0]["rule"], "low_battery");
+    assert_eq!(json[0]["status"], "firing");
+    assert_eq!(json[0]["resolved_at"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn error_invalid_status() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+
+    mock_alert_repo.expect_list().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(AlertRepoLayer(Arc::new(mock_alert_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/alerts?status=snoozed")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        PROBLEM_CONTENT_TYPE
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_alert_repo = MockAlertRepository::new();
+
+    mock_alert_repo
+        .expect_list()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(AlertRepoLayer(Arc::new(mock_alert_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/alerts")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, auth::hash_api_token, layers::api_token::ApiTokenRepoLayer, models::ApiToken,
+    repositories::api_token::MockApiTokenRepository,
+};
+
+use super::API_TOKEN;
+
+fn authenticating_repo() -> MockApiTokenRepository {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo
+        .expect_get_by_hash()
+        .withf(|token_hash| token_hash == hash_api_token(API_TOKEN))
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(ApiToken {
+                    id: "token123".to_string(),
+                    name: "test".to_string(),
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![ApiToken {
+                id: "token123".to_string(),
+                name: "test".to_string(),
+                created_at: 1_700_000_000,
+            }])
+        })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/tokens")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json[0]["id"], "token123");
+    assert_eq!(json[0]["name"], "test");
+    assert!(json[0].get("token_hash").is_none());
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = authenticating_repo();
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/tokens")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device::DeviceRepoLayer, device_command::DeviceCommandRepoLayer},
+    models::{ApprovalStatus, Device, DeviceCommand, QueuedDeviceCommand},
+    repositories::{device::MockDeviceRepository, device_command::MockDeviceCommandRepository},
+};
+
+fn device_repo(found: bool) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(move |_| {
+            Box::pin(async move {
+                Ok(found.then(|| Device {
+                    id: "dev123".to_string(),
+                    mac: None,
+                    _api_key: "valid-token".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo
+}
+
+async fn list(
+    device_repo: MockDeviceRepository,
+    command_repo: MockDeviceCommandRepository,
+) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .layer(DeviceCommandRepoLayer(Arc::new(command_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices/dev123/commands")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+
+    mock_command_repo
+        .expect_list()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(vec![
+                    QueuedDeviceCommand {
+                        id: 2,
+                        device_id: "dev123".to_string(),
+                        command: DeviceCommand::ResetFirmware,
+                        created_at: 1_700_000_100,
+                        delivered_at: None,
+                    },
+                    QueuedDeviceCommand {
+                        id: 1,
+                        device_id: "dev123".to_string(),
+                        command: DeviceCommand::ShowImage {
+                            image: "https://example.com/image.png".to_string(),
+                        },
+                        created_at: 1_700_000_000,
+                        delivered_at: Some(1_700_000_050),
+                    },
+                ])
+            })
+        });
+
+    let response = list(device_repo(true), mock_command_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json[0]["id"], 2);
+    assert_eq!(json[0]["command"]["type"], "reset_firmware");
+    assert!(json[0]["delivered_at"].is_null());
+    assert_eq!(json[1]["command"]["type"], "show_image");
+    assert_eq!(json[1]["command"]["image"], "https://example.com/image.png");
+    assert_eq!(json[1]["delivered_at"], 1_700_000_050);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+    mock_command_repo.expect_list().times(0);
+
+    let response = list(device_repo(false), mock_command_repo).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_command_repo = MockDeviceCommandRepository::new();
+
+    mock_command_repo
+        .expect_list()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = list(device_repo(true), mock_command_repo).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
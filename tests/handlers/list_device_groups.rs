@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::device_group::DeviceGroupRepoLayer, models::DeviceGroup,
+    repositories::device_group::MockDeviceGroupRepository,
+};
+
+use super::get_device_group::device_group;
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(vec![device_group(vec![])]) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/groups")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let groups: Vec<DeviceGroup> = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(groups, vec![device_group(vec![])]);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/groups")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -2,15 +2,19 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use axum::{
+    Extension,
     body::{Body, to_bytes},
-    http::{Request, StatusCode},
+    http::StatusCode,
 };
+use mockall::predicate;
 use tower::ServiceExt;
 use trmnl_server::{
     app::App,
-    layers::device::DeviceRepoLayer,
-    models::{Device, DeviceInfo},
-    repositories::device::MockDeviceRepository,
+    config::StatusSettings,
+    layers::{device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer},
+    models::{ApprovalStatus, Device, DeviceInfo, DeviceStatus},
+    repositories::{device::MockDeviceRepository, device_group::MockDeviceGroupRepository},
+    utils::unix_timestamp,
 };
 
 #[tokio::test]
@@ -25,6 +29,16 @@ async fn success() {
             fw_version: Some("1.0.0".to_string()),
             refresh_rate: Some(60),
             images: vec![],
+            image_cursor: 0,
+            desired_refresh_rate: None,
+            target_fw_version: None,
+            approval_status: ApprovalStatus::Approved,
+            schedule: vec![],
+            timezone: None,
+            quiet_hours: None,
+            group_id: None,
+            first_seen_at: None,
+            last_seen_at: None,
         },
         Device {
             id: "dev456".to_string(),
@@ -35,6 +49,16 @@ async fn success() {
             fw_version: Some("1.1.0".to_string()),
             refresh_rate: Some(120),
             images: vec![],
+            image_cursor: 0,
+            desired_refresh_rate: None,
+            target_fw_version: None,
+            approval_status: ApprovalStatus::Approved,
+            schedule: vec![],
+            timezone: None,
+            quiet_hours: None,
+            group_id: None,
+            first_seen_at: None,
+            last_seen_at: None,
         },
     ];
 
@@ -46,11 +70,16 @@ async fn success() {
 
     let app = App::new()
         .router()
-        .layer(DeviceRepoLayer(Arc::new(mock_repo)));
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )));
 
     let response = app
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices")
                 .body(Body::empty())
                 .unwrap(),
@@ -70,6 +99,125 @@ async fn success() {
     assert_eq!(json[1].mac, None);
 }
 
+#[tokio::test]
+async fn success_filter_approval_status() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_list().times(0);
+    mock_repo
+        .expect_list_by_approval_status()
+        .with(predicate::eq(ApprovalStatus::Pending))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(vec![Device {
+                    id: "dev789".to_string(),
+                    mac: None,
+                    _api_key: "ghi789".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Pending,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }])
+            })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices?approval_status=pending")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Vec<DeviceInfo> = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.len(), 1);
+    assert_eq!(json[0].id, "dev789");
+    assert_eq!(json[0].approval_status, ApprovalStatus::Pending);
+}
+
+#[tokio::test]
+async fn success_filter_group() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_list().times(0);
+    mock_repo.expect_list_by_approval_status().times(0);
+    mock_repo
+        .expect_list_by_group()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| {
+            let member = |id: &str, approval_status| {
+                let mut device = super::approve_device::device(approval_status);
+                device.id = id.to_string();
+                device.group_id = Some("group123".to_string());
+                device.refresh_rate = Some(900);
+                device.last_seen_at = Some(unix_timestamp() - 2000);
+                device
+            };
+            let devices = vec![
+                member("dev123", ApprovalStatus::Approved),
+                member("dev456", ApprovalStatus::Pending),
+            ];
+            Box::pin(async move { Ok(devices) })
+        });
+
+    // Polling every 900 seconds the members would be late, but the group has them sleep longer
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+    mock_group_repo.expect_list().times(1).returning(|| {
+        let mut group = super::get_device_group::device_group(vec![]);
+        group.desired_refresh_rate = Some(3600);
+        Box::pin(async move { Ok(vec![group]) })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_group_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/devices?group=group123&approval_status=approved")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Vec<DeviceInfo> = serde_json::from_slice(&body_bytes).unwrap();
+
+    assert_eq!(json.len(), 1);
+    assert_eq!(json[0].id, "dev123");
+    assert_eq!(json[0].group_id.as_deref(), Some("group123"));
+    assert_eq!(json[0].status, DeviceStatus::Online);
+}
+
 #[tokio::test]
 async fn error() {
     let mut mock_repo = MockDeviceRepository::new();
@@ -80,11 +228,16 @@ async fn error() {
 
     let app = App::new()
         .router()
-        .layer(DeviceRepoLayer(Arc::new(mock_repo)));
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )));
 
     let response = app
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .uri("/api/devices")
                 .body(Body::empty())
                 .unwrap(),
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::firmware::FirmwareRepoLayer, models::Firmware,
+    repositories::firmware::MockFirmwareRepository,
+};
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![Firmware {
+                version: "1.6.5".to_string(),
+                checksum: "abc".to_string(),
+                size: 3,
+                created_at: 1_700_000_000,
+            }])
+        })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/firmware")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json[0]["version"], "1.6.5");
+    assert_eq!(json[0]["checksum"], "abc");
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/firmware")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
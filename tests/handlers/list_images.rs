@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::image::ImageRepoLayer, models::Image,
+    repositories::image::MockImageRepository,
+};
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![Image {
+                id: "abc123".to_string(),
+                content_type: "image/bmp".to_string(),
+                size: 48062,
+                created_at: 1_700_000_000,
+            }])
+        })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(ImageRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/images")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json[0]["id"], "abc123");
+    assert_eq!(json[0]["content_type"], "image/bmp");
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(ImageRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/images")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
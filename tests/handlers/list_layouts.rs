@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::layout::LayoutRepoLayer, models::Layout,
+    repositories::layout::MockLayoutRepository,
+};
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![Layout {
+                id: "layout123".to_string(),
+                name: "Dashboard".to_string(),
+                spec: serde_json::from_str(r#"{"elements":[]}"#).unwrap(),
+                created_at: 1_700_000_000,
+                updated_at: 1_700_000_000,
+            }])
+        })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/layouts")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json[0]["id"], "layout123");
+    assert_eq!(json[0]["spec"]["background"], "white");
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/api/layouts")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
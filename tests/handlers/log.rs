@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use mockall::predicate;
+use serde_json::Value;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    headers::HEADER_ACCESS_TOKEN,
+    layers::{device::DeviceRepoLayer, device_log::DeviceLogRepoLayer},
+    models::{ApprovalStatus, Device},
+    repositories::{device::MockDeviceRepository, device_log::MockDeviceLogRepository},
+};
+
+const LEGACY_PAYLOAD: &str = r#"{
+    "log": {
+        "logs_array": [
+            {
+                "creation_timestamp": 1700000000,
+                "device_status_stamp": {
+                    "wifi_rssi_level": -54,
+                    "wifi_status": "connected",
+                    "refresh_rate": 900,
+                    "time_since_last_sleep_start": 901,
+                    "current_fw_version": "1.6.5",
+                    "special_function": "none",
+                    "battery_voltage": 4.1,
+                    "wakeup_reason": "timer",
+                    "free_heap_size": 160000
+                },
+                "log_id": 7,
+                "log_message": "Failed to download image",
+                "log_codeline": 585,
+                "log_sourcefile": "src/bl.cpp",
+                "additional_info": { "retry_attempt": 2 }
+            }
+        ]
+    }
+}"#;
+
+fn device() -> Device {
+    Device {
+        id: "dev123".to_string(),
+        mac: None,
+        _api_key: "valid-token".to_string(),
+        rssi: None,
+        battery_voltage: None,
+        fw_version: None,
+        refresh_rate: None,
+        images: vec![],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: None,
+        last_seen_at: None,
+    }
+}
+
+fn log_request(token: &str, body: &'static str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/api/log")
+        .header(&HEADER_ACCESS_TOKEN, token)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success_legacy_payload() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("valid-token"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device())) }));
+
+    mock_log_repo
+        .expect_create()
+        .withf(|device_id, _, logs| {
+            let log = &logs[0];
+            device_id == "dev123"
+                && logs.len() == 1
+                && log.created_at == 1_700_000_000
+                && log.level == "info"
+                && log.message == "Failed to download image"
+                && log.source_file.as_deref() == Some("src/bl.cpp")
+                && log.source_line == Some(585)
+                && log.wake_reason.as_deref() == Some("timer")
+                && log.battery_voltage == Some(4.1)
+                && log.wifi_status.as_deref() == Some("connected")
+                && log.wifi_rssi == Some(-54)
+                && log.fw_version.as_deref() == Some("1.6.5")
+        })
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(log_request("valid-token", LEGACY_PAYLOAD))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["status"], 200);
+}
+
+#[tokio::test]
+async fn success_current_payload_without_clock() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device())) }));
+
+    mock_log_repo
+        .expect_create()
+        .withf(|_, received_at, logs| {
+            logs.len() == 1 && logs[0].created_at == *received_at && logs[0].level == "error"
+        })
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(log_request(
+            "valid-token",
+            r#"{"logs":[{"creation_timestamp":0,"log_message":"WiFi connect failed","log_level":"ERROR"}]}"#,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn error_unknown_device() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_api_key()
+        .with(predicate::eq("invalid-token"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_log_repo.expect_create().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(log_request("invalid-token", LEGACY_PAYLOAD))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn error_invalid_payload() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device())) }));
+
+    mock_log_repo.expect_create().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(log_request("valid-token", "not json"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_log_repo = MockDeviceLogRepository::new();
+
+    mock_device_repo
+        .expect_get_by_api_key()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device())) }));
+
+    mock_log_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceLogRepoLayer(Arc::new(mock_log_repo)))
+        .oneshot(log_request("valid-token", LEGACY_PAYLOAD))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
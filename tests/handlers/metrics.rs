@@ -0,0 +1,131 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use axum::{
+    Extension,
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::device::DeviceRepoLayer,
+    metrics::HttpMetrics,
+    models::{ApprovalStatus, Device},
+    repositories::device::MockDeviceRepository,
+    utils::unix_timestamp,
+};
+
+use super::approve_device::device;
+
+async fn get_metrics(
+    device_repo: MockDeviceRepository,
+    metrics: HttpMetrics,
+) -> axum::response::Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(Arc::new(metrics)))
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .oneshot(
+            super::authorized_request()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut device_repo = MockDeviceRepository::new();
+
+    device_repo.expect_list().times(1).returning(|| {
+        Box::pin(async {
+            Ok(vec![
+                Device {
+                    rssi: Some(-62),
+                    battery_voltage: Some(3.9),
+                    fw_version: Some("1.6.5".to_string()),
+                    refresh_rate: Some(900),
+                    desired_refresh_rate: Some(1800),
+                    last_seen_at: Some(unix_timestamp() - 60),
+                    ..device(ApprovalStatus::Approved)
+                },
+                Device {
+                    id: "dev456".to_string(),
+                    ..device(ApprovalStatus::Pending)
+                },
+            ])
+        })
+    });
+
+    let metrics = HttpMetrics::new();
+    metrics.record("GET", "/api/display", 200, Duration::from_millis(20));
+
+    let response = get_metrics(device_repo, metrics).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4")
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(body.contains(
+        "trmnl_http_requests_total{method=\"GET\",route=\"/api/display\",status=\"200\"} 1\n"
+    ));
+    assert!(body.contains(
+        "trmnl_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/display\",status=\"200\",le=\"0.01\"} 0\n"
+    ));
+    assert!(body.contains(
+        "trmnl_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/display\",status=\"200\",le=\"0.025\"} 1\n"
+    ));
+    assert!(body.contains("trmnl_device_battery_voltage{device_id=\"dev123\"} 3.9\n"));
+    assert!(body.contains("trmnl_device_rssi{device_id=\"dev123\"} -62\n"));
+    assert!(body.contains("trmnl_device_refresh_rate_seconds{device_id=\"dev123\"} 1800\n"));
+    assert!(body.lines().any(|line| {
+        line.starts_with("trmnl_device_seconds_since_last_poll{device_id=\"dev123\"} 6")
+    }));
+    assert!(body.contains("trmnl_device_info{device_id=\"dev123\",fw_version=\"1.6.5\"} 1\n"));
+    assert!(body.contains("trmnl_device_info{device_id=\"dev456\",fw_version=\"\"} 1\n"));
+    // Devices without readings are left out rather than reported as zero
+    assert!(!body.contains("trmnl_device_rssi{device_id=\"dev456\"}"));
+    assert!(!body.contains("trmnl_device_seconds_since_last_poll{device_id=\"dev456\"}"));
+}
+
+#[tokio::test]
+async fn error_unauthorized() {
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .oneshot(
+            axum::http::Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn error_repository_failure() {
+    let mut device_repo = MockDeviceRepository::new();
+
+    device_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("Database error")) }));
+
+    let response = get_metrics(device_repo, HttpMetrics::new()).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
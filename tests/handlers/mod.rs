@@ -1,6 +1,83 @@
+mod approve_device;
+mod convert_images;
+mod create_api_token;
+mod create_device_group;
+mod create_layout;
+mod delete_api_token;
+mod delete_device;
+mod delete_device_group;
+mod delete_firmware;
+mod delete_image;
+mod delete_layout;
 mod display;
+mod enqueue_device_command;
 mod get_device;
+mod get_device_group;
 mod get_device_images;
+mod get_device_logs;
+mod get_device_schedule;
+mod get_device_telemetry;
+mod get_firmware_binary;
+mod get_image;
+mod get_image_preview;
+mod get_layout;
+mod healthz;
+mod list_alerts;
+mod list_api_tokens;
+mod list_device_commands;
+mod list_device_groups;
 mod list_devices;
+mod list_firmware;
+mod list_images;
+mod list_layouts;
+mod log;
+mod metrics;
+mod patch_device;
+mod patch_device_group;
+mod pending_approval;
+mod preview_image;
+mod preview_layout;
+mod put_device_group_devices;
+mod put_device_group_images;
 mod put_device_images;
+mod put_device_schedule;
+mod put_firmware;
+mod put_layout;
+mod readyz;
+mod reject_device;
+mod render_layout;
+mod rotate_device_key;
 mod setup;
+mod sleeping_screen;
+mod upload_images;
+
+use std::sync::Arc;
+
+use axum::http::{Request, header, request::Builder};
+use trmnl_server::{
+    auth::hash_api_token, layers::api_token::ApiTokenRepoLayer, models::ApiToken,
+    repositories::api_token::MockApiTokenRepository,
+};
+
+pub const API_TOKEN: &str = "test-api-token";
+
+/// Request builder authenticated with [`API_TOKEN`]
+pub fn authorized_request() -> Builder {
+    Request::builder().header(header::AUTHORIZATION, format!("Bearer {API_TOKEN}"))
+}
+
+/// Token repository that only accepts [`API_TOKEN`]
+pub fn api_token_layer() -> ApiTokenRepoLayer {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo.expect_get_by_hash().returning(|token_hash| {
+        let token = (token_hash == hash_api_token(API_TOKEN)).then(|| ApiToken {
+            id: "token123".to_string(),
+            name: "test".to_string(),
+            created_at: 1_700_000_000,
+        });
+        Box::pin(async move { Ok(token) })
+    });
+
+    ApiTokenRepoLayer(Arc::new(mock_repo))
+}
@@ -0,0 +1,655 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    Extension,
+    body::{Body, to_bytes},
+    http::StatusCode,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    config::StatusSettings,
+    layers::{
+        device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer, firmware::FirmwareRepoLayer,
+    },
+    models::{ApprovalStatus, Device, DevicePatch, Firmware},
+    repositories::{
+        device::MockDeviceRepository, device_group::MockDeviceGroupRepository,
+        firmware::MockFirmwareRepository,
+    },
+    schedule::QuietHours,
+};
+
+fn firmware(version: &str) -> Firmware {
+    Firmware {
+        version: version.to_string(),
+        checksum: "abc".to_string(),
+        size: 3,
+        created_at: 0,
+    }
+}
+
+fn device(desired_refresh_rate: Option<i64>) -> Device {
+    Device {
+        id: "dev123".to_string(),
+        mac: Some("AA:BB:CC:DD:EE:FF".to_string()),
+        _api_key: "abc123".to_string(),
+        rssi: Some(-70),
+        battery_voltage: Some(3.7),
+        fw_version: Some("1.0.0".to_string()),
+        refresh_rate: Some(900),
+        images: vec![],
+        image_cursor: 0,
+        desired_refresh_rate,
+        target_fw_version: None,
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: None,
+        last_seen_at: None,
+    }
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                desired_refresh_rate: Some(Some(3600)),
+                target_fw_version: None,
+                timezone: None,
+                quiet_hours: None,
+                group_id: None,
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device(Some(3600)))) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"desired_refresh_rate":3600}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json["id"], "dev123");
+    assert_eq!(json["refresh_rate"], 900);
+    assert_eq!(json["desired_refresh_rate"], 3600);
+}
+
+#[tokio::test]
+async fn success_clear() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                desired_refresh_rate: Some(None),
+                target_fw_version: None,
+                timezone: None,
+                quiet_hours: None,
+                group_id: None,
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device(None))) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"desired_refresh_rate":null}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert!(json["desired_refresh_rate"].is_null());
+}
+
+#[tokio::test]
+async fn success_target_fw_version() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_firmware_repo = MockFirmwareRepository::new();
+
+    mock_firmware_repo
+        .expect_get()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|version| {
+            let firmware = firmware(version);
+            Box::pin(async move { Ok(Some(firmware)) })
+        });
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                desired_refresh_rate: None,
+                target_fw_version: Some(Some("1.6.5".to_string())),
+                timezone: None,
+                quiet_hours: None,
+                group_id: None,
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| {
+            let mut device = device(None);
+            device.target_fw_version = Some("1.6.5".to_string());
+            Box::pin(async move { Ok(Some(device)) })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(mock_firmware_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"target_fw_version":"1.6.5"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    assert_eq!(json["target_fw_version"], "1.6.5");
+}
+
+#[tokio::test]
+async fn error_unknown_target_fw_version() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_firmware_repo = MockFirmwareRepository::new();
+
+    mock_firmware_repo
+        .expect_get()
+        .with(predicate::eq("9.9.9"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_device_repo.expect_update().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(mock_firmware_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"target_fw_version":"9.9.9"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_invalid_target_fw_version() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo.expect_update().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"target_fw_version":"latest"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_update()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"desired_refresh_rate":3600}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error_invalid_refresh_rate() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo.expect_update().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"desired_refresh_rate":0}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn success_timezone() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                timezone: Some(Some("Europe/Amsterdam".to_string())),
+                ..Default::default()
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo.expect_get_by_id().times(1).returning(|_| {
+        let mut device = device(None);
+        device.timezone = Some("Europe/Amsterdam".to_string());
+        Box::pin(async move { Ok(Some(device)) })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"timezone":"Europe/Amsterdam"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["timezone"], "Europe/Amsterdam");
+}
+
+#[tokio::test]
+async fn error_invalid_timezone() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo.expect_update().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"timezone":"Europe/Atlantis"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn success_quiet_hours() {
+    let quiet_hours = QuietHours {
+        start: "22:00".parse().unwrap(),
+        end: "06:30".parse().unwrap(),
+        sleep_screen: true,
+    };
+
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                quiet_hours: Some(Some(quiet_hours.clone())),
+                ..Default::default()
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(move |_| {
+            let mut device = device(None);
+            device.quiet_hours = Some(quiet_hours.clone());
+            Box::pin(async move { Ok(Some(device)) })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"quiet_hours":{"start":"22:00","end":"06:30","sleep_screen":true}}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["quiet_hours"]["start"], "22:00");
+    assert_eq!(json["quiet_hours"]["end"], "06:30");
+    assert_eq!(json["quiet_hours"]["sleep_screen"], true);
+}
+
+#[tokio::test]
+async fn error_empty_quiet_hours() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo.expect_update().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"quiet_hours":{"start":"22:00","end":"22:00"}}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn success_group_id() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+
+    mock_group_repo
+        .expect_get()
+        .with(predicate::eq("group123"))
+        .times(2)
+        .returning(|_| Box::pin(async { Ok(Some(super::get_device_group::device_group(vec![]))) }));
+
+    mock_device_repo
+        .expect_update()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(DevicePatch {
+                group_id: Some(Some("group123".to_string())),
+                ..Default::default()
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    mock_device_repo.expect_get_by_id().times(1).returning(|_| {
+        let mut device = device(None);
+        device.group_id = Some("group123".to_string());
+        Box::pin(async move { Ok(Some(device)) })
+    });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_group_repo)))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"group_id":"group123"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["group_id"], "group123");
+}
+
+#[tokio::test]
+async fn error_unknown_group() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+
+    mock_group_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_device_repo.expect_update().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_group_repo)))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"group_id":"group404"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_update()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/devices/dev123")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"desired_refresh_rate":3600}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
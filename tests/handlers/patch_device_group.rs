@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device_group::DeviceGroupRepoLayer, firmware::FirmwareRepoLayer},
+    models::{DeviceGroup, DeviceGroupPatch},
+    repositories::{device_group::MockDeviceGroupRepository, firmware::MockFirmwareRepository},
+};
+
+use super::get_device_group::device_group;
+
+async fn patch(mock_repo: MockDeviceGroupRepository, body: &'static str) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .layer(FirmwareRepoLayer(Arc::new(MockFirmwareRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PATCH")
+                .uri("/api/groups/group123")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_update()
+        .with(
+            predicate::eq("group123"),
+            predicate::eq(DeviceGroupPatch {
+                name: Some("Reception".to_string()),
+                desired_refresh_rate: Some(Some(600)),
+                target_fw_version: None,
+            }),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    mock_repo.expect_get().times(1).returning(|_| {
+        let mut group = device_group(vec![]);
+        group.name = "Reception".to_string();
+        group.desired_refresh_rate = Some(600);
+        Box::pin(async move { Ok(Some(group)) })
+    });
+
+    let response = patch(
+        mock_repo,
+        r#"{"name":" Reception ","desired_refresh_rate":600}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let group: DeviceGroup = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(group.name, "Reception");
+    assert_eq!(group.desired_refresh_rate, Some(600));
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_update()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    assert_eq!(
+        patch(mock_repo, r#"{"desired_refresh_rate":600}"#)
+            .await
+            .status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error_invalid_refresh_rate() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo.expect_update().times(0);
+
+    assert_eq!(
+        patch(mock_repo, r#"{"desired_refresh_rate":0}"#)
+            .await
+            .status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
+
+#[tokio::test]
+async fn error_empty_name() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo.expect_update().times(0);
+
+    assert_eq!(
+        patch(mock_repo, r#"{"name":""}"#).await.status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_update()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        patch(mock_repo, r#"{"desired_refresh_rate":600}"#)
+            .await
+            .status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
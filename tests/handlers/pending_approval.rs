@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::device::DeviceRepoLayer, models::ApprovalStatus,
+    repositories::device::MockDeviceRepository,
+};
+
+use super::approve_device::device;
+
+async fn pending_screen(mock_repo: MockDeviceRepository) -> Response {
+    App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            Request::builder()
+                .uri("/pending/dev123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Checks the response is a screen devices can show, shared with the other message screens
+pub async fn assert_screen(response: Response) {
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/bmp");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..2], b"BM");
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device(ApprovalStatus::Pending))) }));
+
+    assert_screen(pending_screen(mock_repo).await).await;
+}
+
+#[tokio::test]
+async fn success_not_pending() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device(ApprovalStatus::Approved))) }));
+
+    assert_eq!(
+        pending_screen(mock_repo).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        pending_screen(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -0,0 +1,66 @@
+use std::io::Cursor;
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+    response::Response,
+};
+use image::{GrayImage, ImageFormat, Luma};
+use tower::ServiceExt;
+use trmnl_server::app::App;
+
+fn png() -> Vec<u8> {
+    let mut data = Cursor::new(Vec::new());
+    GrayImage::from_pixel(40, 24, Luma([0]))
+        .write_to(&mut data, ImageFormat::Png)
+        .unwrap();
+    data.into_inner()
+}
+
+async fn preview(query: &str, body: Vec<u8>) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri(format!("/api/images/preview{query}"))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let response = preview("?resize=fill&dither=atkinson&contrast=1.5", png()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/bmp");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[0..2], b"BM");
+    assert_eq!(body.len(), 48062);
+}
+
+#[tokio::test]
+async fn error_invalid_image() {
+    let response = preview("", b"not an image".to_vec()).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_invalid_options() {
+    let response = preview("?brightness=3", png()).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_unknown_dither() {
+    let response = preview("?dither=sierra", png()).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{StatusCode, header},
+    response::Response,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::image::ImageRepoLayer, repositories::image::MockImageRepository,
+};
+
+async fn preview(image_repo: MockImageRepository, body: &'static str) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(ImageRepoLayer(Arc::new(image_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/layouts/preview")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let response = preview(
+        MockImageRepository::new(),
+        r#"{"elements":[{"type":"text","x":10,"y":10,"text":"Hello"}]}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/bmp");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[0..2], b"BM");
+    assert_eq!(body.len(), 48062);
+}
+
+#[tokio::test]
+async fn error_unknown_image() {
+    let mut image_repo = MockImageRepository::new();
+
+    image_repo
+        .expect_get_content()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    let response = preview(
+        image_repo,
+        r#"{"elements":[{"type":"image","x":0,"y":0,"width":10,"height":10,"image":"missing"}]}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_invalid_spec() {
+    let response = preview(
+        MockImageRepository::new(),
+        r#"{"elements":[{"type":"line","x1":0,"y1":0,"x2":10,"y2":10,"thickness":500}]}"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use serde_json::Value;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer},
+    repositories::{device::MockDeviceRepository, device_group::MockDeviceGroupRepository},
+};
+
+use super::get_device_group::device_group;
+
+fn existing_group() -> MockDeviceGroupRepository {
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+
+    mock_group_repo
+        .expect_get()
+        .with(predicate::eq("group123"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device_group(vec![]))) }));
+
+    mock_group_repo
+}
+
+async fn put(
+    mock_repo: MockDeviceRepository,
+    mock_group_repo: MockDeviceGroupRepository,
+    body: &'static str,
+) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_group_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/groups/group123/devices")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_set_group_members()
+        .with(
+            predicate::eq("group123"),
+            predicate::eq(vec!["dev1".to_string(), "dev2".to_string()]),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    let response = put(mock_repo, existing_group(), r#"["dev1","dev2"]"#).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json, serde_json::json!(["dev1", "dev2"]));
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_group_repo = MockDeviceGroupRepository::new();
+
+    mock_group_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(
+        put(MockDeviceRepository::new(), mock_group_repo, r#"["dev1"]"#)
+            .await
+            .status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error_unknown_device() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_set_group_members()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    let response = put(mock_repo, existing_group(), r#"["dev1","unknown"]"#).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["detail"], "Unknown device ID");
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_set_group_members()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        put(mock_repo, existing_group(), r#"["dev1"]"#)
+            .await
+            .status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
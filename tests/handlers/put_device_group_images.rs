@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use serde_json::Value;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device_group::DeviceGroupRepoLayer, image::ImageRepoLayer, layout::LayoutRepoLayer},
+    repositories::{
+        device_group::MockDeviceGroupRepository, image::MockImageRepository,
+        layout::MockLayoutRepository,
+    },
+};
+
+async fn put(mock_repo: MockDeviceGroupRepository, body: &'static str) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(MockLayoutRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/groups/group123/images")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_update_images()
+        .with(
+            predicate::eq("group123"),
+            predicate::eq(vec!["one.jpg".to_string(), "two.jpg".to_string()]),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    let response = put(mock_repo, r#"["one.jpg","two.jpg"]"#).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json, serde_json::json!(["one.jpg", "two.jpg"]));
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_update_images()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    assert_eq!(
+        put(mock_repo, r#"["one.jpg"]"#).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceGroupRepository::new();
+
+    mock_repo
+        .expect_update_images()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        put(mock_repo, r#"["one.jpg"]"#).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
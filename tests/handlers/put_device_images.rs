@@ -3,13 +3,19 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use axum::{
     body::{Body, to_bytes},
-    http::{Request, StatusCode},
+    http::StatusCode,
 };
 use mockall::predicate;
 use serde_json::Value;
 use tower::ServiceExt;
 use trmnl_server::{
-    app::App, layers::device::DeviceRepoLayer, repositories::device::MockDeviceRepository,
+    app::App,
+    layers::{device::DeviceRepoLayer, image::ImageRepoLayer, layout::LayoutRepoLayer},
+    layout::LayoutSpec,
+    models::{Image, Layout},
+    repositories::{
+        device::MockDeviceRepository, image::MockImageRepository, layout::MockLayoutRepository,
+    },
 };
 
 #[tokio::test]
@@ -27,9 +33,12 @@ async fn success() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(MockLayoutRepository::new())))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .method("PUT")
                 .uri("/api/devices/dev123/images")
                 .header("content-type", "application/json")
@@ -62,9 +71,12 @@ async fn error() {
 
     let response = App::new()
         .router()
+        .layer(super::api_token_layer())
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(MockLayoutRepository::new())))
         .oneshot(
-            Request::builder()
+            super::authorized_request()
                 .method("PUT")
                 .uri("/api/devices/dev123/images")
                 .header("content-type", "application/json")
@@ -76,3 +88,168 @@ async fn error() {
 
     assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
 }
+
+#[tokio::test]
+async fn success_uploaded_image() {
+    let mut mock_repo = MockDeviceRepository::new();
+    let mut mock_image_repo = MockImageRepository::new();
+
+    mock_image_repo
+        .expect_get()
+        .with(predicate::eq("abc123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Image {
+                    id: "abc123".to_string(),
+                    content_type: "image/bmp".to_string(),
+                    size: 48062,
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_images()
+        .with(
+            predicate::eq("dev123".to_string()),
+            predicate::eq(vec!["image:abc123".to_string(), "two.jpg".to_string()]),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(mock_image_repo)))
+        .layer(LayoutRepoLayer(Arc::new(MockLayoutRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/devices/dev123/images")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"["image:abc123","two.jpg"]"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn error_unknown_image() {
+    let mut mock_repo = MockDeviceRepository::new();
+    let mut mock_image_repo = MockImageRepository::new();
+
+    mock_image_repo
+        .expect_get()
+        .with(predicate::eq("missing"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo.expect_update_images().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(mock_image_repo)))
+        .layer(LayoutRepoLayer(Arc::new(MockLayoutRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/devices/dev123/images")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"["image:missing"]"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn success_layout() {
+    let mut mock_repo = MockDeviceRepository::new();
+    let mut mock_layout_repo = MockLayoutRepository::new();
+
+    mock_layout_repo
+        .expect_get()
+        .with(predicate::eq("layout123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Layout {
+                    id: "layout123".to_string(),
+                    name: "Dashboard".to_string(),
+                    spec: serde_json::from_str::<LayoutSpec>(r#"{"elements":[]}"#).unwrap(),
+                    created_at: 1_700_000_000,
+                    updated_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_update_images()
+        .with(
+            predicate::eq("dev123".to_string()),
+            predicate::eq(vec!["layout:layout123".to_string()]),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(()) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(mock_layout_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/devices/dev123/images")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"["layout:layout123"]"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn error_unknown_layout() {
+    let mut mock_repo = MockDeviceRepository::new();
+    let mut mock_layout_repo = MockLayoutRepository::new();
+
+    mock_layout_repo
+        .expect_get()
+        .with(predicate::eq("missing"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo.expect_update_images().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(mock_layout_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/devices/dev123/images")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"["layout:missing"]"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
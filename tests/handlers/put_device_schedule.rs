@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use serde_json::Value;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{device::DeviceRepoLayer, image::ImageRepoLayer, layout::LayoutRepoLayer},
+    repositories::{
+        device::MockDeviceRepository, image::MockImageRepository, layout::MockLayoutRepository,
+    },
+    schedule::{ScheduleRule, Weekday},
+};
+
+const SCHEDULE: &str = r#"[
+    {"weekdays": ["mon", "tue", "wed", "thu", "fri"], "start": "08:00", "end": "18:00", "images": ["layout:meetings"]},
+    {"start": "18:00", "end": "08:00", "images": ["https://example.com/photo.png"]}
+]"#;
+
+async fn put_schedule(
+    mock_repo: MockDeviceRepository,
+    layout_repo: MockLayoutRepository,
+    body: &'static str,
+) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .layer(LayoutRepoLayer(Arc::new(layout_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/devices/dev123/schedule")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn layout_repo(found: bool) -> MockLayoutRepository {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("meetings"))
+        .times(1)
+        .returning(move |id| {
+            let layout = found.then(|| trmnl_server::models::Layout {
+                id: id.to_string(),
+                name: "Meetings".to_string(),
+                spec: trmnl_server::layout::LayoutSpec {
+                    background: Default::default(),
+                    width: 800,
+                    height: 480,
+                    elements: vec![],
+                },
+                created_at: 0,
+                updated_at: 0,
+            });
+            Box::pin(async move { Ok(layout) })
+        });
+
+    mock_repo
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_schedule()
+        .withf(|id, schedule: &[ScheduleRule]| {
+            id == "dev123"
+                && schedule.len() == 2
+                && schedule[0].weekdays.len() == 5
+                && schedule[0].weekdays[0] == Weekday::Mon
+                && schedule[1].weekdays.is_empty()
+        })
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    let response = put_schedule(mock_repo, layout_repo(true), SCHEDULE).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json[0]["start"], "08:00");
+    assert_eq!(json[1]["end"], "08:00");
+    assert_eq!(json[1]["weekdays"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_schedule()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    let response = put_schedule(mock_repo, layout_repo(true), SCHEDULE).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error_unknown_layout() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_update_schedule().times(0);
+
+    let response = put_schedule(mock_repo, layout_repo(false), SCHEDULE).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_invalid_time() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_update_schedule().times(0);
+
+    let response = put_schedule(
+        mock_repo,
+        MockLayoutRepository::new(),
+        r#"[{"start": "25:00", "end": "08:00", "images": ["a.png"]}]"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_empty_rotation() {
+    let mut mock_repo = MockDeviceRepository::new();
+    mock_repo.expect_update_schedule().times(0);
+
+    let response = put_schedule(
+        mock_repo,
+        MockLayoutRepository::new(),
+        r#"[{"start": "08:00", "end": "18:00", "images": []}]"#,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_schedule()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = put_schedule(mock_repo, layout_repo(true), SCHEDULE).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
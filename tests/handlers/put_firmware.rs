@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use mockall::predicate;
+use serde_json::Value;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::firmware::FirmwareRepoLayer, models::Firmware,
+    repositories::firmware::MockFirmwareRepository,
+};
+
+// sha256("firmware")
+const CHECKSUM: &str = "c3bf47ea1f4a4a605470313cacb3a44f4a461f68c6faeab07e737610cb5ac835";
+
+fn put_request(version: &str, body: &'static str) -> Request<Body> {
+    super::authorized_request()
+        .method("PUT")
+        .uri(format!("/api/firmware/{version}"))
+        .header("content-type", "application/octet-stream")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockFirmwareRepository::new();
+    let mut seq = mockall::Sequence::new();
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo
+        .expect_create()
+        .withf(|version, checksum, data| {
+            version == "1.6.5" && checksum == CHECKSUM && data == b"firmware"
+        })
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Firmware {
+                    version: "1.6.5".to_string(),
+                    checksum: CHECKSUM.to_string(),
+                    size: 8,
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(put_request("1.6.5", "firmware"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["version"], "1.6.5");
+    assert_eq!(json["size"], 8);
+}
+
+#[tokio::test]
+async fn error_already_exists() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("1.6.5"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Firmware {
+                    version: "1.6.5".to_string(),
+                    checksum: CHECKSUM.to_string(),
+                    size: 8,
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo.expect_create().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(put_request("1.6.5", "firmware"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn error_invalid_version() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(put_request("latest", "firmware"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error_empty_body() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(put_request("1.6.5", ""))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockFirmwareRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(FirmwareRepoLayer(Arc::new(mock_repo)))
+        .oneshot(put_request("1.6.5", "firmware"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
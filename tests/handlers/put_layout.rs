@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{image::ImageRepoLayer, layout::LayoutRepoLayer},
+    models::Layout,
+    repositories::{image::MockImageRepository, layout::MockLayoutRepository},
+};
+
+const BODY: &str =
+    r#"{"name":"Renamed","spec":{"elements":[{"type":"line","x1":0,"y1":0,"x2":10,"y2":10}]}}"#;
+
+async fn put(mock_repo: MockLayoutRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .oneshot(
+            super::authorized_request()
+                .method("PUT")
+                .uri("/api/layouts/layout123")
+                .header("content-type", "application/json")
+                .body(Body::from(BODY))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_update()
+        .withf(|id, name, spec| id == "layout123" && name == "Renamed" && spec.elements.len() == 1)
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(true) }));
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("layout123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Layout {
+                    id: "layout123".to_string(),
+                    name: "Renamed".to_string(),
+                    spec: serde_json::from_str(r#"{"elements":[]}"#).unwrap(),
+                    created_at: 1_700_000_000,
+                    updated_at: 1_700_000_100,
+                }))
+            })
+        });
+
+    let response = put(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let layout: Layout = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(layout.name, "Renamed");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_update()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(false) }));
+
+    mock_repo.expect_get().times(0);
+
+    assert_eq!(put(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_update()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        put(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    db::latest_migration_version,
+    layers::{device::DeviceRepoLayer, health::HealthRepoLayer},
+    models::{HealthStatus, ReadinessReport},
+    repositories::{device::MockDeviceRepository, health::MockHealthRepository},
+};
+
+fn device_repo(ping: anyhow::Result<()>) -> MockDeviceRepository {
+    let mut mock_repo = MockDeviceRepository::new();
+    let reachable = ping.is_ok();
+    let ping = ping.map_err(|error| error.to_string());
+
+    mock_repo.expect_ping().times(1).returning(move || {
+        let ping = ping.clone().map_err(|error| anyhow!(error));
+        Box::pin(async move { ping })
+    });
+
+    if reachable {
+        mock_repo
+            .expect_latest_migration()
+            .returning(|| Some(20251016090000));
+        mock_repo
+            .expect_applied_migration()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(Some(20251016090000)) }));
+    }
+
+    mock_repo
+}
+
+async fn readyz(mock_repo: MockHealthRepository) -> (StatusCode, ReadinessReport) {
+    readyz_with_devices(mock_repo, device_repo(Ok(()))).await
+}
+
+async fn readyz_with_devices(
+    mock_repo: MockHealthRepository,
+    device_repo: MockDeviceRepository,
+) -> (StatusCode, ReadinessReport) {
+    let response = App::new()
+        .router()
+        .layer(HealthRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceRepoLayer(Arc::new(device_repo)))
+        .oneshot(
+            Request::builder()
+                .uri("/readyz")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockHealthRepository::new();
+
+    mock_repo
+        .expect_ping()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_applied_migration()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(latest_migration_version()) }));
+
+    let (status, report) = readyz(mock_repo).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report.status, HealthStatus::Ok);
+    assert_eq!(report.components.database.status, HealthStatus::Ok);
+    assert_eq!(report.components.migrations.status, HealthStatus::Ok);
+    assert_eq!(report.components.devices.status, HealthStatus::Ok);
+}
+
+#[tokio::test]
+async fn error_database_unreachable() {
+    let mut mock_repo = MockHealthRepository::new();
+
+    mock_repo
+        .expect_ping()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("Database error")) }));
+
+    mock_repo
+        .expect_applied_migration()
+        .times(1)
+        .returning(|| Box::pin(async { Err(anyhow!("Database error")) }));
+
+    let (status, report) = readyz(mock_repo).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.components.database.status, HealthStatus::Error);
+    assert_eq!(
+        report.components.database.message.as_deref(),
+        Some("Database is not responding")
+    );
+    assert_eq!(report.components.migrations.status, HealthStatus::Error);
+}
+
+#[tokio::test]
+async fn error_pending_migrations() {
+    let mut mock_repo = MockHealthRepository::new();
+
+    mock_repo
+        .expect_ping()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_applied_migration()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(Some(20250908205102)) }));
+
+    let (status, report) = readyz(mock_repo).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.components.database.status, HealthStatus::Ok);
+    assert_eq!(report.components.migrations.status, HealthStatus::Error);
+    assert_eq!(
+        report.components.migrations.message,
+        Some(format!(
+            "Database is at migration 20250908205102, expected {}",
+            latest_migration_version().unwrap()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn error_device_store_unreachable() {
+    let mut mock_repo = MockHealthRepository::new();
+
+    mock_repo
+        .expect_ping()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_applied_migration()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(latest_migration_version()) }));
+
+    let (status, report) =
+        readyz_with_devices(mock_repo, device_repo(Err(anyhow!("Connection refused")))).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.components.database.status, HealthStatus::Ok);
+    assert_eq!(report.components.devices.status, HealthStatus::Error);
+    assert_eq!(
+        report.components.devices.message.as_deref(),
+        Some("Device store is not responding")
+    );
+}
+
+#[tokio::test]
+async fn error_device_store_pending_migrations() {
+    let mut mock_repo = MockHealthRepository::new();
+
+    mock_repo
+        .expect_ping()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_applied_migration()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(latest_migration_version()) }));
+
+    // A device store with migrations of its own is compared against those, not the SQLite ones
+    let mut device_repo = MockDeviceRepository::new();
+    device_repo
+        .expect_ping()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(()) }));
+    device_repo
+        .expect_latest_migration()
+        .returning(|| Some(20251016090000));
+    device_repo
+        .expect_applied_migration()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(Some(20251015090000)) }));
+
+    let (status, report) = readyz_with_devices(mock_repo, device_repo).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.components.migrations.status, HealthStatus::Ok);
+    assert_eq!(report.components.devices.status, HealthStatus::Error);
+    assert_eq!(
+        report.components.devices.message.as_deref(),
+        Some("Device store is at migration 20251015090000, expected 20251016090000")
+    );
+}
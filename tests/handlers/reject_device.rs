@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension,
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    config::StatusSettings,
+    layers::{device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer},
+    models::{ApprovalStatus, DeviceInfo},
+    repositories::{device::MockDeviceRepository, device_group::MockDeviceGroupRepository},
+};
+
+use super::approve_device::device;
+
+async fn reject(mock_repo: MockDeviceRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/devices/dev123/reject")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_approval_status()
+        .with(
+            predicate::eq("dev123"),
+            predicate::eq(ApprovalStatus::Rejected),
+        )
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(Some(device(ApprovalStatus::Rejected))) }));
+
+    let response = reject(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: DeviceInfo = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json.approval_status, ApprovalStatus::Rejected);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_update_approval_status()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    assert_eq!(reject(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
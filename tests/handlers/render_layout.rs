@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    layers::{image::ImageRepoLayer, layout::LayoutRepoLayer},
+    models::Layout,
+    repositories::{image::MockImageRepository, layout::MockLayoutRepository},
+};
+
+async fn render(mock_repo: MockLayoutRepository) -> Response {
+    App::new()
+        .router()
+        .layer(LayoutRepoLayer(Arc::new(mock_repo)))
+        .layer(ImageRepoLayer(Arc::new(MockImageRepository::new())))
+        .oneshot(
+            Request::builder()
+                .uri("/layouts/layout123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq("layout123"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Layout {
+                    id: "layout123".to_string(),
+                    name: "Dashboard".to_string(),
+                    spec: serde_json::from_str(
+                        r#"{"elements":[{"type":"icon","x":0,"y":0,"icon":"sun"}]}"#,
+                    )
+                    .unwrap(),
+                    created_at: 1_700_000_000,
+                    updated_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    let response = render(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/bmp");
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[0..2], b"BM");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(render(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockLayoutRepository::new();
+
+    mock_repo
+        .expect_get()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        render(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
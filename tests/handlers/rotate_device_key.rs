@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::device::DeviceRepoLayer, models::DeviceApiKey,
+    repositories::device::MockDeviceRepository,
+};
+
+async fn rotate(mock_repo: MockDeviceRepository) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/devices/dev123/rotate-key")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_rotate_api_key()
+        .withf(|id, api_key| id == "dev123" && api_key.len() == 22)
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(true) }));
+
+    let response = rotate(mock_repo).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let key: DeviceApiKey = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(key.id, "dev123");
+    assert_eq!(key.api_key.len(), 22);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_rotate_api_key()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Ok(false) }));
+
+    assert_eq!(rotate(mock_repo).await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_rotate_api_key()
+        .times(1)
+        .returning(|_, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        rotate(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
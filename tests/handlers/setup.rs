@@ -10,7 +10,11 @@ use mockall::predicate;
 use serde_json::Value;
 use tower::ServiceExt;
 use trmnl_server::{
-    app::App, config::AppSettings, headers::HEADER_MAC, layers::device::DeviceRepoLayer,
+    app::App,
+    config::{AppSettings, ApprovalSettings},
+    headers::HEADER_MAC,
+    layers::device::DeviceRepoLayer,
+    models::{ApprovalStatus, Device},
     repositories::device::MockDeviceRepository,
 };
 
@@ -24,14 +28,22 @@ async fn success_already_exists() {
         .times(1)
         .returning(|_| Box::pin(async { Ok(true) }));
 
+    mock_repo
+        .expect_take_pending_setup()
+        .with(predicate::eq("AA:BB:CC:DD:EE:FF"))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
     let settings = AppSettings {
         setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
     };
 
     let response = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .layer(Extension(settings))
+        .layer(Extension(ApprovalSettings::default()))
         .oneshot(
             Request::builder()
                 .uri("/api/setup")
@@ -51,6 +63,77 @@ async fn success_already_exists() {
     assert!(json["api_key"].is_null());
 }
 
+#[tokio::test]
+async fn success_rotated_key() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_exists_by_mac()
+        .with(predicate::eq("AA:BB:CC:DD:EE:FF".to_string()))
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(true) }));
+
+    mock_repo
+        .expect_take_pending_setup()
+        .with(predicate::eq("AA:BB:CC:DD:EE:FF"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(Device {
+                    id: "dev123".to_string(),
+                    mac: Some("AA:BB:CC:DD:EE:FF".to_string()),
+                    _api_key: "rotated-key".to_string(),
+                    rssi: None,
+                    battery_voltage: None,
+                    fw_version: None,
+                    refresh_rate: None,
+                    images: vec![],
+                    image_cursor: 0,
+                    desired_refresh_rate: None,
+                    target_fw_version: None,
+                    approval_status: ApprovalStatus::Approved,
+                    schedule: vec![],
+                    timezone: None,
+                    quiet_hours: None,
+                    group_id: None,
+                    first_seen_at: None,
+                    last_seen_at: None,
+                }))
+            })
+        });
+
+    mock_repo.expect_create().times(0);
+
+    let settings = AppSettings {
+        setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
+    };
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(Extension(settings))
+        .layer(Extension(ApprovalSettings::default()))
+        .oneshot(
+            Request::builder()
+                .uri("/api/setup")
+                .header(HEADER_MAC, "AA:BB:CC:DD:EE:FF")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["status"], 200);
+    assert_eq!(json["api_key"], "rotated-key");
+    assert_eq!(json["friendly_id"], "dev123");
+}
+
 #[tokio::test]
 async fn success_created_physical() {
     let mut mock_repo = MockDeviceRepository::new();
@@ -63,17 +146,20 @@ async fn success_created_physical() {
 
     mock_repo
         .expect_create()
+        .withf(|_, _, _, approval_status| *approval_status == ApprovalStatus::Approved)
         .times(1)
-        .returning(|_id, _mac, _api_key| Box::pin(async { Ok(()) }));
+        .returning(|_id, _mac, _api_key, _approval_status| Box::pin(async { Ok(()) }));
 
     let settings = AppSettings {
         setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
     };
 
     let response = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .layer(Extension(settings))
+        .layer(Extension(ApprovalSettings::default()))
         .oneshot(
             Request::builder()
                 .uri("/api/setup")
@@ -96,6 +182,76 @@ async fn success_created_physical() {
     assert_eq!(json["filename"], "empty_state");
 }
 
+async fn register_with_approval(approval: ApprovalSettings, expected: ApprovalStatus) {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_exists_by_mac()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(false) }));
+
+    mock_repo
+        .expect_create()
+        .withf(move |_, mac, _, approval_status| {
+            *mac == Some("AA:BB:CC:DD:EE:FF") && *approval_status == expected
+        })
+        .times(1)
+        .returning(|_id, _mac, _api_key, _approval_status| Box::pin(async { Ok(()) }));
+
+    let settings = AppSettings {
+        setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
+    };
+
+    let response = App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .layer(Extension(settings))
+        .layer(Extension(approval))
+        .oneshot(
+            Request::builder()
+                .uri("/api/setup")
+                .header(HEADER_MAC, "AA:BB:CC:DD:EE:FF")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Pending devices still get a key so they can poll for the decision
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["status"], 200);
+    assert!(json["api_key"].is_string());
+}
+
+#[tokio::test]
+async fn success_created_pending_approval() {
+    register_with_approval(
+        ApprovalSettings {
+            required: true,
+            allowlist: vec!["11:22:33:44:55:66".to_string()],
+        },
+        ApprovalStatus::Pending,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn success_created_allowlisted() {
+    register_with_approval(
+        ApprovalSettings {
+            required: true,
+            allowlist: vec!["aa:bb:cc:dd:ee:ff".to_string()],
+        },
+        ApprovalStatus::Approved,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn success_created_virtual() {
     let mut mock_repo = MockDeviceRepository::new();
@@ -105,16 +261,18 @@ async fn success_created_virtual() {
     mock_repo
         .expect_create()
         .times(1)
-        .returning(|_id, _mac, _api_key| Box::pin(async { Ok(()) }));
+        .returning(|_id, _mac, _api_key, _approval_status| Box::pin(async { Ok(()) }));
 
     let settings = AppSettings {
         setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
     };
 
     let response = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .layer(Extension(settings))
+        .layer(Extension(ApprovalSettings::default()))
         .oneshot(
             Request::builder()
                 .uri("/api/setup")
@@ -148,12 +306,14 @@ async fn error_exists_by_mac() {
 
     let settings = AppSettings {
         setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
     };
 
     let response = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .layer(Extension(settings))
+        .layer(Extension(ApprovalSettings::default()))
         .oneshot(
             Request::builder()
                 .uri("/api/setup")
@@ -180,16 +340,20 @@ async fn error_create() {
     mock_repo
         .expect_create()
         .times(1)
-        .returning(|_id, _mac, _api_key| Box::pin(async { Err(anyhow!("DB Error")) }));
+        .returning(|_id, _mac, _api_key, _approval_status| {
+            Box::pin(async { Err(anyhow!("DB Error")) })
+        });
 
     let settings = AppSettings {
         setup_logo_url: "http://example.com/logo.png".to_string(),
+        base_url: "http://localhost:3000".to_string(),
     };
 
     let response = App::new()
         .router()
         .layer(DeviceRepoLayer(Arc::new(mock_repo)))
         .layer(Extension(settings))
+        .layer(Extension(ApprovalSettings::default()))
         .oneshot(
             Request::builder()
                 .uri("/api/setup")
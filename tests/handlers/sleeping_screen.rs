@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::device::DeviceRepoLayer, models::ApprovalStatus,
+    repositories::device::MockDeviceRepository, schedule::QuietHours,
+};
+
+use super::{approve_device::device, pending_approval::assert_screen};
+
+async fn sleeping_screen(mock_repo: MockDeviceRepository) -> Response {
+    App::new()
+        .router()
+        .layer(DeviceRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            Request::builder()
+                .uri("/sleeping/dev123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn quiet_hours(sleep_screen: bool) -> QuietHours {
+    QuietHours {
+        start: "22:00".parse().unwrap(),
+        end: "06:30".parse().unwrap(),
+        sleep_screen,
+    }
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_| {
+            let mut device = device(ApprovalStatus::Approved);
+            device.quiet_hours = Some(quiet_hours(true));
+            Box::pin(async move { Ok(Some(device)) })
+        });
+
+    assert_screen(sleeping_screen(mock_repo).await).await;
+}
+
+#[tokio::test]
+async fn success_sleep_screen_disabled() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo.expect_get_by_id().times(1).returning(|_| {
+        let mut device = device(ApprovalStatus::Approved);
+        device.quiet_hours = Some(quiet_hours(false));
+        Box::pin(async move { Ok(Some(device)) })
+    });
+
+    assert_eq!(
+        sleeping_screen(mock_repo).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    assert_eq!(
+        sleeping_screen(mock_repo).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockDeviceRepository::new();
+
+    mock_repo
+        .expect_get_by_id()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    assert_eq!(
+        sleeping_screen(mock_repo).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::{Body, to_bytes},
+    http::StatusCode,
+    response::Response,
+};
+use mockall::predicate;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, layers::image::ImageRepoLayer, models::Image,
+    repositories::image::MockImageRepository,
+};
+
+const BOUNDARY: &str = "trmnl-boundary";
+const BMP: &[u8] = b"BM\x36\x00\x00\x00fake-bitmap";
+
+fn multipart_body(parts: &[(&str, Option<&str>, &[u8])]) -> Vec<u8> {
+    let mut body = Vec::new();
+
+    for (name, file_name, data) in parts {
+        body.extend_from_slice(format!("--{BOUNDARY}\r\n").as_bytes());
+
+        match file_name {
+            Some(file_name) => body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{name}\"; filename=\"{file_name}\"\r\n\r\n"
+                )
+                .as_bytes(),
+            ),
+            None => body.extend_from_slice(
+                format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+            ),
+        }
+
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    }
+
+    body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+async fn upload(mock_repo: MockImageRepository, body: Vec<u8>) -> Response {
+    App::new()
+        .router()
+        .layer(super::api_token_layer())
+        .layer(ImageRepoLayer(Arc::new(mock_repo)))
+        .oneshot(
+            super::authorized_request()
+                .method("POST")
+                .uri("/api/images")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={BOUNDARY}"),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut mock_repo = MockImageRepository::new();
+    let hash = format!("{:x}", Sha256::digest(BMP));
+    let expected = hash.clone();
+
+    mock_repo
+        .expect_create()
+        .with(
+            predicate::eq(hash.clone()),
+            predicate::eq("image/bmp"),
+            predicate::eq(BMP.to_vec()),
+        )
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+    mock_repo
+        .expect_get()
+        .with(predicate::eq(hash))
+        .times(1)
+        .returning(|id| {
+            let id = id.to_string();
+            Box::pin(async move {
+                Ok(Some(Image {
+                    id,
+                    content_type: "image/bmp".to_string(),
+                    size: BMP.len() as i64,
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    let response = upload(
+        mock_repo,
+        multipart_body(&[
+            ("caption", None, b"ignored"),
+            ("file", Some("weather.bmp"), BMP),
+        ]),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let images: Vec<Image> = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].id, expected);
+}
+
+#[tokio::test]
+async fn error_unsupported_format() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = upload(
+        mock_repo,
+        multipart_body(&[("file", Some("weather.gif"), b"GIF89a")]),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn error_no_files() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo.expect_create().times(0);
+
+    let response = upload(mock_repo, multipart_body(&[("caption", None, b"nothing")])).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn error() {
+    let mut mock_repo = MockImageRepository::new();
+
+    mock_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _, _| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = upload(
+        mock_repo,
+        multipart_body(&[("file", Some("weather.bmp"), BMP)]),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
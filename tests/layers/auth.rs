@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App, auth::hash_api_token, layers::api_token::ApiTokenRepoLayer, models::ApiToken,
+    repositories::api_token::MockApiTokenRepository,
+};
+
+async fn list_tokens(
+    mock_repo: MockApiTokenRepository,
+    authorization: Option<&str>,
+) -> axum::response::Response {
+    let mut request = Request::builder().uri("/api/tokens");
+
+    if let Some(authorization) = authorization {
+        request = request.header(header::AUTHORIZATION, authorization);
+    }
+
+    App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(mock_repo)))
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn success_valid_token() {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo
+        .expect_get_by_hash()
+        .withf(|token_hash| token_hash == hash_api_token("secret"))
+        .times(1)
+        .returning(|_| {
+            Box::pin(async {
+                Ok(Some(ApiToken {
+                    id: "token123".to_string(),
+                    name: "test".to_string(),
+                    created_at: 1_700_000_000,
+                }))
+            })
+        });
+
+    mock_repo
+        .expect_list()
+        .times(1)
+        .returning(|| Box::pin(async { Ok(vec![]) }));
+
+    let response = list_tokens(mock_repo, Some("Bearer secret")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn error_missing_token() {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo.expect_get_by_hash().times(0);
+    mock_repo.expect_list().times(0);
+
+    let response = list_tokens(mock_repo, None).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+}
+
+#[tokio::test]
+async fn error_wrong_scheme() {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo.expect_get_by_hash().times(0);
+
+    let response = list_tokens(mock_repo, Some("Basic c2VjcmV0")).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn error_unknown_token() {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo
+        .expect_get_by_hash()
+        .times(1)
+        .returning(|_| Box::pin(async { Ok(None) }));
+
+    mock_repo.expect_list().times(0);
+
+    let response = list_tokens(mock_repo, Some("Bearer wrong")).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn error_lookup() {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo
+        .expect_get_by_hash()
+        .times(1)
+        .returning(|_| Box::pin(async { Err(anyhow!("DB Error")) }));
+
+    let response = list_tokens(mock_repo, Some("Bearer secret")).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn success_device_routes_unauthenticated() {
+    let mut mock_repo = MockApiTokenRepository::new();
+
+    mock_repo.expect_get_by_hash().times(0);
+
+    let response = App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(mock_repo)))
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,101 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, Response, StatusCode},
+    middleware,
+    routing::get,
+};
+use tower::ServiceExt;
+use tower_http::trace::TraceLayer;
+use tracing::Span;
+use trmnl_server::{
+    layers::metrics::{record_response, tag_route},
+    metrics::HttpMetrics,
+};
+
+fn traced(router: Router, metrics: Arc<HttpMetrics>) -> Router {
+    router
+        .layer(middleware::from_fn(tag_route))
+        .layer(TraceLayer::new_for_http().on_response(
+            move |response: &Response<Body>, latency: Duration, _span: &Span| {
+                record_response(&metrics, response, latency)
+            },
+        ))
+}
+
+async fn get_path(router: &Router, path: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn success_labelled_by_route_template() {
+    let metrics = Arc::new(HttpMetrics::new());
+    let router = traced(
+        Router::new().route("/devices/{id}", get(|| async { "ok" })),
+        metrics.clone(),
+    );
+
+    assert_eq!(get_path(&router, "/devices/dev123").await, StatusCode::OK);
+    assert_eq!(get_path(&router, "/devices/dev456").await, StatusCode::OK);
+
+    let mut output = String::new();
+    metrics.render(&mut output);
+
+    assert!(output.contains(
+        "trmnl_http_requests_total{method=\"GET\",route=\"/devices/{id}\",status=\"200\"} 2\n"
+    ));
+    assert!(output.contains(
+        "trmnl_http_request_duration_seconds_count{method=\"GET\",route=\"/devices/{id}\",status=\"200\"} 2\n"
+    ));
+    assert!(output.contains(
+        "trmnl_http_request_duration_seconds_bucket{method=\"GET\",route=\"/devices/{id}\",status=\"200\",le=\"+Inf\"} 2\n"
+    ));
+    assert!(!output.contains("dev123"));
+}
+
+#[tokio::test]
+async fn success_unmatched_route() {
+    let metrics = Arc::new(HttpMetrics::new());
+    let router = traced(
+        Router::new()
+            .route("/devices/{id}", get(|| async { "ok" }))
+            .fallback(|| async { StatusCode::NOT_FOUND }),
+        metrics.clone(),
+    );
+
+    assert_eq!(get_path(&router, "/unknown").await, StatusCode::NOT_FOUND);
+
+    let mut output = String::new();
+    metrics.render(&mut output);
+
+    assert!(output.contains(
+        "trmnl_http_requests_total{method=\"GET\",route=\"unmatched\",status=\"404\"} 1\n"
+    ));
+}
+
+#[tokio::test]
+async fn success_untagged_response_not_recorded() {
+    let metrics = Arc::new(HttpMetrics::new());
+    let router = Router::new()
+        .route("/devices/{id}", get(|| async { "ok" }))
+        .layer(TraceLayer::new_for_http().on_response({
+            let metrics = metrics.clone();
+            move |response: &Response<Body>, latency: Duration, _span: &Span| {
+                record_response(&metrics, response, latency)
+            }
+        }));
+
+    assert_eq!(get_path(&router, "/devices/dev123").await, StatusCode::OK);
+
+    let mut output = String::new();
+    metrics.render(&mut output);
+
+    assert!(!output.contains("trmnl_http_requests_total{"));
+}
@@ -0,0 +1,3 @@
+mod auth;
+mod metrics;
+mod problem;
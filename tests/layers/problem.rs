@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    Extension,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+    response::Response,
+};
+use mockall::predicate;
+use tower::ServiceExt;
+use trmnl_server::{
+    app::App,
+    config::StatusSettings,
+    error::{PROBLEM_CONTENT_TYPE, Problem},
+    layers::{
+        api_token::ApiTokenRepoLayer, device::DeviceRepoLayer, device_group::DeviceGroupRepoLayer,
+    },
+    repositories::{
+        api_token::MockApiTokenRepository, device::MockDeviceRepository,
+        device_group::MockDeviceGroupRepository,
+    },
+};
+
+async fn problem(response: Response) -> Problem {
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        PROBLEM_CONTENT_TYPE
+    );
+
+    serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
+
+async fn get_device(mock_device_repo: MockDeviceRepository, request_id: Option<&str>) -> Response {
+    let mut request = crate::handlers::authorized_request().uri("/api/devices/dev123");
+
+    if let Some(request_id) = request_id {
+        request = request.header("x-request-id", request_id);
+    }
+
+    App::new()
+        .router()
+        .layer(crate::handlers::api_token_layer())
+        .layer(Extension(StatusSettings::default()))
+        .layer(DeviceRepoLayer(Arc::new(mock_device_repo)))
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn not_found_includes_request_id() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_id| Box::pin(async { Ok(None) }));
+
+    let response = get_device(mock_device_repo, Some("req-123")).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let problem = problem(response).await;
+
+    assert_eq!(problem.status, 404);
+    assert_eq!(problem.title, "Not Found");
+    assert_eq!(problem.code, "not_found");
+    assert_eq!(problem.detail, "Device not found");
+    assert_eq!(problem.request_id.as_deref(), Some("req-123"));
+}
+
+#[tokio::test]
+async fn internal_error_hides_cause() {
+    let mut mock_device_repo = MockDeviceRepository::new();
+
+    mock_device_repo
+        .expect_get_by_id()
+        .with(predicate::eq("dev123"))
+        .times(1)
+        .returning(|_id| Box::pin(async { Err(anyhow!("connection refused")) }));
+
+    let response = get_device(mock_device_repo, None).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let problem = problem(response).await;
+
+    assert_eq!(problem.code, "internal_error");
+    assert_eq!(problem.detail, "Something went wrong");
+    assert_eq!(problem.request_id, None);
+}
+
+#[tokio::test]
+async fn unauthorized_keeps_authenticate_header() {
+    let response = App::new()
+        .router()
+        .layer(ApiTokenRepoLayer(Arc::new(MockApiTokenRepository::new())))
+        .oneshot(
+            Request::builder()
+                .uri("/api/tokens")
+                .header("x-request-id", "req-456")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        response.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+        "Bearer"
+    );
+
+    let problem = problem(response).await;
+
+    assert_eq!(problem.code, "unauthorized");
+    assert_eq!(problem.request_id.as_deref(), Some("req-456"));
+}
+
+#[tokio::test]
+async fn rejected_body_becomes_problem() {
+    let response = App::new()
+        .router()
+        .layer(crate::handlers::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .oneshot(
+            crate::handlers::authorized_request()
+                .method("POST")
+                .uri("/api/groups")
+                .header("content-type", "application/json")
+                .header("x-request-id", "req-789")
+                .body(Body::from("{"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let problem = problem(response).await;
+
+    assert_eq!(problem.status, 400);
+    assert_eq!(problem.code, "bad_request");
+    assert!(problem.detail.contains("JSON"));
+    assert_eq!(problem.request_id.as_deref(), Some("req-789"));
+}
+
+#[tokio::test]
+async fn rejected_content_type_becomes_problem() {
+    let response = App::new()
+        .router()
+        .layer(crate::handlers::api_token_layer())
+        .layer(DeviceGroupRepoLayer(Arc::new(
+            MockDeviceGroupRepository::new(),
+        )))
+        .oneshot(
+            crate::handlers::authorized_request()
+                .method("POST")
+                .uri("/api/groups")
+                .body(Body::from(r#"{"name":"Lobby"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let problem = problem(response).await;
+
+    assert_eq!(problem.code, "unsupported_media_type");
+    assert_eq!(problem.request_id, None);
+}
@@ -0,0 +1,331 @@
+use std::{collections::HashMap, io::Cursor};
+
+use image::{GrayImage, ImageFormat, Luma};
+use trmnl_server::layout::{LayoutSpec, message_screen, render_to_bmp};
+
+fn render(spec: serde_json::Value) -> GrayImage {
+    render_with_images(spec, &HashMap::new())
+}
+
+fn render_with_images(spec: serde_json::Value, images: &HashMap<String, Vec<u8>>) -> GrayImage {
+    let spec: LayoutSpec = serde_json::from_value(spec).unwrap();
+    let bmp = render_to_bmp(&spec, images).unwrap();
+
+    image::load_from_memory(&bmp).unwrap().to_luma8()
+}
+
+fn is_black(image: &GrayImage, x: u32, y: u32) -> bool {
+    image.get_pixel(x, y).0 == [0]
+}
+
+/// Counts the black pixels within the box
+fn black_in(image: &GrayImage, x: u32, y: u32, width: u32, height: u32) -> usize {
+    (y..y + height)
+        .flat_map(|y| (x..x + width).map(move |x| (x, y)))
+        .filter(|(x, y)| is_black(image, *x, *y))
+        .count()
+}
+
+#[test]
+fn success_empty_layout() {
+    let image = render(serde_json::json!({ "elements": [] }));
+
+    assert_eq!(image.dimensions(), (800, 480));
+    assert_eq!(black_in(&image, 0, 0, 800, 480), 0);
+}
+
+#[test]
+fn success_custom_resolution() {
+    let image = render(serde_json::json!({
+        "width": 400,
+        "height": 300,
+        "elements": [{ "type": "rectangle", "x": 350, "y": 250, "width": 100, "height": 100, "fill": true }]
+    }));
+
+    assert_eq!(image.dimensions(), (400, 300));
+    assert_eq!(black_in(&image, 350, 250, 50, 50), 50 * 50);
+    assert_eq!(black_in(&image, 0, 0, 350, 300), 0);
+}
+
+#[test]
+fn success_black_background() {
+    let image = render(serde_json::json!({ "background": "black", "elements": [] }));
+
+    assert_eq!(black_in(&image, 0, 0, 800, 480), 800 * 480);
+}
+
+#[test]
+fn success_text() {
+    let image = render(serde_json::json!({
+        "elements": [{ "type": "text", "x": 10, "y": 20, "text": "Hello", "size": 16 }]
+    }));
+
+    // Five glyphs of 16px wide starting at x=10
+    assert!(black_in(&image, 10, 20, 80, 16) > 0);
+    assert_eq!(black_in(&image, 90, 0, 710, 480), 0);
+    assert_eq!(black_in(&image, 0, 36, 800, 444), 0);
+}
+
+#[test]
+fn success_text_alignment() {
+    let right = render(serde_json::json!({
+        "elements": [{
+            "type": "text", "x": 0, "y": 0, "width": 800, "text": "AB", "size": 8, "align": "right"
+        }]
+    }));
+    let center = render(serde_json::json!({
+        "elements": [{
+            "type": "text", "x": 0, "y": 0, "width": 800, "text": "AB", "size": 8, "align": "center"
+        }]
+    }));
+
+    assert_eq!(black_in(&right, 0, 0, 784, 8), 0);
+    assert!(black_in(&right, 784, 0, 16, 8) > 0);
+
+    assert_eq!(black_in(&center, 0, 0, 392, 8), 0);
+    assert!(black_in(&center, 392, 0, 16, 8) > 0);
+    assert_eq!(black_in(&center, 408, 0, 392, 8), 0);
+}
+
+#[test]
+fn success_text_wraps() {
+    let image = render(serde_json::json!({
+        "elements": [{
+            "type": "text", "x": 0, "y": 0, "width": 40, "text": "one two three", "size": 8
+        }]
+    }));
+
+    // Each word ends up on its own 10px line
+    assert!(black_in(&image, 0, 0, 40, 8) > 0);
+    assert!(black_in(&image, 0, 10, 40, 8) > 0);
+    assert!(black_in(&image, 0, 20, 40, 8) > 0);
+    assert_eq!(black_in(&image, 40, 0, 760, 480), 0);
+}
+
+#[test]
+fn success_text_without_wrap() {
+    let image = render(serde_json::json!({
+        "elements": [{
+            "type": "text", "x": 0, "y": 0, "width": 40, "text": "one two three",
+            "size": 8, "wrap": false
+        }]
+    }));
+
+    assert!(black_in(&image, 40, 0, 64, 8) > 0);
+    assert_eq!(black_in(&image, 0, 10, 800, 470), 0);
+}
+
+#[test]
+fn success_bold_text_is_heavier() {
+    let regular = render(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": "Hello", "size": 16 }]
+    }));
+    let bold = render(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": "Hello", "size": 16, "font": "bold" }]
+    }));
+
+    assert!(black_in(&bold, 0, 0, 100, 16) > black_in(&regular, 0, 0, 100, 16));
+}
+
+#[test]
+fn success_rectangles() {
+    let image = render(serde_json::json!({
+        "elements": [
+            { "type": "rectangle", "x": 10, "y": 10, "width": 100, "height": 50, "thickness": 2 },
+            { "type": "rectangle", "x": 200, "y": 10, "width": 100, "height": 50, "fill": true }
+        ]
+    }));
+
+    assert!(is_black(&image, 10, 10));
+    assert!(is_black(&image, 11, 30));
+    assert!(!is_black(&image, 60, 30));
+    assert!(is_black(&image, 109, 59));
+    assert_eq!(black_in(&image, 200, 10, 100, 50), 100 * 50);
+}
+
+#[test]
+fn success_line() {
+    let image = render(serde_json::json!({
+        "elements": [
+            { "type": "line", "x1": 0, "y1": 100, "x2": 799, "y2": 100, "thickness": 3 },
+            { "type": "line", "x1": 0, "y1": 200, "x2": 99, "y2": 299 }
+        ]
+    }));
+
+    assert_eq!(black_in(&image, 0, 99, 800, 3), 800 * 3);
+    assert!(is_black(&image, 50, 250));
+    assert!(!is_black(&image, 50, 260));
+}
+
+#[test]
+fn success_line_clipped_to_canvas() {
+    let image = render(serde_json::json!({
+        "elements": [
+            { "type": "line", "x1": -3200, "y1": 100, "x2": 3200, "y2": 100 },
+            { "type": "line", "x1": -100, "y1": 100, "x2": 500, "y2": 700 },
+            { "type": "line", "x1": -3000, "y1": -1000, "x2": -2000, "y2": 1000 }
+        ]
+    }));
+
+    assert_eq!(black_in(&image, 0, 100, 800, 1), 800);
+    // The diagonal enters the canvas at (0, 200) and leaves it at (279, 479)
+    assert!(is_black(&image, 0, 200));
+    assert!(is_black(&image, 150, 350));
+    assert!(is_black(&image, 279, 479));
+    assert_eq!(black_in(&image, 0, 0, 800, 99), 0);
+}
+
+#[test]
+fn success_text_at_extreme_positions() {
+    // Unvalidated specs must not overflow while positioning text
+    let image = render(serde_json::json!({
+        "elements": [
+            {
+                "type": "text", "x": 2147483000, "y": 2147483000, "width": 4294967295u32,
+                "text": "overflow", "size": 256, "font": "bold", "align": "center"
+            },
+            {
+                "type": "text", "x": -2147483000, "y": 0, "text": "overflow", "size": 256,
+                "align": "right"
+            }
+        ]
+    }));
+
+    assert_eq!(black_in(&image, 0, 0, 800, 480), 0);
+}
+
+#[test]
+fn success_icon() {
+    let image = render(serde_json::json!({
+        "elements": [{ "type": "icon", "x": 100, "y": 100, "icon": "battery", "size": 32 }]
+    }));
+
+    assert!(black_in(&image, 100, 100, 32, 32) > 0);
+    assert_eq!(black_in(&image, 0, 0, 100, 480), 0);
+    assert_eq!(black_in(&image, 132, 0, 668, 480), 0);
+}
+
+#[test]
+fn success_image() {
+    let mut data = Cursor::new(Vec::new());
+    GrayImage::from_pixel(10, 10, Luma([0]))
+        .write_to(&mut data, ImageFormat::Png)
+        .unwrap();
+
+    let images = HashMap::from([("abc123".to_string(), data.into_inner())]);
+    let image = render_with_images(
+        serde_json::json!({
+            "elements": [{
+                "type": "image", "x": 50, "y": 60, "width": 40, "height": 40,
+                "image": "abc123", "resize": "stretch"
+            }]
+        }),
+        &images,
+    );
+
+    assert_eq!(black_in(&image, 50, 60, 40, 40), 40 * 40);
+    assert_eq!(black_in(&image, 0, 0, 50, 480), 0);
+    assert_eq!(black_in(&image, 90, 0, 710, 480), 0);
+}
+
+#[test]
+fn success_elements_outside_canvas() {
+    let image = render(serde_json::json!({
+        "elements": [
+            { "type": "rectangle", "x": -50, "y": -50, "width": 100, "height": 100, "fill": true },
+            { "type": "text", "x": 790, "y": 470, "text": "clipped", "size": 32 }
+        ]
+    }));
+
+    assert_eq!(black_in(&image, 0, 0, 50, 50), 50 * 50);
+    assert!(black_in(&image, 790, 470, 10, 10) > 0);
+}
+
+#[test]
+fn success_image_ids() {
+    let spec: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [
+            { "type": "image", "x": 0, "y": 0, "width": 10, "height": 10, "image": "abc" },
+            { "type": "text", "x": 0, "y": 0, "text": "hi" },
+            { "type": "image", "x": 0, "y": 0, "width": 10, "height": 10, "image": "def" }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(spec.image_ids(), vec!["abc", "def"]);
+}
+
+#[test]
+fn success_message_screen() {
+    let spec = message_screen("Sleeping", "Back at 06:30");
+    let bmp = render_to_bmp(&spec, &HashMap::new()).unwrap();
+    let image = image::load_from_memory(&bmp).unwrap().to_luma8();
+
+    assert_eq!(spec.validate(), Ok(()));
+    // Title and subtitle are centered, leaving the edges and the top of the screen blank
+    assert!(black_in(&image, 0, 176, 800, 48) > 0);
+    assert!(black_in(&image, 0, 256, 800, 32) > 0);
+    assert_eq!(black_in(&image, 0, 0, 800, 176), 0);
+    assert_eq!(black_in(&image, 0, 0, 100, 480), 0);
+}
+
+#[test]
+fn error_invalid_spec() {
+    let too_large: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": "hi", "size": 1024 }]
+    }))
+    .unwrap();
+    let empty_image: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "image", "x": 0, "y": 0, "width": 0, "height": 10, "image": "abc" }]
+    }))
+    .unwrap();
+
+    let far_line: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "line", "x1": -2000000000, "y1": 0, "x2": 2000000000, "y2": 0 }]
+    }))
+    .unwrap();
+    let far_text: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 1921, "text": "hi" }]
+    }))
+    .unwrap();
+    let wide_text: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "width": 4294967295u32, "text": "hi" }]
+    }))
+    .unwrap();
+    let no_size: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "width": 0,
+        "elements": []
+    }))
+    .unwrap();
+    let large_image: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "image", "x": 0, "y": 0, "width": 1000, "height": 10, "image": "abc" }]
+    }))
+    .unwrap();
+    let large_display: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "width": 1872,
+        "height": 1404,
+        "elements": [{ "type": "image", "x": 0, "y": 0, "width": 1000, "height": 10, "image": "abc" }]
+    }))
+    .unwrap();
+    let offscreen: LayoutSpec = serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "line", "x1": -3200, "y1": -1920, "x2": 3200, "y2": 1920 }]
+    }))
+    .unwrap();
+
+    assert!(too_large.validate().is_err());
+    assert!(empty_image.validate().is_err());
+    assert!(far_line.validate().is_err());
+    assert!(far_text.validate().is_err());
+    assert!(wide_text.validate().is_err());
+    assert!(no_size.validate().is_err());
+    assert!(large_image.validate().is_err());
+    assert!(large_display.validate().is_ok());
+    assert!(offscreen.validate().is_ok());
+
+    assert!(
+        serde_json::from_value::<LayoutSpec>(serde_json::json!({
+            "elements": [{ "type": "circle", "x": 0, "y": 0 }]
+        }))
+        .is_err()
+    );
+}
@@ -0,0 +1,184 @@
+use chrono::{TimeZone, Utc};
+use trmnl_server::{
+    config::StatusSettings,
+    models::{ApprovalStatus, DEFAULT_REFRESH_RATE, Device, DeviceStatus},
+    schedule::{QuietHours, TimeOfDay},
+};
+
+const NOW: i64 = 1_700_000_000;
+
+fn device(last_seen_at: Option<i64>, refresh_rate: Option<i64>) -> Device {
+    Device {
+        id: "dev123".to_string(),
+        mac: None,
+        _api_key: "abc123".to_string(),
+        rssi: None,
+        battery_voltage: None,
+        fw_version: None,
+        refresh_rate,
+        images: vec![],
+        image_cursor: 0,
+        desired_refresh_rate: None,
+        target_fw_version: None,
+        approval_status: ApprovalStatus::Approved,
+        schedule: vec![],
+        timezone: None,
+        quiet_hours: None,
+        group_id: None,
+        first_seen_at: last_seen_at,
+        last_seen_at,
+    }
+}
+
+#[test]
+fn status_follows_missed_polls() {
+    let settings = StatusSettings::default();
+
+    for (seconds_ago, expected) in [
+        (0, DeviceStatus::Online),
+        (900, DeviceStatus::Online),
+        (1350, DeviceStatus::Online),
+        (1351, DeviceStatus::Late),
+        (2700, DeviceStatus::Late),
+        (2701, DeviceStatus::Offline),
+    ] {
+        let device = device(Some(NOW - seconds_ago), Some(900));
+
+        assert_eq!(device.status(&settings, NOW), expected, "{seconds_ago}");
+    }
+}
+
+#[test]
+fn status_offline_when_never_seen() {
+    let device = device(None, Some(900));
+
+    assert_eq!(
+        device.status(&StatusSettings::default(), NOW),
+        DeviceStatus::Offline
+    );
+}
+
+#[test]
+fn status_prefers_desired_refresh_rate() {
+    let device = Device {
+        desired_refresh_rate: Some(3600),
+        ..device(Some(NOW - 3000), Some(900))
+    };
+
+    assert_eq!(device.expected_poll_interval(), 3600);
+    assert_eq!(
+        device.status(&StatusSettings::default(), NOW),
+        DeviceStatus::Online
+    );
+}
+
+#[test]
+fn status_defaults_refresh_rate() {
+    let device = device(Some(NOW - DEFAULT_REFRESH_RATE), None);
+
+    assert_eq!(device.expected_poll_interval(), DEFAULT_REFRESH_RATE);
+    assert_eq!(
+        device.status(&StatusSettings::default(), NOW),
+        DeviceStatus::Online
+    );
+}
+
+#[test]
+fn status_uses_configured_factors() {
+    let settings = StatusSettings {
+        grace_factor: 1.0,
+        offline_factor: 1.0,
+    };
+
+    assert_eq!(
+        device(Some(NOW - 900), Some(900)).status(&settings, NOW),
+        DeviceStatus::Online
+    );
+    assert_eq!(
+        device(Some(NOW - 901), Some(900)).status(&settings, NOW),
+        DeviceStatus::Offline
+    );
+}
+
+/// Unix timestamp of 2025-01-15 at the given UTC time
+fn at(hour: u32, minute: u32) -> i64 {
+    Utc.with_ymd_and_hms(2025, 1, 15, hour, minute, 0)
+        .unwrap()
+        .timestamp()
+}
+
+fn sleeping_device(last_seen_at: i64) -> Device {
+    Device {
+        timezone: Some("Europe/Berlin".to_string()),
+        quiet_hours: Some(QuietHours {
+            start: TimeOfDay::new(22, 0).unwrap(),
+            end: TimeOfDay::new(7, 0).unwrap(),
+            sleep_screen: false,
+        }),
+        ..device(Some(last_seen_at), Some(900))
+    }
+}
+
+#[test]
+fn status_online_while_sleeping_through_quiet_hours() {
+    let settings = StatusSettings::default();
+    // 23:00 in Berlin, told to sleep until 07:00 local time
+    let device = sleeping_device(at(22, 0));
+
+    assert_eq!(device.expected_poll_interval(), 8 * 60 * 60);
+    assert_eq!(
+        device.status(&settings, at(5, 59) + 86_400),
+        DeviceStatus::Online
+    );
+    // Missing the wake-up poll is still noticed
+    assert_eq!(
+        device.status(&settings, at(22, 0) + 3 * 8 * 60 * 60 + 1),
+        DeviceStatus::Offline
+    );
+}
+
+#[test]
+fn expected_poll_interval_outside_quiet_hours() {
+    // 21:00 in Berlin, the window has not started yet
+    assert_eq!(sleeping_device(at(20, 0)).expected_poll_interval(), 900);
+}
+
+#[test]
+fn status_settings_validate() {
+    assert!(StatusSettings::default().validate().is_ok());
+    assert!(
+        StatusSettings {
+            grace_factor: 0.0,
+            offline_factor: 3.0,
+        }
+        .validate()
+        .is_err()
+    );
+    assert!(
+        StatusSettings {
+            grace_factor: 2.0,
+            offline_factor: 1.0,
+        }
+        .validate()
+        .is_err()
+    );
+}
+
+#[test]
+fn apply_status_keeps_unreported_values() {
+    let mut device = Device {
+        rssi: Some(-60),
+        battery_voltage: Some(4.1),
+        fw_version: Some("1.6.4".to_string()),
+        ..device(Some(NOW - 900), Some(900))
+    };
+
+    device.apply_status(NOW, None, Some(3.5), Some("1.6.5"), None);
+
+    assert_eq!(device.rssi, Some(-60));
+    assert_eq!(device.battery_voltage, Some(3.5));
+    assert_eq!(device.fw_version.as_deref(), Some("1.6.5"));
+    assert_eq!(device.refresh_rate, Some(900));
+    assert_eq!(device.first_seen_at, Some(NOW - 900));
+    assert_eq!(device.last_seen_at, Some(NOW));
+}
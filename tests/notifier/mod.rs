@@ -0,0 +1,97 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use axum::{Json, Router, extract::State, routing::post};
+use tokio::sync::mpsc;
+use trmnl_server::{
+    models::{Alert, AlertRule, AlertStatus},
+    notifier::{MockNotifier, Notifier, QueuedNotifier, WebhookNotifier},
+};
+
+fn alert(id: i64) -> Alert {
+    Alert {
+        id,
+        device_id: "dev123".to_string(),
+        rule: AlertRule::LowBattery,
+        status: AlertStatus::Firing,
+        message: "Battery at 3.20 V, below 3.40 V".to_string(),
+        fired_at: 1_700_000_000,
+        resolved_at: None,
+    }
+}
+
+#[tokio::test]
+async fn queued_returns_before_delivery() {
+    let (delivered, mut deliveries) = mpsc::unbounded_channel();
+    let mut mock_notifier = MockNotifier::new();
+
+    mock_notifier
+        .expect_notify()
+        .times(2)
+        .returning(move |alert| {
+            let delivered = delivered.clone();
+            let id = alert.id;
+            Box::pin(async move {
+                // A black-holed endpoint, as far as the caller can tell
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                delivered.send(id).unwrap();
+                Err(anyhow!("timed out"))
+            })
+        });
+
+    let notifier = QueuedNotifier::spawn(Arc::new(mock_notifier));
+
+    tokio::time::timeout(Duration::from_millis(50), async {
+        notifier.notify(&alert(1)).await.unwrap();
+        notifier.notify(&alert(2)).await.unwrap();
+    })
+    .await
+    .unwrap();
+
+    // A failed delivery does not stop the ones queued after it
+    assert_eq!(deliveries.recv().await, Some(1));
+    assert_eq!(deliveries.recv().await, Some(2));
+}
+
+#[tokio::test]
+async fn webhook_posts_alert() {
+    let (received, mut receiver) = mpsc::unbounded_channel::<Alert>();
+    let app =
+        Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(received): State<mpsc::UnboundedSender<Alert>>,
+                     Json(alert): Json<Alert>| async move {
+                        received.send(alert).unwrap();
+                    },
+                ),
+            )
+            .with_state(received);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let notifier = WebhookNotifier::new(format!("http://{addr}/hook")).unwrap();
+
+    notifier.notify(&alert(7)).await.unwrap();
+
+    assert_eq!(receiver.recv().await, Some(alert(7)));
+}
+
+#[tokio::test]
+async fn webhook_error_status() {
+    let app = Router::new().route(
+        "/hook",
+        post(|| async { axum::http::StatusCode::BAD_GATEWAY }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let notifier = WebhookNotifier::new(format!("http://{addr}/hook")).unwrap();
+
+    assert!(notifier.notify(&alert(7)).await.is_err());
+}
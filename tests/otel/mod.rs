@@ -0,0 +1 @@
+mod tracer_provider;
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use trmnl_server::{
+    config::{TelemetrySettings, TraceExporter},
+    otel::tracer_provider,
+};
+
+#[test]
+fn success_disabled_by_default() {
+    assert!(
+        tracer_provider(&TelemetrySettings::default())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn success_stdout() {
+    let provider = tracer_provider(&TelemetrySettings {
+        exporter: TraceExporter::Stdout,
+        sampling_ratio: 0.5,
+        ..Default::default()
+    })
+    .unwrap()
+    .unwrap();
+
+    provider.shutdown().unwrap();
+}
+
+#[tokio::test]
+async fn success_otlp_grpc_with_headers() {
+    let provider = tracer_provider(&TelemetrySettings {
+        exporter: TraceExporter::OtlpGrpc,
+        endpoint: Some("http://127.0.0.1:4317".to_string()),
+        headers: HashMap::from([("authorization".to_string(), "Bearer secret".to_string())]),
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert!(provider.is_some());
+}
+
+#[test]
+fn error_sampling_ratio_out_of_range() {
+    assert!(
+        tracer_provider(&TelemetrySettings {
+            exporter: TraceExporter::Stdout,
+            sampling_ratio: 1.5,
+            ..Default::default()
+        })
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn error_invalid_header() {
+    assert!(
+        tracer_provider(&TelemetrySettings {
+            exporter: TraceExporter::OtlpGrpc,
+            headers: HashMap::from([("bad header".to_string(), "value".to_string())]),
+            ..Default::default()
+        })
+        .is_err()
+    );
+}
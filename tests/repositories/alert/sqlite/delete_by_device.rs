@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{AlertListQuery, AlertRule},
+    repositories::alert::{AlertRepository, SqliteAlertRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_firing_and_resolved() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+
+    repo.fire(
+        "dev123",
+        AlertRule::LowBattery,
+        "Battery low",
+        1_700_000_000,
+    )
+    .await
+    .unwrap();
+    repo.resolve("dev123", AlertRule::LowBattery, 1_700_000_100)
+        .await
+        .unwrap();
+    repo.fire("dev123", AlertRule::Offline, "No poll", 1_700_000_200)
+        .await
+        .unwrap();
+    repo.fire("dev456", AlertRule::Offline, "No poll", 1_700_000_300)
+        .await
+        .unwrap();
+
+    repo.delete_by_device("dev123").await.unwrap();
+
+    let alerts = repo.list(&AlertListQuery::default()).await.unwrap();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].device_id, "dev456");
+}
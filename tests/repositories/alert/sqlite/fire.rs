@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{AlertListQuery, AlertRule, AlertStatus},
+    repositories::alert::{AlertRepository, SqliteAlertRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+
+    let alert = repo
+        .fire(
+            "dev123",
+            AlertRule::LowBattery,
+            "Battery at 3.20 V",
+            1_700_000_000,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(alert.device_id, "dev123");
+    assert_eq!(alert.rule, AlertRule::LowBattery);
+    assert_eq!(alert.status, AlertStatus::Firing);
+    assert_eq!(alert.message, "Battery at 3.20 V");
+    assert_eq!(alert.fired_at, 1_700_000_000);
+    assert_eq!(alert.resolved_at, None);
+}
+
+#[tokio::test]
+async fn success_already_firing() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+
+    repo.fire(
+        "dev123",
+        AlertRule::LowBattery,
+        "Battery at 3.20 V",
+        1_700_000_000,
+    )
+    .await
+    .unwrap();
+
+    // A rule fires once until it is resolved, other rules and devices are independent
+    let again = repo
+        .fire(
+            "dev123",
+            AlertRule::LowBattery,
+            "Battery at 3.10 V",
+            1_700_000_100,
+        )
+        .await
+        .unwrap();
+    assert!(again.is_none());
+
+    assert!(
+        repo.fire(
+            "dev123",
+            AlertRule::WeakSignal,
+            "Signal at -90 dBm",
+            1_700_000_100
+        )
+        .await
+        .unwrap()
+        .is_some()
+    );
+    assert!(
+        repo.fire(
+            "dev456",
+            AlertRule::LowBattery,
+            "Battery at 3.20 V",
+            1_700_000_100
+        )
+        .await
+        .unwrap()
+        .is_some()
+    );
+
+    let firing = repo.list_firing("dev123").await.unwrap();
+    assert_eq!(firing.len(), 2);
+    assert_eq!(firing[0].message, "Battery at 3.20 V");
+}
+
+#[tokio::test]
+async fn success_fires_again_after_resolve() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+
+    let first = repo
+        .fire("dev123", AlertRule::Offline, "No poll", 1_700_000_000)
+        .await
+        .unwrap()
+        .unwrap();
+    repo.resolve("dev123", AlertRule::Offline, 1_700_000_100)
+        .await
+        .unwrap();
+
+    let second = repo
+        .fire("dev123", AlertRule::Offline, "No poll", 1_700_000_200)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_ne!(first.id, second.id);
+    assert_eq!(
+        repo.list(&AlertListQuery::default()).await.unwrap().len(),
+        2
+    );
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{AlertListQuery, AlertRule, AlertStatus},
+    repositories::alert::{AlertRepository, SqliteAlertRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+async fn seed(repo: &SqliteAlertRepo) {
+    repo.fire(
+        "dev123",
+        AlertRule::LowBattery,
+        "Battery low",
+        1_700_000_000,
+    )
+    .await
+    .unwrap();
+    repo.resolve("dev123", AlertRule::LowBattery, 1_700_000_100)
+        .await
+        .unwrap();
+    repo.fire("dev123", AlertRule::Offline, "No poll", 1_700_000_200)
+        .await
+        .unwrap();
+    repo.fire(
+        "dev456",
+        AlertRule::WeakSignal,
+        "Signal weak",
+        1_700_000_300,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn success_newest_first() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+    seed(&repo).await;
+
+    let alerts = repo.list(&AlertListQuery::default()).await.unwrap();
+
+    let rules: Vec<_> = alerts.iter().map(|alert| alert.rule).collect();
+    assert_eq!(
+        rules,
+        vec![
+            AlertRule::WeakSignal,
+            AlertRule::Offline,
+            AlertRule::LowBattery
+        ]
+    );
+}
+
+#[tokio::test]
+async fn success_filters() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+    seed(&repo).await;
+
+    let alerts = repo
+        .list(&AlertListQuery {
+            status: Some(AlertStatus::Firing),
+            device_id: Some("dev123".to_string()),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].rule, AlertRule::Offline);
+
+    let alerts = repo
+        .list(&AlertListQuery {
+            status: Some(AlertStatus::Resolved),
+            device_id: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].rule, AlertRule::LowBattery);
+}
+
+#[tokio::test]
+async fn success_list_firing() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+    seed(&repo).await;
+
+    let firing = repo.list_firing("dev123").await.unwrap();
+
+    assert_eq!(firing.len(), 1);
+    assert_eq!(firing[0].rule, AlertRule::Offline);
+    assert_eq!(firing[0].status, AlertStatus::Firing);
+}
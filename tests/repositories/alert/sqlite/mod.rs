@@ -0,0 +1,4 @@
+mod delete_by_device;
+mod fire;
+mod list;
+mod resolve;
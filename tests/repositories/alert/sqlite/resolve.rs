@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{AlertListQuery, AlertRule, AlertStatus},
+    repositories::alert::{AlertRepository, SqliteAlertRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+
+    let fired = repo
+        .fire(
+            "dev123",
+            AlertRule::WeakSignal,
+            "Signal at -90 dBm",
+            1_700_000_000,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let resolved = repo
+        .resolve("dev123", AlertRule::WeakSignal, 1_700_000_500)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(resolved.id, fired.id);
+    assert_eq!(resolved.status, AlertStatus::Resolved);
+    assert_eq!(resolved.fired_at, 1_700_000_000);
+    assert_eq!(resolved.resolved_at, Some(1_700_000_500));
+    assert!(repo.list_firing("dev123").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn success_not_firing() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteAlertRepo::new(Arc::new(pool));
+
+    repo.fire(
+        "dev456",
+        AlertRule::WeakSignal,
+        "Signal at -90 dBm",
+        1_700_000_000,
+    )
+    .await
+    .unwrap();
+
+    let resolved = repo
+        .resolve("dev123", AlertRule::WeakSignal, 1_700_000_500)
+        .await
+        .unwrap();
+
+    assert!(resolved.is_none());
+    assert_eq!(
+        repo.list(&AlertListQuery {
+            status: Some(AlertStatus::Firing),
+            device_id: None,
+        })
+        .await
+        .unwrap()
+        .len(),
+        1
+    );
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::api_token::{ApiTokenRepository, SqliteApiTokenRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    repo.create("token123", "ci", "hash123").await.unwrap();
+
+    let record = sqlx::query!(
+        "SELECT id, name, token_hash FROM api_tokens WHERE id = ?",
+        "token123"
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(record.name, "ci");
+    assert_eq!(record.token_hash, "hash123");
+}
+
+#[tokio::test]
+async fn error_duplicate_hash() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    repo.create("token123", "ci", "hash123").await.unwrap();
+    let result = repo.create("token456", "other", "hash123").await;
+
+    assert!(result.is_err());
+}
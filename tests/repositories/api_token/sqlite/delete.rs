@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::api_token::{ApiTokenRepository, SqliteApiTokenRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_existing_token() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    repo.create("token123", "ci", "hash123").await.unwrap();
+
+    assert!(repo.delete("token123").await.unwrap());
+    assert!(repo.get_by_hash("hash123").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn success_nonexistent_token() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    assert!(!repo.delete("token123").await.unwrap());
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::api_token::{ApiTokenRepository, SqliteApiTokenRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    repo.create("token123", "ci", "hash123").await.unwrap();
+
+    let token = repo.get_by_hash("hash123").await.unwrap().unwrap();
+
+    assert_eq!(token.id, "token123");
+    assert_eq!(token.name, "ci");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    let token = repo.get_by_hash("hash123").await.unwrap();
+    assert!(token.is_none());
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::api_token::{ApiTokenRepository, SqliteApiTokenRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_multiple_tokens() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    repo.create("token1", "ci", "hash1").await.unwrap();
+    repo.create("token2", "admin", "hash2").await.unwrap();
+
+    let list = repo.list().await.unwrap();
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].id, "token1");
+    assert_eq!(list[1].id, "token2");
+}
+
+#[tokio::test]
+async fn success_empty_table() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteApiTokenRepo::new(Arc::new(pool.clone()));
+
+    let list = repo.list().await.unwrap();
+    assert!(list.is_empty());
+}
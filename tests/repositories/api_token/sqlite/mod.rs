@@ -0,0 +1,4 @@
+mod create;
+mod delete;
+mod get_by_hash;
+mod list;
@@ -0,0 +1,14 @@
+use std::{future::Future, sync::Arc};
+
+use trmnl_server::repositories::device::{DeviceRepo, InMemoryDeviceRepo};
+
+use super::shared::device_repository_tests;
+
+device_repository_tests!();
+
+async fn run<F>(case: impl FnOnce(DeviceRepo) -> F)
+where
+    F: Future<Output = ()>,
+{
+    case(Arc::new(InMemoryDeviceRepo::new())).await;
+}
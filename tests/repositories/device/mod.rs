@@ -1 +1,6 @@
+mod shared;
+
+mod memory;
+#[cfg(feature = "postgres")]
+mod postgres;
 mod sqlite;
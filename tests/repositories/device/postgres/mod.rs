@@ -0,0 +1,56 @@
+use std::{future::Future, str::FromStr, sync::Arc};
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use trmnl_server::{
+    db::{apply_postgres_migrations, connect_postgres},
+    repositories::device::{DeviceRepo, PostgresDeviceRepo},
+};
+use uuid::Uuid;
+
+use super::shared::device_repository_tests;
+
+device_repository_tests!();
+
+// Runs against the database in TEST_POSTGRES_URL, each test in a schema of its own that is
+// dropped again afterwards, also when the case fails. Skipped when the variable is unset
+async fn run<F>(case: impl FnOnce(DeviceRepo) -> F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+        eprintln!("TEST_POSTGRES_URL is not set, skipping");
+        return;
+    };
+
+    let schema = format!("test_{}", Uuid::new_v4().simple());
+    let admin = connect_postgres(&url).await.unwrap();
+    sqlx::query(&format!("CREATE SCHEMA {schema}"))
+        .execute(&admin)
+        .await
+        .unwrap();
+
+    let options = PgConnectOptions::from_str(&url)
+        .unwrap()
+        .options([("search_path", schema.as_str())]);
+    let pool = Arc::new(
+        PgPoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .unwrap(),
+    );
+
+    apply_postgres_migrations(&pool).await.unwrap();
+    let outcome = tokio::spawn(case(Arc::new(PostgresDeviceRepo::new(pool.clone())))).await;
+
+    pool.close().await;
+    sqlx::query(&format!("DROP SCHEMA {schema} CASCADE"))
+        .execute(&admin)
+        .await
+        .unwrap();
+    admin.close().await;
+
+    if let Err(error) = outcome {
+        std::panic::resume_unwind(error.into_panic());
+    }
+}
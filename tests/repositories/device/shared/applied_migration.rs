@@ -0,0 +1,8 @@
+use trmnl_server::repositories::device::DeviceRepo;
+
+pub async fn success_up_to_date(repo: DeviceRepo) {
+    assert_eq!(
+        repo.applied_migration().await.unwrap(),
+        repo.latest_migration()
+    );
+}
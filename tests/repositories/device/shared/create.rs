@@ -0,0 +1,67 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create(
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        ApprovalStatus::Pending,
+    )
+    .await
+    .unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.mac.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+    assert_eq!(device._api_key, "apikey123");
+    assert_eq!(device.approval_status, ApprovalStatus::Pending);
+    assert!(device.images.is_empty());
+    assert_eq!(device.image_cursor, 0);
+    assert!(device.schedule.is_empty());
+    assert_eq!(device.quiet_hours, None);
+    assert_eq!(device.group_id, None);
+    assert_eq!(device.last_seen_at, None);
+}
+
+pub async fn error_duplicate_api_key(repo: DeviceRepo) {
+    repo.create("dev1", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    assert!(
+        repo.create("dev2", None, "apikey123", ApprovalStatus::Approved)
+            .await
+            .is_err()
+    );
+}
+
+pub async fn success_without_mac(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.mac, None);
+    assert_eq!(device.approval_status, ApprovalStatus::Approved);
+}
+
+pub async fn error_duplicate_id(repo: DeviceRepo) {
+    repo.create(
+        "dev123",
+        Some("AA:BB:CC:DD:EE:01"),
+        "apikey123",
+        ApprovalStatus::Approved,
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        repo.create(
+            "dev123",
+            Some("AA:BB:CC:DD:EE:02"),
+            "apikey456",
+            ApprovalStatus::Approved,
+        )
+        .await
+        .is_err()
+    );
+}
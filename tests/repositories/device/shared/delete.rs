@@ -0,0 +1,11 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    assert!(repo.delete("dev123").await.unwrap());
+    assert!(repo.get_by_id("dev123").await.unwrap().is_none());
+    assert!(!repo.delete("dev123").await.unwrap());
+}
@@ -0,0 +1,15 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create(
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        ApprovalStatus::Approved,
+    )
+    .await
+    .unwrap();
+
+    assert!(repo.exists_by_mac("AA:BB:CC:DD:EE:FF").await.unwrap());
+    assert!(!repo.exists_by_mac("11:22:33:44:55:66").await.unwrap());
+}
@@ -0,0 +1,12 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    let device = repo.get_by_api_key("apikey123").await.unwrap().unwrap();
+    assert_eq!(device.id, "dev123");
+
+    assert!(repo.get_by_api_key("unknown").await.unwrap().is_none());
+}
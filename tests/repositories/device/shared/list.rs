@@ -0,0 +1,18 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success_sorted_by_id(repo: DeviceRepo) {
+    for id in ["dev2", "dev3", "dev1"] {
+        repo.create(id, None, &format!("apikey-{id}"), ApprovalStatus::Approved)
+            .await
+            .unwrap();
+    }
+
+    let devices = repo.list().await.unwrap();
+
+    let ids: Vec<_> = devices.iter().map(|device| device.id.as_str()).collect();
+    assert_eq!(ids, vec!["dev1", "dev2", "dev3"]);
+}
+
+pub async fn success_empty(repo: DeviceRepo) {
+    assert!(repo.list().await.unwrap().is_empty());
+}
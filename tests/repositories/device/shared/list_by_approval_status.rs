@@ -0,0 +1,28 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create("dev2", None, "apikey2", ApprovalStatus::Pending)
+        .await
+        .unwrap();
+    repo.create("dev1", None, "apikey1", ApprovalStatus::Pending)
+        .await
+        .unwrap();
+    repo.create("dev3", None, "apikey3", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    let pending = repo
+        .list_by_approval_status(ApprovalStatus::Pending)
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = pending.iter().map(|device| device.id.as_str()).collect();
+    assert_eq!(ids, vec!["dev1", "dev2"]);
+
+    assert!(
+        repo.list_by_approval_status(ApprovalStatus::Rejected)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,31 @@
+use trmnl_server::{
+    models::{ApprovalStatus, DevicePatch},
+    repositories::device::DeviceRepo,
+};
+
+pub async fn success(repo: DeviceRepo) {
+    for id in ["dev2", "dev1", "dev3"] {
+        repo.create(id, None, &format!("apikey-{id}"), ApprovalStatus::Approved)
+            .await
+            .unwrap();
+    }
+
+    for id in ["dev2", "dev1"] {
+        repo.update(
+            id,
+            &DevicePatch {
+                group_id: Some(Some("group123".to_string())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let devices = repo.list_by_group("group123").await.unwrap();
+
+    let ids: Vec<_> = devices.iter().map(|device| device.id.as_str()).collect();
+    assert_eq!(ids, vec!["dev1", "dev2"]);
+
+    assert!(repo.list_by_group("unknown").await.unwrap().is_empty());
+}
@@ -0,0 +1,72 @@
+//! Backend independent cases of the device repository contract. Each backend module defines
+//! `async fn run(case)` calling the case with an empty repository and cleaning up after it,
+//! and invokes `device_repository_tests!()`, optionally with attributes for every test.
+
+pub mod applied_migration;
+pub mod create;
+pub mod delete;
+pub mod exists_by_mac;
+pub mod get_by_api_key;
+pub mod get_by_id;
+pub mod list;
+pub mod list_by_approval_status;
+pub mod list_by_group;
+pub mod ping;
+pub mod rotate_api_key;
+pub mod set_group_members;
+pub mod take_pending_setup;
+pub mod update;
+pub mod update_approval_status;
+pub mod update_image_cursor;
+pub mod update_images;
+pub mod update_schedule;
+pub mod update_status;
+
+macro_rules! device_repository_tests {
+    (@cases $attrs:tt $($module:ident { $($case:ident),* })*) => {
+        mod shared {
+            $(
+                mod $module {
+                    $(
+                        crate::repositories::device::shared::device_repository_tests!(
+                            @case $attrs $module $case
+                        );
+                    )*
+                }
+            )*
+        }
+    };
+    (@case [$(#[$attr:meta])*] $module:ident $case:ident) => {
+        $(#[$attr])*
+        #[tokio::test]
+        async fn $case() {
+            super::super::run(crate::repositories::device::shared::$module::$case).await;
+        }
+    };
+    ($(#[$attr:meta])*) => {
+        crate::repositories::device::shared::device_repository_tests! {
+            @cases [$(#[$attr])*]
+            applied_migration { success_up_to_date }
+            create { success, success_without_mac, error_duplicate_id, error_duplicate_api_key }
+            delete { success }
+            exists_by_mac { success }
+            get_by_api_key { success }
+            get_by_id { success }
+            list { success_sorted_by_id, success_empty }
+            list_by_approval_status { success }
+            list_by_group { success }
+            ping { success }
+            rotate_api_key { success, success_nonexistent_device }
+            set_group_members { success, success_unknown_device }
+            take_pending_setup { success_once, success_unknown_mac }
+            update { success_set_and_clear, success_empty_patch_keeps_values }
+            update_approval_status { success, success_nonexistent_device }
+            update_image_cursor { success, success_nonexistent_device }
+            update_images { success_resets_cursor, success_nonexistent_device }
+            update_schedule { success, success_nonexistent_device }
+            update_status { success_partial, success_nonexistent_device }
+        }
+    };
+}
+
+pub(crate) use device_repository_tests;
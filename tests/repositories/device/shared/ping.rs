@@ -0,0 +1,5 @@
+use trmnl_server::repositories::device::DeviceRepo;
+
+pub async fn success(repo: DeviceRepo) {
+    repo.ping().await.unwrap();
+}
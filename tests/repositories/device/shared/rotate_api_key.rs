@@ -0,0 +1,21 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create(
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        ApprovalStatus::Approved,
+    )
+    .await
+    .unwrap();
+
+    assert!(repo.rotate_api_key("dev123", "apikey456").await.unwrap());
+
+    assert!(repo.get_by_api_key("apikey123").await.unwrap().is_none());
+    assert!(repo.get_by_api_key("apikey456").await.unwrap().is_some());
+}
+
+pub async fn success_nonexistent_device(repo: DeviceRepo) {
+    assert!(!repo.rotate_api_key("dev123", "apikey456").await.unwrap());
+}
@@ -0,0 +1,59 @@
+use trmnl_server::{
+    models::{ApprovalStatus, DevicePatch},
+    repositories::device::DeviceRepo,
+};
+
+async fn create_devices(repo: &DeviceRepo) {
+    for id in ["dev1", "dev2", "dev3"] {
+        repo.create(id, None, &format!("apikey-{id}"), ApprovalStatus::Approved)
+            .await
+            .unwrap();
+    }
+}
+
+async fn group_of(repo: &DeviceRepo, id: &str) -> Option<String> {
+    repo.get_by_id(id).await.unwrap().unwrap().group_id
+}
+
+pub async fn success(repo: DeviceRepo) {
+    create_devices(&repo).await;
+
+    repo.update(
+        "dev1",
+        &DevicePatch {
+            group_id: Some(Some("group123".to_string())),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    repo.update(
+        "dev3",
+        &DevicePatch {
+            group_id: Some(Some("group456".to_string())),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let members = vec!["dev2".to_string(), "dev3".to_string(), "dev3".to_string()];
+    assert!(repo.set_group_members("group123", &members).await.unwrap());
+
+    // Unlisted members leave, listed devices move over from other groups
+    assert_eq!(group_of(&repo, "dev1").await, None);
+    assert_eq!(group_of(&repo, "dev2").await.as_deref(), Some("group123"));
+    assert_eq!(group_of(&repo, "dev3").await.as_deref(), Some("group123"));
+
+    assert!(repo.set_group_members("group123", &[]).await.unwrap());
+    assert!(repo.list_by_group("group123").await.unwrap().is_empty());
+}
+
+pub async fn success_unknown_device(repo: DeviceRepo) {
+    create_devices(&repo).await;
+
+    let members = vec!["dev1".to_string(), "unknown".to_string()];
+    assert!(!repo.set_group_members("group123", &members).await.unwrap());
+
+    assert_eq!(group_of(&repo, "dev1").await, None);
+}
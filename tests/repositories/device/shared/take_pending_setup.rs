@@ -0,0 +1,44 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success_once(repo: DeviceRepo) {
+    repo.create(
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        ApprovalStatus::Approved,
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        repo.take_pending_setup("AA:BB:CC:DD:EE:FF")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    repo.rotate_api_key("dev123", "apikey456").await.unwrap();
+
+    let device = repo
+        .take_pending_setup("AA:BB:CC:DD:EE:FF")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(device._api_key, "apikey456");
+
+    assert!(
+        repo.take_pending_setup("AA:BB:CC:DD:EE:FF")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+pub async fn success_unknown_mac(repo: DeviceRepo) {
+    assert!(
+        repo.take_pending_setup("11:22:33:44:55:66")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
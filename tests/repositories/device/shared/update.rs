@@ -0,0 +1,77 @@
+use trmnl_server::{
+    models::{ApprovalStatus, DevicePatch},
+    repositories::device::DeviceRepo,
+    schedule::QuietHours,
+};
+
+pub async fn success_set_and_clear(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    let quiet_hours = QuietHours {
+        start: "22:00".parse().unwrap(),
+        end: "06:30".parse().unwrap(),
+        sleep_screen: true,
+    };
+
+    repo.update(
+        "dev123",
+        &DevicePatch {
+            desired_refresh_rate: Some(Some(3600)),
+            target_fw_version: Some(Some("1.6.5".to_string())),
+            timezone: Some(Some("Europe/Amsterdam".to_string())),
+            quiet_hours: Some(Some(quiet_hours.clone())),
+            group_id: Some(Some("group123".to_string())),
+        },
+    )
+    .await
+    .unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.desired_refresh_rate, Some(3600));
+    assert_eq!(device.target_fw_version.as_deref(), Some("1.6.5"));
+    assert_eq!(device.timezone.as_deref(), Some("Europe/Amsterdam"));
+    assert_eq!(device.quiet_hours, Some(quiet_hours));
+    assert_eq!(device.group_id.as_deref(), Some("group123"));
+
+    // Only the fields present in the patch are touched
+    repo.update(
+        "dev123",
+        &DevicePatch {
+            desired_refresh_rate: Some(None),
+            quiet_hours: Some(None),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.desired_refresh_rate, None);
+    assert_eq!(device.quiet_hours, None);
+    assert_eq!(device.target_fw_version.as_deref(), Some("1.6.5"));
+    assert_eq!(device.group_id.as_deref(), Some("group123"));
+}
+
+pub async fn success_empty_patch_keeps_values(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+    repo.update(
+        "dev123",
+        &DevicePatch {
+            desired_refresh_rate: Some(Some(3600)),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    repo.update("dev123", &DevicePatch::default())
+        .await
+        .unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.desired_refresh_rate, Some(3600));
+}
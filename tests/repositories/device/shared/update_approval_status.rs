@@ -0,0 +1,25 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Pending)
+        .await
+        .unwrap();
+
+    assert!(
+        repo.update_approval_status("dev123", ApprovalStatus::Rejected)
+            .await
+            .unwrap()
+    );
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.approval_status, ApprovalStatus::Rejected);
+}
+
+pub async fn success_nonexistent_device(repo: DeviceRepo) {
+    assert!(
+        !repo
+            .update_approval_status("dev123", ApprovalStatus::Approved)
+            .await
+            .unwrap()
+    );
+}
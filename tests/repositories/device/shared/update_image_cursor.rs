@@ -0,0 +1,18 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    repo.update_image_cursor("dev123", 3).await.unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.image_cursor, 3);
+}
+
+pub async fn success_nonexistent_device(repo: DeviceRepo) {
+    repo.update_image_cursor("dev123", 1).await.unwrap();
+
+    assert!(repo.get_by_id("dev123").await.unwrap().is_none());
+}
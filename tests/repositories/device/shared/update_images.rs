@@ -0,0 +1,23 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success_resets_cursor(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+    repo.update_image_cursor("dev123", 2).await.unwrap();
+
+    let images = vec!["one.png".to_string(), "layout:dashboard".to_string()];
+    repo.update_images("dev123", &images).await.unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.images, images);
+    assert_eq!(device.image_cursor, 0);
+}
+
+pub async fn success_nonexistent_device(repo: DeviceRepo) {
+    repo.update_images("dev123", &["one.png".to_string()])
+        .await
+        .unwrap();
+
+    assert!(repo.get_by_id("dev123").await.unwrap().is_none());
+}
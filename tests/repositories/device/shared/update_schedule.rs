@@ -0,0 +1,26 @@
+use trmnl_server::{
+    models::ApprovalStatus, repositories::device::DeviceRepo, schedule::ScheduleRule,
+};
+
+pub async fn success(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+    repo.update_image_cursor("dev123", 2).await.unwrap();
+
+    let schedule = vec![ScheduleRule {
+        weekdays: vec![],
+        start: "09:00".parse().unwrap(),
+        end: "17:00".parse().unwrap(),
+        images: vec!["layout:meetings".to_string()],
+    }];
+    assert!(repo.update_schedule("dev123", &schedule).await.unwrap());
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.schedule, schedule);
+    assert_eq!(device.image_cursor, 0);
+}
+
+pub async fn success_nonexistent_device(repo: DeviceRepo) {
+    assert!(!repo.update_schedule("dev123", &[]).await.unwrap());
+}
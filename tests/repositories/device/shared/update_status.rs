@@ -0,0 +1,44 @@
+use trmnl_server::{models::ApprovalStatus, repositories::device::DeviceRepo};
+
+pub async fn success_partial(repo: DeviceRepo) {
+    repo.create("dev123", None, "apikey123", ApprovalStatus::Approved)
+        .await
+        .unwrap();
+
+    repo.update_status(
+        "dev123",
+        1_700_000_000,
+        Some(-70),
+        Some(3.9),
+        Some("1.1.0"),
+        Some(900),
+    )
+    .await
+    .unwrap();
+    repo.update_status("dev123", 1_700_000_900, Some(-60), None, None, None)
+        .await
+        .unwrap();
+
+    let device = repo.get_by_id("dev123").await.unwrap().unwrap();
+    assert_eq!(device.rssi, Some(-60));
+    assert!((device.battery_voltage.unwrap() - 3.9).abs() < 1e-6);
+    assert_eq!(device.fw_version.as_deref(), Some("1.1.0"));
+    assert_eq!(device.refresh_rate, Some(900));
+    assert_eq!(device.first_seen_at, Some(1_700_000_000));
+    assert_eq!(device.last_seen_at, Some(1_700_000_900));
+}
+
+pub async fn success_nonexistent_device(repo: DeviceRepo) {
+    repo.update_status(
+        "dev123",
+        1_700_000_000,
+        Some(-70),
+        Some(3.9),
+        Some("1.1.0"),
+        Some(900),
+    )
+    .await
+    .unwrap();
+
+    assert!(repo.list().await.unwrap().is_empty());
+}
@@ -1,7 +1,32 @@
-mod create;
-mod exists_by_mac;
-mod get_by_api_key;
-mod get_by_id;
-mod list;
-mod update_images;
-mod update_status;
+use std::{future::Future, str::FromStr, sync::Arc};
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device::{DeviceRepo, SqliteDeviceRepo},
+};
+
+use super::shared::device_repository_tests;
+
+mod ping;
+mod update_approval_status;
+
+device_repository_tests!();
+
+// Groups are outside the device repository, so the shared cases reference them by id only
+async fn run<F>(case: impl FnOnce(DeviceRepo) -> F)
+where
+    F: Future<Output = ()>,
+{
+    let options = SqliteConnectOptions::from_str("sqlite::memory:")
+        .unwrap()
+        .foreign_keys(false);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .unwrap();
+
+    apply_migrations(&pool).await.unwrap();
+    case(Arc::new(SqliteDeviceRepo::new(Arc::new(pool)))).await;
+}
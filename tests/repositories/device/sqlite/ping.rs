@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device::{DeviceRepository, SqliteDeviceRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn error_closed_pool() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceRepo::new(Arc::new(pool.clone()));
+
+    pool.close().await;
+
+    assert!(repo.ping().await.is_err());
+}
+
+#[tokio::test]
+async fn error_missing_table() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let repo = SqliteDeviceRepo::new(Arc::new(pool));
+
+    assert!(repo.ping().await.is_err());
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::DeviceCommand,
+    repositories::device_command::{DeviceCommandRepository, SqliteDeviceCommandRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_delivered_and_pending() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool));
+
+    for device_id in ["dev123", "dev456"] {
+        repo.enqueue(device_id, &DeviceCommand::ResetFirmware)
+            .await
+            .unwrap();
+    }
+    repo.take_next("dev123").await.unwrap();
+    repo.enqueue("dev123", &DeviceCommand::ResetFirmware)
+        .await
+        .unwrap();
+
+    repo.delete_by_device("dev123").await.unwrap();
+
+    assert!(repo.list("dev123").await.unwrap().is_empty());
+    assert_eq!(repo.list("dev456").await.unwrap().len(), 1);
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::DeviceCommand,
+    repositories::device_command::{DeviceCommandRepository, SqliteDeviceCommandRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool.clone()));
+
+    let command = DeviceCommand::UpdateFirmware {
+        version: "1.6.5".to_string(),
+    };
+
+    let queued = repo.enqueue("dev123", &command).await.unwrap();
+
+    assert_eq!(queued.device_id, "dev123");
+    assert_eq!(queued.command, command);
+    assert!(queued.created_at > 0);
+    assert_eq!(queued.delivered_at, None);
+
+    let record = sqlx::query!(
+        "SELECT command_json FROM device_commands WHERE id = ?",
+        queued.id
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(
+        record.command_json,
+        r#"{"type":"update_firmware","version":"1.6.5"}"#
+    );
+}
+
+#[tokio::test]
+async fn error_unknown_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool));
+
+    let result = repo
+        .enqueue("nonexistent", &DeviceCommand::ResetFirmware)
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::DeviceCommand,
+    repositories::device_command::{DeviceCommandRepository, SqliteDeviceCommandRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_includes_history() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool));
+
+    repo.enqueue("dev123", &DeviceCommand::ResetFirmware)
+        .await
+        .unwrap();
+    repo.take_next("dev123").await.unwrap();
+    repo.enqueue(
+        "dev123",
+        &DeviceCommand::UpdateFirmware {
+            version: "1.6.5".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let commands = repo.list("dev123").await.unwrap();
+
+    assert_eq!(commands.len(), 2);
+    assert_eq!(
+        commands[0].command,
+        DeviceCommand::UpdateFirmware {
+            version: "1.6.5".to_string()
+        }
+    );
+    assert_eq!(commands[0].delivered_at, None);
+    assert_eq!(commands[1].command, DeviceCommand::ResetFirmware);
+    assert!(commands[1].delivered_at.is_some());
+}
+
+#[tokio::test]
+async fn success_other_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool));
+
+    repo.enqueue("dev123", &DeviceCommand::ResetFirmware)
+        .await
+        .unwrap();
+
+    assert!(repo.list("dev456").await.unwrap().is_empty());
+}
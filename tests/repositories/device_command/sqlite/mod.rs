@@ -0,0 +1,4 @@
+mod delete_by_device;
+mod enqueue;
+mod list;
+mod take_next;
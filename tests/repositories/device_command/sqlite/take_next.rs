@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::DeviceCommand,
+    repositories::device_command::{DeviceCommandRepository, SqliteDeviceCommandRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_oldest_first() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool));
+
+    let first = repo
+        .enqueue("dev123", &DeviceCommand::ResetFirmware)
+        .await
+        .unwrap();
+    let second = repo
+        .enqueue(
+            "dev123",
+            &DeviceCommand::ShowImage {
+                image: "https://example.com/image.png".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let taken = repo.take_next("dev123").await.unwrap().unwrap();
+    assert_eq!(taken.id, first.id);
+    assert_eq!(taken.command, DeviceCommand::ResetFirmware);
+    assert!(taken.delivered_at.is_some());
+
+    let taken = repo.take_next("dev123").await.unwrap().unwrap();
+    assert_eq!(taken.id, second.id);
+
+    // Every command is only delivered once
+    assert!(repo.take_next("dev123").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn success_empty_queue() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceCommandRepo::new(Arc::new(pool));
+
+    assert!(repo.take_next("dev123").await.unwrap().is_none());
+}
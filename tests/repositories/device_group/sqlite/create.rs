@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device_group::{DeviceGroupRepository, SqliteDeviceGroupRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    let group = repo.get("group123").await.unwrap().unwrap();
+    assert_eq!(group.name, "Lobby");
+    assert!(group.images.is_empty());
+    assert_eq!(group.desired_refresh_rate, None);
+    assert_eq!(group.target_fw_version, None);
+}
+
+#[tokio::test]
+async fn error_duplicate_id() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    assert!(repo.create("group123", "Kitchen").await.is_err());
+}
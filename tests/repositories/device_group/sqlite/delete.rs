@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device_group::{DeviceGroupRepository, SqliteDeviceGroupRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_existing_group() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    assert!(repo.delete("group123").await.unwrap());
+    assert!(repo.get("group123").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn success_nonexistent_group() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    assert!(!repo.delete("group123").await.unwrap());
+}
+
+#[tokio::test]
+async fn success_unassigns_devices() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    sqlx::query(
+        "INSERT INTO devices (id, mac, api_key, group_id) VALUES ('dev123', 'AA:BB:CC:DD:EE:FF', 'abc123', 'group123')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert!(repo.delete("group123").await.unwrap());
+
+    let group_id: Option<String> =
+        sqlx::query_scalar("SELECT group_id FROM devices WHERE id = 'dev123'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(group_id, None);
+}
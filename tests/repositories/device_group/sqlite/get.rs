@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device_group::{DeviceGroupRepository, SqliteDeviceGroupRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_existing_group() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    let group = repo.get("group123").await.unwrap().unwrap();
+    assert_eq!(group.id, "group123");
+    assert_eq!(group.name, "Lobby");
+    assert!(group.created_at > 0);
+}
+
+#[tokio::test]
+async fn success_nonexistent_group() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.get("group123").await.unwrap().is_none());
+}
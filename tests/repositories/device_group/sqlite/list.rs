@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device_group::{DeviceGroupRepository, SqliteDeviceGroupRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_sorted_by_name() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group1", "Lobby").await.unwrap();
+    repo.create("group2", "Kitchen").await.unwrap();
+
+    let names: Vec<String> = repo
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|group| group.name)
+        .collect();
+    assert_eq!(names, vec!["Kitchen", "Lobby"]);
+}
+
+#[tokio::test]
+async fn success_empty() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.list().await.unwrap().is_empty());
+}
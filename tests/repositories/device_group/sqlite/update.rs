@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::DeviceGroupPatch,
+    repositories::device_group::{DeviceGroupRepository, SqliteDeviceGroupRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    assert!(
+        repo.update(
+            "group123",
+            &DeviceGroupPatch {
+                name: Some("Reception".to_string()),
+                desired_refresh_rate: Some(Some(600)),
+                target_fw_version: Some(Some("1.6.5".to_string())),
+            },
+        )
+        .await
+        .unwrap()
+    );
+
+    let group = repo.get("group123").await.unwrap().unwrap();
+    assert_eq!(group.name, "Reception");
+    assert_eq!(group.desired_refresh_rate, Some(600));
+    assert_eq!(group.target_fw_version.as_deref(), Some("1.6.5"));
+}
+
+#[tokio::test]
+async fn success_partial() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+    repo.update(
+        "group123",
+        &DeviceGroupPatch {
+            desired_refresh_rate: Some(Some(600)),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    repo.update(
+        "group123",
+        &DeviceGroupPatch {
+            desired_refresh_rate: Some(None),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let group = repo.get("group123").await.unwrap().unwrap();
+    assert_eq!(group.name, "Lobby");
+    assert_eq!(group.desired_refresh_rate, None);
+}
+
+#[tokio::test]
+async fn success_nonexistent_group() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    assert!(
+        !repo
+            .update("group123", &DeviceGroupPatch::default())
+            .await
+            .unwrap()
+    );
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::device_group::{DeviceGroupRepository, SqliteDeviceGroupRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    repo.create("group123", "Lobby").await.unwrap();
+
+    let images = vec![
+        "https://example.com/a.png".to_string(),
+        "layout:dashboard".to_string(),
+    ];
+    assert!(repo.update_images("group123", &images).await.unwrap());
+
+    let group = repo.get("group123").await.unwrap().unwrap();
+    assert_eq!(group.images, images);
+}
+
+#[tokio::test]
+async fn success_nonexistent_group() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceGroupRepo::new(Arc::new(pool.clone()));
+
+    assert!(!repo.update_images("group123", &[]).await.unwrap());
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{DeviceLogQuery, NewDeviceLog},
+    repositories::device_log::{DeviceLogRepository, SqliteDeviceLogRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+fn log(created_at: i64, level: &str, message: &str) -> NewDeviceLog {
+    NewDeviceLog {
+        created_at,
+        level: level.to_string(),
+        message: message.to_string(),
+        source_file: Some("src/bl.cpp".to_string()),
+        source_line: Some(585),
+        wake_reason: Some("timer".to_string()),
+        battery_voltage: Some(4.1),
+        wifi_status: Some("connected".to_string()),
+        wifi_rssi: Some(-54),
+        fw_version: Some("1.6.5".to_string()),
+        additional_info: Some(r#"{"retry_attempt":2}"#.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceLogRepo::new(Arc::new(pool.clone()));
+
+    repo.create(
+        "dev123",
+        1_700_000_500,
+        &[
+            log(1_700_000_000, "info", "one"),
+            log(1_700_000_100, "error", "two"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let logs = repo
+        .list("dev123", &DeviceLogQuery::default())
+        .await
+        .unwrap();
+
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0].message, "two");
+    assert_eq!(logs[0].received_at, 1_700_000_500);
+    assert_eq!(logs[0].source_line, Some(585));
+    assert_eq!(logs[0].wifi_rssi, Some(-54));
+    assert_eq!(
+        logs[0].additional_info,
+        Some(serde_json::json!({ "retry_attempt": 2 }))
+    );
+}
+
+#[tokio::test]
+async fn error_unknown_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceLogRepo::new(Arc::new(pool.clone()));
+
+    let result = repo
+        .create(
+            "nonexistent",
+            1_700_000_500,
+            &[log(1_700_000_000, "info", "one")],
+        )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn success_deleted_with_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceLogRepo::new(Arc::new(pool.clone()));
+
+    repo.create(
+        "dev123",
+        1_700_000_500,
+        &[log(1_700_000_000, "info", "one")],
+    )
+    .await
+    .unwrap();
+
+    sqlx::query!("DELETE FROM devices WHERE id = ?", "dev123")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let count = sqlx::query!("SELECT COUNT(*) as count FROM device_logs")
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .count;
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{DeviceLogQuery, NewDeviceLog},
+    repositories::device_log::{DeviceLogRepository, SqliteDeviceLogRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+fn log(message: &str) -> NewDeviceLog {
+    NewDeviceLog {
+        created_at: 1_700_000_000,
+        level: "info".to_string(),
+        message: message.to_string(),
+        source_file: None,
+        source_line: None,
+        wake_reason: None,
+        battery_voltage: None,
+        wifi_status: None,
+        wifi_rssi: None,
+        fw_version: None,
+        additional_info: None,
+    }
+}
+
+#[tokio::test]
+async fn success_only_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceLogRepo::new(Arc::new(pool));
+
+    for device_id in ["dev123", "dev456"] {
+        repo.create(device_id, 1_700_000_000, &[log("Woke up from timer")])
+            .await
+            .unwrap();
+    }
+
+    repo.delete_by_device("dev123").await.unwrap();
+
+    let query = DeviceLogQuery::default();
+    assert!(repo.list("dev123", &query).await.unwrap().is_empty());
+    assert_eq!(repo.list("dev456", &query).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn success_no_logs() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceLogRepo::new(Arc::new(pool));
+
+    repo.delete_by_device("dev123").await.unwrap();
+}
@@ -0,0 +1,204 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::{DeviceLogQuery, NewDeviceLog},
+    repositories::device_log::{DeviceLogRepository, SqliteDeviceLogRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+fn log(created_at: i64, level: &str, message: &str) -> NewDeviceLog {
+    NewDeviceLog {
+        created_at,
+        level: level.to_string(),
+        message: message.to_string(),
+        source_file: Some("src/bl.cpp".to_string()),
+        source_line: Some(585),
+        wake_reason: Some("timer".to_string()),
+        battery_voltage: Some(4.1),
+        wifi_status: Some("connected".to_string()),
+        wifi_rssi: Some(-54),
+        fw_version: Some("1.6.5".to_string()),
+        additional_info: Some(r#"{"retry_attempt":2}"#.to_string()),
+    }
+}
+
+async fn seeded_repo() -> SqliteDeviceLogRepo {
+    let pool = connect().await.unwrap();
+    let repo = SqliteDeviceLogRepo::new(Arc::new(pool.clone()));
+
+    repo.create(
+        "dev123",
+        1_700_000_500,
+        &[
+            log(1_700_000_000, "info", "Woke up from timer"),
+            log(1_700_000_100, "error", "WiFi connect failed"),
+            log(1_700_000_200, "info", "Image downloaded"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    repo
+}
+
+#[tokio::test]
+async fn success_newest_first() {
+    let repo = seeded_repo().await;
+
+    let logs = repo
+        .list("dev123", &DeviceLogQuery::default())
+        .await
+        .unwrap();
+
+    let messages: Vec<_> = logs.iter().map(|log| log.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Image downloaded",
+            "WiFi connect failed",
+            "Woke up from timer"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn success_time_range() {
+    let repo = seeded_repo().await;
+
+    let logs = repo
+        .list(
+            "dev123",
+            &DeviceLogQuery {
+                from: Some(1_700_000_050),
+                to: Some(1_700_000_150),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "WiFi connect failed");
+}
+
+#[tokio::test]
+async fn success_level_and_text() {
+    let repo = seeded_repo().await;
+
+    let logs = repo
+        .list(
+            "dev123",
+            &DeviceLogQuery {
+                level: Some("info".to_string()),
+                q: Some("download".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "Image downloaded");
+}
+
+#[tokio::test]
+async fn success_level_any_case() {
+    let repo = seeded_repo().await;
+
+    let logs = repo
+        .list(
+            "dev123",
+            &DeviceLogQuery {
+                level: Some("ERROR".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "WiFi connect failed");
+}
+
+#[tokio::test]
+async fn success_text_wildcards_literal() {
+    let repo = seeded_repo().await;
+    repo.create(
+        "dev123",
+        1_700_000_500,
+        &[
+            log(1_700_000_300, "info", "Battery at 50% after wake_up"),
+            log(1_700_000_400, "info", "Battery at 505 after wakeXup"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    for q in ["50%", "wake_up"] {
+        let logs = repo
+            .list(
+                "dev123",
+                &DeviceLogQuery {
+                    q: Some(q.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(logs.len(), 1, "{q}");
+        assert_eq!(logs[0].message, "Battery at 50% after wake_up");
+    }
+}
+
+#[tokio::test]
+async fn success_limit() {
+    let repo = seeded_repo().await;
+
+    let logs = repo
+        .list(
+            "dev123",
+            &DeviceLogQuery {
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(logs.len(), 2);
+}
+
+#[tokio::test]
+async fn success_other_device() {
+    let repo = seeded_repo().await;
+
+    let logs = repo
+        .list("dev456", &DeviceLogQuery::default())
+        .await
+        .unwrap();
+
+    assert!(logs.is_empty());
+}
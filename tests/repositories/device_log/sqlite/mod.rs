@@ -0,0 +1,3 @@
+mod create;
+mod delete_by_device;
+mod list;
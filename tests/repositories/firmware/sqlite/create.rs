@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::firmware::{FirmwareRepository, SqliteFirmwareRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.6.5", "abc123", b"firmware").await.unwrap();
+
+    let record = sqlx::query!(
+        "SELECT version, checksum, size, data FROM firmware WHERE version = ?",
+        "1.6.5"
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(record.version, "1.6.5");
+    assert_eq!(record.checksum, "abc123");
+    assert_eq!(record.size, 8);
+    assert_eq!(record.data, b"firmware");
+}
+
+#[tokio::test]
+async fn error_duplicate_version() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.6.5", "abc123", b"firmware").await.unwrap();
+    let result = repo.create("1.6.5", "def456", b"other").await;
+
+    assert!(result.is_err());
+}
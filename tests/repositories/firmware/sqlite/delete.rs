@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::firmware::{FirmwareRepository, SqliteFirmwareRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_existing_release() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.6.5", "abc123", b"firmware").await.unwrap();
+
+    assert!(repo.delete("1.6.5").await.unwrap());
+    assert!(repo.get("1.6.5").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn success_nonexistent_release() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    assert!(!repo.delete("1.6.5").await.unwrap());
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::firmware::{FirmwareRepository, SqliteFirmwareRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.6.5", "abc123", b"firmware").await.unwrap();
+
+    let firmware = repo.get("1.6.5").await.unwrap().unwrap();
+
+    assert_eq!(firmware.version, "1.6.5");
+    assert_eq!(firmware.checksum, "abc123");
+    assert_eq!(firmware.size, 8);
+    assert!(firmware.created_at > 0);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    let firmware = repo.get("1.6.5").await.unwrap();
+    assert!(firmware.is_none());
+}
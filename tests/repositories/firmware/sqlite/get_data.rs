@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::firmware::{FirmwareRepository, SqliteFirmwareRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.6.5", "abc123", b"firmware").await.unwrap();
+
+    let data = repo.get_data("1.6.5").await.unwrap().unwrap();
+    assert_eq!(data, b"firmware");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    let data = repo.get_data("1.6.5").await.unwrap();
+    assert!(data.is_none());
+}
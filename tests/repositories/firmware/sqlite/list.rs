@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::firmware::{FirmwareRepository, SqliteFirmwareRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_multiple_releases() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.6.4", "abc123", b"old").await.unwrap();
+    repo.create("1.6.5", "def456", b"new").await.unwrap();
+
+    let list = repo.list().await.unwrap();
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].version, "1.6.5");
+    assert_eq!(list[1].version, "1.6.4");
+}
+
+#[tokio::test]
+async fn success_sorted_by_semver() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    repo.create("1.9.0", "abc123", b"old").await.unwrap();
+    repo.create("1.10.0", "def456", b"new").await.unwrap();
+    repo.create("1.2.0", "ghi789", b"older").await.unwrap();
+
+    sqlx::query("UPDATE firmware SET created_at = 1700000000")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let list = repo.list().await.unwrap();
+
+    let versions: Vec<_> = list
+        .iter()
+        .map(|firmware| firmware.version.as_str())
+        .collect();
+    assert_eq!(versions, vec!["1.10.0", "1.9.0", "1.2.0"]);
+}
+
+#[tokio::test]
+async fn success_empty_table() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteFirmwareRepo::new(Arc::new(pool.clone()));
+
+    let list = repo.list().await.unwrap();
+    assert!(list.is_empty());
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::{apply_migrations, latest_migration_version},
+    repositories::health::{HealthRepository, SqliteHealthRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_latest() {
+    let pool = connect().await.unwrap();
+    apply_migrations(&pool).await.unwrap();
+    let repo = SqliteHealthRepo::new(Arc::new(pool.clone()));
+
+    let applied = repo.applied_migration().await.unwrap();
+
+    assert!(applied.is_some());
+    assert_eq!(applied, latest_migration_version());
+}
+
+#[tokio::test]
+async fn error_never_migrated() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteHealthRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.applied_migration().await.is_err());
+}
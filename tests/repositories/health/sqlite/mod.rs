@@ -0,0 +1,2 @@
+mod applied_migration;
+mod ping;
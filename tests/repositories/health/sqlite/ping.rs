@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::repositories::health::{HealthRepository, SqliteHealthRepo};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteHealthRepo::new(Arc::new(pool.clone()));
+
+    repo.ping().await.unwrap();
+}
+
+#[tokio::test]
+async fn error_closed_pool() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteHealthRepo::new(Arc::new(pool.clone()));
+
+    pool.close().await;
+
+    assert!(repo.ping().await.is_err());
+}
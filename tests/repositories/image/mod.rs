@@ -0,0 +1 @@
+mod sqlite;
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::image::{ImageRepository, SqliteImageRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    repo.create("abc123", "image/bmp", b"BMdata").await.unwrap();
+
+    let record = sqlx::query!(
+        "SELECT content_type, size, data FROM images WHERE id = ?",
+        "abc123"
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(record.content_type, "image/bmp");
+    assert_eq!(record.size, 6);
+    assert_eq!(record.data, b"BMdata");
+}
+
+#[tokio::test]
+async fn success_duplicate_is_noop() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    repo.create("abc123", "image/bmp", b"BMdata").await.unwrap();
+    repo.create("abc123", "image/bmp", b"BMdata").await.unwrap();
+
+    assert_eq!(repo.list().await.unwrap().len(), 1);
+}
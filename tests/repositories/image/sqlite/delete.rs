@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::image::{ImageRepository, SqliteImageRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_existing_image() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    repo.create("abc123", "image/bmp", b"BMdata").await.unwrap();
+
+    assert!(repo.delete("abc123").await.unwrap());
+    assert!(repo.get("abc123").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn success_nonexistent_image() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    assert!(!repo.delete("abc123").await.unwrap());
+}
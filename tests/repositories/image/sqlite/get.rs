@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::image::{ImageRepository, SqliteImageRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    repo.create("abc123", "image/png", b"png").await.unwrap();
+
+    let image = repo.get("abc123").await.unwrap().unwrap();
+
+    assert_eq!(image.id, "abc123");
+    assert_eq!(image.content_type, "image/png");
+    assert_eq!(image.size, 3);
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.get("abc123").await.unwrap().is_none());
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::image::{ImageRepository, SqliteImageRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    repo.create("abc123", "image/bmp", b"BMdata").await.unwrap();
+
+    let content = repo.get_content("abc123").await.unwrap().unwrap();
+
+    assert_eq!(content.content_type, "image/bmp");
+    assert_eq!(content.data, b"BMdata");
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.get_content("abc123").await.unwrap().is_none());
+}
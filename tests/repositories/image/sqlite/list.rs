@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    repositories::image::{ImageRepository, SqliteImageRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_multiple_images() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    repo.create("abc", "image/bmp", b"BMone").await.unwrap();
+    repo.create("def", "image/png", b"png").await.unwrap();
+
+    let list = repo.list().await.unwrap();
+
+    assert_eq!(list.len(), 2);
+    assert!(list.iter().any(|image| image.id == "abc"));
+    assert!(list.iter().any(|image| image.id == "def"));
+}
+
+#[tokio::test]
+async fn success_empty_table() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteImageRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.list().await.unwrap().is_empty());
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    layout::LayoutSpec,
+    repositories::layout::{LayoutRepository, SqliteLayoutRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+fn spec(text: &str) -> LayoutSpec {
+    serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": text }]
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    repo.create("layout123", "Dashboard", &spec("Hello"))
+        .await
+        .unwrap();
+
+    let record = sqlx::query!(
+        "SELECT name, spec_json FROM layouts WHERE id = ?",
+        "layout123"
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(record.name, "Dashboard");
+    assert!(record.spec_json.contains("Hello"));
+}
+
+#[tokio::test]
+async fn error_duplicate_id() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    repo.create("layout123", "Dashboard", &spec("Hello"))
+        .await
+        .unwrap();
+
+    assert!(
+        repo.create("layout123", "Other", &spec("Hello"))
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    layout::LayoutSpec,
+    repositories::layout::{LayoutRepository, SqliteLayoutRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+fn spec(text: &str) -> LayoutSpec {
+    serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": text }]
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn success_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    repo.create("layout123", "Dashboard", &spec("Hello"))
+        .await
+        .unwrap();
+
+    let layout = repo.get("layout123").await.unwrap().unwrap();
+
+    assert_eq!(layout.name, "Dashboard");
+    assert_eq!(layout.spec, spec("Hello"));
+}
+
+#[tokio::test]
+async fn success_not_found() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.get("layout123").await.unwrap().is_none());
+}
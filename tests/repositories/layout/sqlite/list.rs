@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    layout::LayoutSpec,
+    repositories::layout::{LayoutRepository, SqliteLayoutRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+fn spec(text: &str) -> LayoutSpec {
+    serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": text }]
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn success_sorted_by_name() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    repo.create("layout1", "Weather", &spec("Sunny"))
+        .await
+        .unwrap();
+    repo.create("layout2", "Calendar", &spec("Monday"))
+        .await
+        .unwrap();
+
+    let list = repo.list().await.unwrap();
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].name, "Calendar");
+    assert_eq!(list[1].name, "Weather");
+}
+
+#[tokio::test]
+async fn success_empty_table() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    assert!(repo.list().await.unwrap().is_empty());
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    layout::LayoutSpec,
+    repositories::layout::{LayoutRepository, SqliteLayoutRepo},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+    Ok(pool)
+}
+
+fn spec(text: &str) -> LayoutSpec {
+    serde_json::from_value(serde_json::json!({
+        "elements": [{ "type": "text", "x": 0, "y": 0, "text": text }]
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn success_existing_layout() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    repo.create("layout123", "Dashboard", &spec("Hello"))
+        .await
+        .unwrap();
+
+    assert!(
+        repo.update("layout123", "Renamed", &spec("Goodbye"))
+            .await
+            .unwrap()
+    );
+
+    let layout = repo.get("layout123").await.unwrap().unwrap();
+
+    assert_eq!(layout.name, "Renamed");
+    assert_eq!(layout.spec, spec("Goodbye"));
+}
+
+#[tokio::test]
+async fn success_nonexistent_layout() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteLayoutRepo::new(Arc::new(pool.clone()));
+
+    assert!(
+        !repo
+            .update("layout123", "Renamed", &spec("Goodbye"))
+            .await
+            .unwrap()
+    );
+}
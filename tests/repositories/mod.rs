@@ -1 +1,11 @@
+mod alert;
+mod api_token;
 mod device;
+mod device_command;
+mod device_group;
+mod device_log;
+mod firmware;
+mod health;
+mod image;
+mod layout;
+mod telemetry;
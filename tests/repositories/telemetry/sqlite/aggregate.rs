@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::TelemetrySample,
+    repositories::telemetry::{SqliteTelemetryRepo, TelemetryRepository},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+fn sample(recorded_at: i64, rssi: Option<i64>, battery_voltage: Option<f64>) -> TelemetrySample {
+    TelemetrySample {
+        recorded_at,
+        rssi,
+        battery_voltage,
+        fw_version: Some("1.6.5".to_string()),
+        refresh_rate: Some(900),
+    }
+}
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+#[tokio::test]
+async fn success_buckets() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool.clone()));
+
+    // Two readings in the first hour, one in the second
+    for (recorded_at, rssi, battery_voltage) in [
+        (3_600, Some(-60), Some(4.0)),
+        (5_400, Some(-70), Some(3.8)),
+        (7_200, None, Some(3.7)),
+    ] {
+        repo.record("dev123", &sample(recorded_at, rssi, battery_voltage))
+            .await
+            .unwrap();
+    }
+
+    let buckets = repo.aggregate("dev123", 0, 10_000, 3_600).await.unwrap();
+
+    assert_eq!(buckets.len(), 2);
+
+    assert_eq!(buckets[0].start, 3_600);
+    assert_eq!(buckets[0].samples, 2);
+    let rssi = buckets[0].rssi.as_ref().unwrap();
+    assert!(approx_eq(rssi.min, -70.0));
+    assert!(approx_eq(rssi.avg, -65.0));
+    assert!(approx_eq(rssi.max, -60.0));
+    let battery_voltage = buckets[0].battery_voltage.as_ref().unwrap();
+    assert!(approx_eq(battery_voltage.avg, 3.9));
+
+    assert_eq!(buckets[1].start, 7_200);
+    assert_eq!(buckets[1].samples, 1);
+    assert!(buckets[1].rssi.is_none());
+}
+
+#[tokio::test]
+async fn success_empty() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool.clone()));
+
+    let buckets = repo.aggregate("dev123", 0, 10_000, 3_600).await.unwrap();
+    assert!(buckets.is_empty());
+}
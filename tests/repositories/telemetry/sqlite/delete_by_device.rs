@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::TelemetrySample,
+    repositories::telemetry::{SqliteTelemetryRepo, TelemetryRepository},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    for (id, api_key) in [("dev123", "apikey123"), ("dev456", "apikey456")] {
+        sqlx::query!(
+            "INSERT INTO devices (id, api_key, images_json) VALUES (?, ?, ?)",
+            id,
+            api_key,
+            "[]"
+        )
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(pool)
+}
+
+#[tokio::test]
+async fn success_only_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool));
+
+    let sample = TelemetrySample {
+        recorded_at: 1_700_000_000,
+        rssi: Some(-60),
+        battery_voltage: Some(3.9),
+        fw_version: Some("1.6.5".to_string()),
+        refresh_rate: Some(900),
+    };
+
+    for device_id in ["dev123", "dev456"] {
+        repo.record(device_id, &sample).await.unwrap();
+    }
+
+    repo.delete_by_device("dev123").await.unwrap();
+
+    assert!(repo.list("dev123", 0, i64::MAX).await.unwrap().is_empty());
+    assert_eq!(repo.list("dev456", 0, i64::MAX).await.unwrap().len(), 1);
+}
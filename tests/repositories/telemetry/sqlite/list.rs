@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::TelemetrySample,
+    repositories::telemetry::{SqliteTelemetryRepo, TelemetryRepository},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+fn sample(recorded_at: i64, rssi: Option<i64>, battery_voltage: Option<f64>) -> TelemetrySample {
+    TelemetrySample {
+        recorded_at,
+        rssi,
+        battery_voltage,
+        fw_version: Some("1.6.5".to_string()),
+        refresh_rate: Some(900),
+    }
+}
+
+#[tokio::test]
+async fn success_range() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool.clone()));
+
+    for (recorded_at, rssi) in [
+        (1_700_000_000, -60),
+        (1_700_000_900, -65),
+        (1_700_001_800, -70),
+    ] {
+        repo.record("dev123", &sample(recorded_at, Some(rssi), Some(3.9)))
+            .await
+            .unwrap();
+    }
+
+    let samples = repo
+        .list("dev123", 1_700_000_500, 1_700_001_800)
+        .await
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0], sample(1_700_000_900, Some(-65), Some(3.9)));
+    assert_eq!(samples[1].recorded_at, 1_700_001_800);
+}
+
+#[tokio::test]
+async fn success_empty() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool.clone()));
+
+    let samples = repo.list("dev123", 0, i64::MAX).await.unwrap();
+    assert!(samples.is_empty());
+}
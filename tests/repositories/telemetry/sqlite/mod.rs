@@ -0,0 +1,4 @@
+mod aggregate;
+mod delete_by_device;
+mod list;
+mod record;
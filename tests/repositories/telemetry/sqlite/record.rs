@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use trmnl_server::{
+    db::apply_migrations,
+    models::TelemetrySample,
+    repositories::telemetry::{SqliteTelemetryRepo, TelemetryRepository},
+};
+
+async fn connect() -> anyhow::Result<SqlitePool> {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    apply_migrations(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO devices (id, mac, api_key, images_json) VALUES (?, ?, ?, ?)",
+        "dev123",
+        Some("AA:BB:CC:DD:EE:FF"),
+        "apikey123",
+        "[]"
+    )
+    .execute(&pool)
+    .await?;
+
+    Ok(pool)
+}
+
+fn sample(recorded_at: i64, rssi: Option<i64>, battery_voltage: Option<f64>) -> TelemetrySample {
+    TelemetrySample {
+        recorded_at,
+        rssi,
+        battery_voltage,
+        fw_version: Some("1.6.5".to_string()),
+        refresh_rate: Some(900),
+    }
+}
+
+#[tokio::test]
+async fn success_appends() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool.clone()));
+
+    repo.record("dev123", &sample(1_700_000_000, Some(-60), Some(3.9)))
+        .await
+        .unwrap();
+    repo.record("dev123", &sample(1_700_000_900, Some(-65), Some(3.8)))
+        .await
+        .unwrap();
+
+    let count = sqlx::query!("SELECT COUNT(*) as count FROM device_telemetry")
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .count;
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn error_unknown_device() {
+    let pool = connect().await.unwrap();
+    let repo = SqliteTelemetryRepo::new(Arc::new(pool.clone()));
+
+    let result = repo
+        .record("nonexistent", &sample(1_700_000_000, Some(-60), Some(3.9)))
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,222 @@
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use trmnl_server::schedule::{
+    QuietHours, ScheduleRule, TimeOfDay, Weekday, active_rotation, local_time, parse_timezone,
+    validate_schedule,
+};
+
+fn time(s: &str) -> TimeOfDay {
+    s.parse().unwrap()
+}
+
+fn rule(weekdays: Vec<Weekday>, start: &str, end: &str, image: &str) -> ScheduleRule {
+    ScheduleRule {
+        weekdays,
+        start: time(start),
+        end: time(end),
+        images: vec![image.to_string()],
+    }
+}
+
+fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn time_of_day_round_trips() {
+    assert_eq!(time("09:30").minutes(), 570);
+    assert_eq!(time("23:59").to_string(), "23:59");
+    assert_eq!(serde_json::to_string(&time("07:05")).unwrap(), r#""07:05""#);
+}
+
+#[test]
+fn time_of_day_rejects_invalid() {
+    for invalid in ["24:00", "12:60", "9:30", "0930", "ab:cd", ""] {
+        assert!(invalid.parse::<TimeOfDay>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn rule_deserializes_weekdays() {
+    let rule: ScheduleRule = serde_json::from_str(
+        r#"{"weekdays": ["mon", "fri"], "start": "09:00", "end": "17:00", "images": ["a"]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(rule.weekdays, vec![Weekday::Mon, Weekday::Fri]);
+    assert!(
+        serde_json::from_str::<ScheduleRule>(
+            r#"{"weekdays": ["monday"], "start": "09:00", "end": "17:00", "images": ["a"]}"#
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn daytime_window() {
+    let rule = rule(vec![Weekday::Mon], "09:00", "17:00", "a");
+
+    assert!(rule.is_active(Weekday::Mon, time("09:00")));
+    assert!(rule.is_active(Weekday::Mon, time("16:59")));
+    assert!(!rule.is_active(Weekday::Mon, time("17:00")));
+    assert!(!rule.is_active(Weekday::Mon, time("08:59")));
+    assert!(!rule.is_active(Weekday::Tue, time("12:00")));
+}
+
+#[test]
+fn overnight_window_belongs_to_start_day() {
+    let rule = rule(vec![Weekday::Fri], "22:00", "06:00", "a");
+
+    assert!(rule.is_active(Weekday::Fri, time("23:00")));
+    assert!(rule.is_active(Weekday::Sat, time("05:59")));
+    assert!(!rule.is_active(Weekday::Sat, time("06:00")));
+    assert!(!rule.is_active(Weekday::Fri, time("05:00")));
+    assert!(!rule.is_active(Weekday::Sat, time("23:00")));
+}
+
+#[test]
+fn all_day_window() {
+    let rule = rule(vec![], "00:00", "00:00", "a");
+
+    assert!(rule.is_active(Weekday::Sun, time("00:00")));
+    assert!(rule.is_active(Weekday::Wed, time("13:37")));
+}
+
+#[test]
+fn local_time_uses_timezone() {
+    let amsterdam: Tz = parse_timezone("Europe/Amsterdam").unwrap();
+
+    // Summer time, UTC+2
+    assert_eq!(
+        local_time(utc(2025, 7, 7, 7, 30), amsterdam),
+        (Weekday::Mon, time("09:30"))
+    );
+
+    // The date changes crossing midnight
+    assert_eq!(
+        local_time(utc(2025, 7, 6, 23, 0), amsterdam),
+        (Weekday::Mon, time("01:00"))
+    );
+
+    assert!(parse_timezone("Mars/Olympus_Mons").is_none());
+}
+
+#[test]
+fn first_matching_rule_wins() {
+    let rules = vec![
+        rule(
+            vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            "08:00",
+            "18:00",
+            "meetings",
+        ),
+        rule(vec![], "00:00", "00:00", "photos"),
+    ];
+
+    // Monday 10:00 in Amsterdam
+    assert_eq!(
+        active_rotation(&rules, Tz::Europe__Amsterdam, utc(2025, 7, 7, 8, 0)),
+        Some(&["meetings".to_string()][..])
+    );
+
+    // Monday 20:00 in Amsterdam
+    assert_eq!(
+        active_rotation(&rules, Tz::Europe__Amsterdam, utc(2025, 7, 7, 18, 0)),
+        Some(&["photos".to_string()][..])
+    );
+}
+
+#[test]
+fn no_matching_rule() {
+    let rules = vec![rule(vec![Weekday::Sat], "10:00", "12:00", "a")];
+
+    assert_eq!(
+        active_rotation(&rules, Tz::UTC, utc(2025, 7, 7, 11, 0)),
+        None
+    );
+    assert_eq!(active_rotation(&[], Tz::UTC, utc(2025, 7, 7, 11, 0)), None);
+}
+
+#[test]
+fn validate() {
+    assert!(validate_schedule(&[rule(vec![], "09:00", "17:00", "a")]).is_ok());
+
+    let mut empty = rule(vec![], "09:00", "17:00", "a");
+    empty.images.clear();
+    assert!(validate_schedule(&[empty]).is_err());
+
+    let too_many = vec![rule(vec![], "09:00", "17:00", "a"); 65];
+    assert!(validate_schedule(&too_many).is_err());
+}
+
+fn quiet(start: &str, end: &str) -> QuietHours {
+    QuietHours {
+        start: time(start),
+        end: time(end),
+        sleep_screen: false,
+    }
+}
+
+#[test]
+fn quiet_hours_overnight() {
+    let quiet_hours = quiet("22:00", "06:30");
+
+    assert_eq!(
+        quiet_hours.remaining(Tz::UTC, utc(2025, 7, 7, 22, 0)),
+        Some(8 * 3600 + 1800)
+    );
+    assert_eq!(
+        quiet_hours.remaining(Tz::UTC, utc(2025, 7, 8, 6, 0)),
+        Some(1800)
+    );
+    assert_eq!(quiet_hours.remaining(Tz::UTC, utc(2025, 7, 8, 6, 30)), None);
+    assert_eq!(
+        quiet_hours.remaining(Tz::UTC, utc(2025, 7, 7, 21, 59)),
+        None
+    );
+}
+
+#[test]
+fn quiet_hours_daytime() {
+    let quiet_hours = quiet("01:00", "05:00");
+
+    assert_eq!(
+        quiet_hours.remaining(Tz::UTC, utc(2025, 7, 7, 3, 15)),
+        Some(6300)
+    );
+    assert_eq!(quiet_hours.remaining(Tz::UTC, utc(2025, 7, 7, 0, 59)), None);
+}
+
+#[test]
+fn quiet_hours_use_timezone() {
+    let quiet_hours = quiet("22:00", "07:00");
+    let amsterdam = parse_timezone("Europe/Amsterdam").unwrap();
+
+    // 21:00 UTC is 23:00 in Amsterdam during summer time
+    assert_eq!(
+        quiet_hours.remaining(amsterdam, utc(2025, 7, 7, 21, 0)),
+        Some(8 * 3600)
+    );
+    assert_eq!(quiet_hours.remaining(Tz::UTC, utc(2025, 7, 7, 21, 0)), None);
+}
+
+#[test]
+fn quiet_hours_validate() {
+    assert!(quiet("22:00", "06:00").validate().is_ok());
+    assert!(quiet("22:00", "22:00").validate().is_err());
+}
+
+#[test]
+fn quiet_hours_sleep_screen_defaults_to_off() {
+    let quiet_hours: QuietHours =
+        serde_json::from_str(r#"{"start": "22:00", "end": "06:00"}"#).unwrap();
+
+    assert_eq!(quiet_hours, quiet("22:00", "06:00"));
+}
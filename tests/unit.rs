@@ -1 +1,11 @@
+mod alerts;
+mod config;
+mod convert;
+mod flows;
 mod handlers;
+mod layers;
+mod layout;
+mod models;
+mod notifier;
+mod otel;
+mod schedule;